@@ -212,7 +212,12 @@ impl ParseableParametrized<[u8], u16> for RouteNetlinkMessage {
             | RTM_DELTFILTER | RTM_GETTFILTER | RTM_NEWCHAIN | RTM_DELCHAIN
             | RTM_GETCHAIN => {
                 let err = "invalid tc message";
-                let msg = TcMessage::parse(buf).context(err)?;
+                let msg = match message_type {
+                    RTM_NEWTCLASS | RTM_DELTCLASS | RTM_GETTCLASS => {
+                        TcMessage::parse_class(buf).context(err)?
+                    }
+                    _ => TcMessage::parse(buf).context(err)?,
+                };
                 match message_type {
                     RTM_NEWQDISC => {
                         RouteNetlinkMessage::NewQueueDiscipline(msg)
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT
+
+const TCA_ACT_FLAGS_NO_PERCPU_STATS: u32 = 1 << 0;
+const TCA_ACT_FLAGS_SKIP_HW: u32 = 1 << 1;
+const TCA_ACT_FLAGS_SKIP_SW: u32 = 1 << 2;
+
+bitflags! {
+    /// Flags of an action, in the TCA_ACT_FLAGS attribute.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+    #[non_exhaustive]
+    pub struct TcActionFlags: u32 {
+        /// Do not use the per-CPU allocator for the action statistics.
+        const NoPercpuStats = TCA_ACT_FLAGS_NO_PERCPU_STATS;
+        /// Do not offload the action to hardware.
+        const SkipHw = TCA_ACT_FLAGS_SKIP_HW;
+        /// Only run the action in hardware.
+        const SkipSw = TCA_ACT_FLAGS_SKIP_SW;
+        const _ = !0;
+    }
+}
+
+const TCA_ACT_HW_STATS_IMMEDIATE: u32 = 1 << 0;
+const TCA_ACT_HW_STATS_DELAYED: u32 = 1 << 1;
+
+bitflags! {
+    /// Types of hardware statistics of an offloaded action, in the
+    /// TCA_ACT_HW_STATS and TCA_ACT_USED_HW_STATS attributes. No flag
+    /// means the hardware statistics are disabled.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+    #[non_exhaustive]
+    pub struct TcActionHwStats: u32 {
+        /// Statistics are read from the hardware on every dump.
+        const Immediate = TCA_ACT_HW_STATS_IMMEDIATE;
+        /// Statistics are periodically synchronized from the hardware.
+        const Delayed = TCA_ACT_HW_STATS_DELAYED;
+        /// Any type the driver supports, which is the kernel default.
+        const Any = TCA_ACT_HW_STATS_IMMEDIATE | TCA_ACT_HW_STATS_DELAYED;
+        const _ = !0;
+    }
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MIT
 
-use std::mem::size_of;
+use std::{mem::size_of, time::Duration};
 
 use netlink_packet_core::{
     emit_u32, parse_string, parse_u32, DecodeError, DefaultNla, Emitable,
@@ -10,8 +10,17 @@ use netlink_packet_core::{
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 
 use super::{
-    TcActionMirror, TcActionMirrorOption, TcActionNat, TcActionNatOption,
-    TcActionTunnelKey, TcActionTunnelKeyOption,
+    TcActionBpf, TcActionBpfOption, TcActionConnmark, TcActionConnmarkOption,
+    TcActionCsum, TcActionCsumOption, TcActionCt, TcActionCtInfo,
+    TcActionCtInfoOption, TcActionCtOption, TcActionFlags, TcActionGact,
+    TcActionGactOption, TcActionGate, TcActionGateOption, TcActionHwStats,
+    TcActionIfe, TcActionIfeOption, TcActionMirror, TcActionMirrorOption,
+    TcActionMpls, TcActionMplsOption, TcActionNat, TcActionNatOption,
+    TcActionPedit, TcActionPeditOption, TcActionPolice, TcActionPoliceOption,
+    TcActionSample, TcActionSampleOption, TcActionSkbEdit,
+    TcActionSkbEditOption, TcActionSkbMod, TcActionSkbModOption,
+    TcActionTunnelKey, TcActionTunnelKeyOption, TcActionVlan,
+    TcActionVlanOption,
 };
 use crate::tc::TcStats2;
 
@@ -118,9 +127,9 @@ const TCA_ACT_INDEX: u16 = 3;
 const TCA_ACT_STATS: u16 = 4;
 // const TCA_ACT_PAD: u16 = 5;
 const TCA_ACT_COOKIE: u16 = 6;
-// const TCA_ACT_FLAGS: u16 = 7;
-// const TCA_ACT_HW_STATS: u16 = 8;
-// const TCA_ACT_USED_HW_STATS: u16 = 9;
+const TCA_ACT_FLAGS: u16 = 7;
+const TCA_ACT_HW_STATS: u16 = 8;
+const TCA_ACT_USED_HW_STATS: u16 = 9;
 const TCA_ACT_IN_HW_COUNT: u16 = 10;
 
 /// Attributes of a traffic control action.
@@ -168,7 +177,23 @@ pub enum TcActionAttribute {
     ///
     /// [`Cookie`]: #variant.Cookie
     Cookie(Vec<u8>),
-    /// Number of times the action has been installed in hardware.
+    /// Flags of the action.
+    ///
+    /// Encoded as a `nla_bitfield32` whose selector is the flags
+    /// themselves, like the kernel dumps it. Only the bits set in both the
+    /// value and the selector are parsed, so a request whose selector
+    /// differs from its value does not round-trip.
+    Flags(TcActionFlags),
+    /// Types of hardware statistics allowed for the action.
+    ///
+    /// Only dumped by the kernel when not [`TcActionHwStats::Any`].
+    /// Encoded as a `nla_bitfield32` whose selector is
+    /// [`TcActionHwStats::Any`], like the kernel dumps it.
+    HwStats(TcActionHwStats),
+    /// Types of hardware statistics actually used by the drivers the
+    /// action is offloaded to.
+    UsedHwStats(TcActionHwStats),
+    /// Number of hardware devices the action is offloaded to.
     InHwCount(u32),
     /// Other attributes unknown at the time of writing or not yet supported by
     /// this library.
@@ -182,6 +207,7 @@ impl Nla for TcActionAttribute {
             Self::Kind(k) => k.len() + 1,
             Self::Options(opt) => opt.as_slice().buffer_len(),
             Self::Index(_) | Self::InHwCount(_) => 4,
+            Self::Flags(_) | Self::HwStats(_) | Self::UsedHwStats(_) => 8,
             Self::Stats(s) => s.as_slice().buffer_len(),
             Self::Other(attr) => attr.value_len(),
         }
@@ -197,6 +223,15 @@ impl Nla for TcActionAttribute {
             Self::Index(value) | Self::InHwCount(value) => {
                 emit_u32(buffer, *value).unwrap();
             }
+            Self::Flags(flags) => {
+                emit_u32(&mut buffer[..4], flags.bits()).unwrap();
+                emit_u32(&mut buffer[4..8], flags.bits()).unwrap();
+            }
+            Self::HwStats(v) | Self::UsedHwStats(v) => {
+                emit_u32(&mut buffer[..4], v.bits()).unwrap();
+                emit_u32(&mut buffer[4..8], TcActionHwStats::Any.bits())
+                    .unwrap();
+            }
             Self::Stats(s) => s.as_slice().emit(buffer),
             Self::Other(attr) => attr.emit_value(buffer),
         }
@@ -208,6 +243,9 @@ impl Nla for TcActionAttribute {
             Self::Index(_) => TCA_ACT_INDEX,
             Self::Stats(_) => TCA_ACT_STATS,
             Self::Cookie(_) => TCA_ACT_COOKIE,
+            Self::Flags(_) => TCA_ACT_FLAGS,
+            Self::HwStats(_) => TCA_ACT_HW_STATS,
+            Self::UsedHwStats(_) => TCA_ACT_USED_HW_STATS,
             Self::InHwCount(_) => TCA_ACT_IN_HW_COUNT,
             Self::Other(nla) => nla.kind(),
         }
@@ -254,6 +292,24 @@ where
                     .collect::<Result<Vec<_>, _>>()?,
             ),
             TCA_ACT_COOKIE => TcActionAttribute::Cookie(buf.value().to_vec()),
+            TCA_ACT_FLAGS => {
+                TcActionAttribute::Flags(TcActionFlags::from_bits_retain(
+                    parse_bitfield32(buf.value())
+                        .context("failed to parse TCA_ACT_FLAGS")?,
+                ))
+            }
+            TCA_ACT_HW_STATS => {
+                TcActionAttribute::HwStats(TcActionHwStats::from_bits_retain(
+                    parse_bitfield32(buf.value())
+                        .context("failed to parse TCA_ACT_HW_STATS")?,
+                ))
+            }
+            TCA_ACT_USED_HW_STATS => TcActionAttribute::UsedHwStats(
+                TcActionHwStats::from_bits_retain(
+                    parse_bitfield32(buf.value())
+                        .context("failed to parse TCA_ACT_USED_HW_STATS")?,
+                ),
+            ),
             TCA_ACT_IN_HW_COUNT => TcActionAttribute::InHwCount(
                 parse_u32(buf.value())
                     .context("failed to parse TCA_ACT_IN_HW_COUNT")?,
@@ -265,6 +321,19 @@ where
     }
 }
 
+// Value of a `struct nla_bitfield32`, the selector only tells which bits
+// of the value are meaningful. The selector itself is dropped: the kernel
+// dumps either the value itself or all the known bits as selector.
+fn parse_bitfield32(payload: &[u8]) -> Result<u32, DecodeError> {
+    if payload.len() != 8 {
+        return Err(DecodeError::from(format!(
+            "invalid nla_bitfield32 length {}",
+            payload.len()
+        )));
+    }
+    Ok(parse_u32(&payload[..4])? & parse_u32(&payload[4..])?)
+}
+
 /// [`TcActionOption`] is a netlink message attribute that describes an option
 /// of a [tc-actions] action.
 ///
@@ -290,6 +359,74 @@ pub enum TcActionOption {
     /// These options type can be used to assign encapsulation properties to
     /// the packet.
     TunnelKey(TcActionTunnelKeyOption),
+    /// Generic action options.
+    ///
+    /// These options can be used to drop, pass or otherwise apply a control
+    /// action to packets.
+    Gact(TcActionGactOption),
+    /// Police options.
+    ///
+    /// These options can be used to rate limit packets.
+    Police(TcActionPoliceOption),
+    /// Pedit options.
+    ///
+    /// These options can be used to edit arbitrary packet data.
+    Pedit(TcActionPeditOption),
+    /// Ct options.
+    ///
+    /// These options can be used to send packets through connection
+    /// tracking, commit connections and apply NAT to them.
+    Ct(TcActionCtOption),
+    /// Ctinfo options.
+    ///
+    /// These options can be used to restore the DSCP or the mark of
+    /// packets from the conntrack mark.
+    CtInfo(TcActionCtInfoOption),
+    /// Connmark options.
+    ///
+    /// These options can be used to restore the mark of packets from the
+    /// conntrack mark.
+    Connmark(TcActionConnmarkOption),
+    /// Mpls options.
+    ///
+    /// These options can be used to push, pop or modify MPLS headers.
+    Mpls(TcActionMplsOption),
+    /// Csum options.
+    ///
+    /// These options can be used to update the checksums of packets.
+    Csum(TcActionCsumOption),
+    /// Sample options.
+    ///
+    /// These options can be used to send a sample of packets to a psample
+    /// group.
+    Sample(TcActionSampleOption),
+    /// Gate options.
+    ///
+    /// These options can be used to accept or drop packets following a
+    /// time schedule (IEEE 802.1Qci).
+    Gate(TcActionGateOption),
+    /// Bpf options.
+    ///
+    /// These options can be used to run a BPF program on packets.
+    Bpf(TcActionBpfOption),
+    /// Ife options.
+    ///
+    /// These options can be used to encode or decode packet metadata in an
+    /// Inter-FE header.
+    Ife(TcActionIfeOption),
+    /// Skbedit options.
+    ///
+    /// These options can be used to change the priority, transmit queue,
+    /// mark or type of packets.
+    SkbEdit(TcActionSkbEditOption),
+    /// Skbmod options.
+    ///
+    /// These options can be used to rewrite the Ethernet header of packets.
+    SkbMod(TcActionSkbModOption),
+    /// Vlan options.
+    ///
+    /// These options can be used to push, pop or modify VLAN tags.
+    Vlan(TcActionVlanOption),
     /// Other action types not yet supported by this library.
     Other(DefaultNla),
 }
@@ -300,6 +437,21 @@ impl Nla for TcActionOption {
             Self::Mirror(nla) => nla.value_len(),
             Self::Nat(nla) => nla.value_len(),
             Self::TunnelKey(nla) => nla.value_len(),
+            Self::Gact(nla) => nla.value_len(),
+            Self::Police(nla) => nla.value_len(),
+            Self::Pedit(nla) => nla.value_len(),
+            Self::Ct(nla) => nla.value_len(),
+            Self::CtInfo(nla) => nla.value_len(),
+            Self::Connmark(nla) => nla.value_len(),
+            Self::Mpls(nla) => nla.value_len(),
+            Self::Csum(nla) => nla.value_len(),
+            Self::Sample(nla) => nla.value_len(),
+            Self::Gate(nla) => nla.value_len(),
+            Self::Bpf(nla) => nla.value_len(),
+            Self::Ife(nla) => nla.value_len(),
+            Self::SkbEdit(nla) => nla.value_len(),
+            Self::SkbMod(nla) => nla.value_len(),
+            Self::Vlan(nla) => nla.value_len(),
             Self::Other(nla) => nla.value_len(),
         }
     }
@@ -309,6 +461,21 @@ impl Nla for TcActionOption {
             Self::Mirror(nla) => nla.emit_value(buffer),
             Self::Nat(nla) => nla.emit_value(buffer),
             Self::TunnelKey(nla) => nla.emit_value(buffer),
+            Self::Gact(nla) => nla.emit_value(buffer),
+            Self::Police(nla) => nla.emit_value(buffer),
+            Self::Pedit(nla) => nla.emit_value(buffer),
+            Self::Ct(nla) => nla.emit_value(buffer),
+            Self::CtInfo(nla) => nla.emit_value(buffer),
+            Self::Connmark(nla) => nla.emit_value(buffer),
+            Self::Mpls(nla) => nla.emit_value(buffer),
+            Self::Csum(nla) => nla.emit_value(buffer),
+            Self::Sample(nla) => nla.emit_value(buffer),
+            Self::Gate(nla) => nla.emit_value(buffer),
+            Self::Bpf(nla) => nla.emit_value(buffer),
+            Self::Ife(nla) => nla.emit_value(buffer),
+            Self::SkbEdit(nla) => nla.emit_value(buffer),
+            Self::SkbMod(nla) => nla.emit_value(buffer),
+            Self::Vlan(nla) => nla.emit_value(buffer),
             Self::Other(nla) => nla.emit_value(buffer),
         }
     }
@@ -318,6 +485,21 @@ impl Nla for TcActionOption {
             Self::Mirror(nla) => nla.kind(),
             Self::Nat(nla) => nla.kind(),
             Self::TunnelKey(nla) => nla.kind(),
+            Self::Gact(nla) => nla.kind(),
+            Self::Police(nla) => nla.kind(),
+            Self::Pedit(nla) => nla.kind(),
+            Self::Ct(nla) => nla.kind(),
+            Self::CtInfo(nla) => nla.kind(),
+            Self::Connmark(nla) => nla.kind(),
+            Self::Mpls(nla) => nla.kind(),
+            Self::Csum(nla) => nla.kind(),
+            Self::Sample(nla) => nla.kind(),
+            Self::Gate(nla) => nla.kind(),
+            Self::Bpf(nla) => nla.kind(),
+            Self::Ife(nla) => nla.kind(),
+            Self::SkbEdit(nla) => nla.kind(),
+            Self::SkbMod(nla) => nla.kind(),
+            Self::Vlan(nla) => nla.kind(),
             Self::Other(nla) => nla.kind(),
         }
     }
@@ -345,6 +527,66 @@ where
                 TcActionTunnelKeyOption::parse(buf)
                     .context("failed to parse tunnel_key action")?,
             ),
+            TcActionGact::KIND => Self::Gact(
+                TcActionGactOption::parse(buf)
+                    .context("failed to parse gact action")?,
+            ),
+            TcActionPolice::KIND => Self::Police(
+                TcActionPoliceOption::parse(buf)
+                    .context("failed to parse police action")?,
+            ),
+            TcActionPedit::KIND => Self::Pedit(
+                TcActionPeditOption::parse(buf)
+                    .context("failed to parse pedit action")?,
+            ),
+            TcActionCt::KIND => Self::Ct(
+                TcActionCtOption::parse(buf)
+                    .context("failed to parse ct action")?,
+            ),
+            TcActionCtInfo::KIND => Self::CtInfo(
+                TcActionCtInfoOption::parse(buf)
+                    .context("failed to parse ctinfo action")?,
+            ),
+            TcActionConnmark::KIND => Self::Connmark(
+                TcActionConnmarkOption::parse(buf)
+                    .context("failed to parse connmark action")?,
+            ),
+            TcActionMpls::KIND => Self::Mpls(
+                TcActionMplsOption::parse(buf)
+                    .context("failed to parse mpls action")?,
+            ),
+            TcActionCsum::KIND => Self::Csum(
+                TcActionCsumOption::parse(buf)
+                    .context("failed to parse csum action")?,
+            ),
+            TcActionSample::KIND => Self::Sample(
+                TcActionSampleOption::parse(buf)
+                    .context("failed to parse sample action")?,
+            ),
+            TcActionGate::KIND => Self::Gate(
+                TcActionGateOption::parse(buf)
+                    .context("failed to parse gate action")?,
+            ),
+            TcActionBpf::KIND => Self::Bpf(
+                TcActionBpfOption::parse(buf)
+                    .context("failed to parse bpf action")?,
+            ),
+            TcActionIfe::KIND => Self::Ife(
+                TcActionIfeOption::parse(buf)
+                    .context("failed to parse ife action")?,
+            ),
+            TcActionSkbEdit::KIND => Self::SkbEdit(
+                TcActionSkbEditOption::parse(buf)
+                    .context("failed to parse skbedit action")?,
+            ),
+            TcActionSkbMod::KIND => Self::SkbMod(
+                TcActionSkbModOption::parse(buf)
+                    .context("failed to parse skbmod action")?,
+            ),
+            TcActionVlan::KIND => Self::Vlan(
+                TcActionVlanOption::parse(buf)
+                    .context("failed to parse vlan action")?,
+            ),
             _ => Self::Other(
                 DefaultNla::parse(buf)
                     .context("failed to parse action options")?,
@@ -495,6 +737,11 @@ const TC_ACT_REPEAT: i32 = 6;
 const TC_ACT_REDIRECT: i32 = 7;
 const TC_ACT_TRAP: i32 = 8;
 
+const TC_ACT_EXT_SHIFT: u32 = 28;
+const TC_ACT_EXT_VAL_MASK: i32 = (1 << TC_ACT_EXT_SHIFT) - 1;
+const TC_ACT_JUMP: i32 = 1 << TC_ACT_EXT_SHIFT;
+const TC_ACT_GOTO_CHAIN: i32 = 2 << TC_ACT_EXT_SHIFT;
+
 /// Generic traffic control action types.
 ///
 /// These are the possible "outcomes" for a packet after an action is applied to
@@ -542,6 +789,10 @@ pub enum TcActionType {
     ///
     /// [`Shot`]: #variant.Shot
     Trap,
+    /// Skip the given number of following actions.
+    Jump(u32),
+    /// Continue classification with the filters of the given chain.
+    GotoChain(u32),
     /// Other action types not known at the time of writing or not yet
     /// supported by this library.
     Other(i32),
@@ -560,7 +811,13 @@ impl From<i32> for TcActionType {
             TC_ACT_REPEAT => Self::Repeat,
             TC_ACT_REDIRECT => Self::Redirect,
             TC_ACT_TRAP => Self::Trap,
-            _ => Self::Other(d),
+            _ => match d & !TC_ACT_EXT_VAL_MASK {
+                TC_ACT_JUMP => Self::Jump((d & TC_ACT_EXT_VAL_MASK) as u32),
+                TC_ACT_GOTO_CHAIN => {
+                    Self::GotoChain((d & TC_ACT_EXT_VAL_MASK) as u32)
+                }
+                _ => Self::Other(d),
+            },
         }
     }
 }
@@ -578,16 +835,31 @@ impl From<TcActionType> for i32 {
             TcActionType::Repeat => TC_ACT_REPEAT,
             TcActionType::Redirect => TC_ACT_REDIRECT,
             TcActionType::Trap => TC_ACT_TRAP,
+            TcActionType::Jump(count) => {
+                TC_ACT_JUMP | (count as i32 & TC_ACT_EXT_VAL_MASK)
+            }
+            TcActionType::GotoChain(chain) => {
+                TC_ACT_GOTO_CHAIN | (chain as i32 & TC_ACT_EXT_VAL_MASK)
+            }
             TcActionType::Other(d) => d,
         }
     }
 }
 
+/// Timestamps of an action
+///
+/// The kernel dumps them as clock ticks of [`Tcf::USER_HZ`] per second,
+/// relative to the time of the dump, except `expires`.
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Tcf {
+    /// Ticks since the action was installed
     pub install: u64,
+    /// Ticks since the action was last used
     pub lastuse: u64,
+    /// Absolute expiry time of the action in ticks of the kernel clock
+    /// (jiffies converted to [`Tcf::USER_HZ`]), not relative to the dump
     pub expires: u64,
+    /// Ticks since the action was first used, 0 if never used
     pub firstuse: u64,
 }
 
@@ -612,6 +884,36 @@ pub struct TcfBuffer {
 }
 
 impl Tcf {
+    /// Clock ticks per second of the timestamps, the `USER_HZ` of the
+    /// kernel user space ABI.
+    pub const USER_HZ: u64 = 100;
+
+    /// Time elapsed since the action was installed.
+    pub fn since_install(&self) -> Duration {
+        Self::ticks_to_duration(self.install)
+    }
+
+    /// Time elapsed since the action was last used.
+    pub fn since_lastuse(&self) -> Duration {
+        Self::ticks_to_duration(self.lastuse)
+    }
+
+    /// Time elapsed since the action was first used, `None` if never used.
+    pub fn since_firstuse(&self) -> Option<Duration> {
+        match self.firstuse {
+            0 => None,
+            v => Some(Self::ticks_to_duration(v)),
+        }
+    }
+
+    /// Convert clock ticks of [`Tcf::USER_HZ`] into a [`Duration`].
+    pub fn ticks_to_duration(ticks: u64) -> Duration {
+        Duration::from_secs(ticks / Self::USER_HZ)
+            + Duration::from_nanos(
+                (ticks % Self::USER_HZ) * 1_000_000_000 / Self::USER_HZ,
+            )
+    }
+
     pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
         let (raw, _) = TcfBuffer::ref_from_prefix(payload).map_err(|_| {
             DecodeError::buffer_too_small(payload.len(), size_of::<TcfBuffer>())
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: MIT
+
+/// Bpf action
+///
+/// The bpf action runs an eBPF program, or a classic BPF program given
+/// inline, and uses its return value as the action verdict.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, emit_u32, parse_string, parse_u16, parse_u32, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+use crate::tc::TcFilterBpf;
+
+/// Traffic control action used to run a BPF program.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionBpf {}
+impl TcActionBpf {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "bpf";
+}
+
+const TCA_ACT_BPF_TM: u16 = 1;
+const TCA_ACT_BPF_PARMS: u16 = 2;
+const TCA_ACT_BPF_OPS_LEN: u16 = 3;
+const TCA_ACT_BPF_OPS: u16 = 4;
+const TCA_ACT_BPF_FD: u16 = 5;
+const TCA_ACT_BPF_NAME: u16 = 6;
+// const TCA_ACT_BPF_PAD: u16 = 7;
+const TCA_ACT_BPF_TAG: u16 = 8;
+const TCA_ACT_BPF_ID: u16 = 9;
+
+/// Options for the `TcActionBpf` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionBpfOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Generic parameters for the bpf action.
+    Parms(TcActionGeneric),
+    /// Number of instructions of the classic BPF program.
+    OpsLen(u16),
+    /// Instructions of the classic BPF program.
+    Ops(Vec<TcBpfSockFilter>),
+    /// File descriptor of the eBPF program.
+    ProgFd(u32),
+    /// Name of the eBPF program.
+    ProgName(String),
+    /// Tag of the eBPF program.
+    ProgTag([u8; TcFilterBpf::BPF_TAG_SIZE]),
+    /// Id of the eBPF program.
+    ProgId(u32),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionBpfOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::OpsLen(_) => 2,
+            Self::Ops(ops) => ops.len() * size_of::<TcBpfSockFilterBuffer>(),
+            Self::ProgFd(_) | Self::ProgId(_) => 4,
+            Self::ProgName(name) => name.len() + 1,
+            Self::ProgTag(_) => TcFilterBpf::BPF_TAG_SIZE,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::OpsLen(i) => emit_u16(buffer, *i).unwrap(),
+            Self::Ops(ops) => {
+                for (op, chunk) in ops
+                    .iter()
+                    .zip(buffer.chunks_mut(size_of::<TcBpfSockFilterBuffer>()))
+                {
+                    op.emit(chunk);
+                }
+            }
+            Self::ProgFd(i) | Self::ProgId(i) => emit_u32(buffer, *i).unwrap(),
+            Self::ProgName(name) => {
+                buffer[..name.len()].copy_from_slice(name.as_bytes());
+                buffer[name.len()] = 0;
+            }
+            Self::ProgTag(tag) => buffer.copy_from_slice(tag),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_ACT_BPF_TM,
+            Self::Parms(_) => TCA_ACT_BPF_PARMS,
+            Self::OpsLen(_) => TCA_ACT_BPF_OPS_LEN,
+            Self::Ops(_) => TCA_ACT_BPF_OPS,
+            Self::ProgFd(_) => TCA_ACT_BPF_FD,
+            Self::ProgName(_) => TCA_ACT_BPF_NAME,
+            Self::ProgTag(_) => TCA_ACT_BPF_TAG,
+            Self::ProgId(_) => TCA_ACT_BPF_ID,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionBpfOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_ACT_BPF_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_ACT_BPF_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_ACT_BPF_OPS_LEN => Self::OpsLen(
+                parse_u16(payload)
+                    .context("failed to parse TCA_ACT_BPF_OPS_LEN")?,
+            ),
+            TCA_ACT_BPF_OPS => Self::Ops(
+                payload
+                    .chunks(size_of::<TcBpfSockFilterBuffer>())
+                    .map(TcBpfSockFilter::parse)
+                    .collect::<Result<Vec<_>, _>>()
+                    .context("failed to parse TCA_ACT_BPF_OPS")?,
+            ),
+            TCA_ACT_BPF_FD => Self::ProgFd(
+                parse_u32(payload).context("failed to parse TCA_ACT_BPF_FD")?,
+            ),
+            TCA_ACT_BPF_NAME => Self::ProgName(
+                parse_string(payload)
+                    .context("failed to parse TCA_ACT_BPF_NAME")?,
+            ),
+            TCA_ACT_BPF_TAG => {
+                let tag: [u8; TcFilterBpf::BPF_TAG_SIZE] =
+                    payload.try_into().map_err(|_| {
+                        DecodeError::nla_length_mismatch(
+                            TcFilterBpf::BPF_TAG_SIZE,
+                            payload.len(),
+                        )
+                    })?;
+                Self::ProgTag(tag)
+            }
+            TCA_ACT_BPF_ID => Self::ProgId(
+                parse_u32(payload).context("failed to parse TCA_ACT_BPF_ID")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Classic BPF instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct TcBpfSockFilter {
+    pub code: u16,
+    /// Jump offset if the condition is true.
+    pub jt: u8,
+    /// Jump offset if the condition is false.
+    pub jf: u8,
+    pub k: u32,
+}
+
+// kernel struct `sock_filter`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcBpfSockFilterBuffer {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
+
+impl TcBpfSockFilter {
+    pub fn new(code: u16, jt: u8, jf: u8, k: u32) -> Self {
+        Self { code, jt, jf, k }
+    }
+
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcBpfSockFilterBuffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcBpfSockFilterBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            code: raw.code,
+            jt: raw.jt,
+            jf: raw.jf,
+            k: raw.k,
+        })
+    }
+}
+
+impl From<&TcBpfSockFilter> for TcBpfSockFilterBuffer {
+    fn from(op: &TcBpfSockFilter) -> Self {
+        Self {
+            code: op.code,
+            jt: op.jt,
+            jf: op.jf,
+            k: op.k,
+        }
+    }
+}
+
+impl Emitable for TcBpfSockFilter {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcBpfSockFilterBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcBpfSockFilterBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
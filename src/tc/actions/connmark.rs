@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT
+
+/// Connmark action
+///
+/// The connmark action restores the packet mark from the conntrack mark of
+/// its connection.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to restore the conntrack mark.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionConnmark {}
+impl TcActionConnmark {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "connmark";
+}
+
+const TCA_CONNMARK_PARMS: u16 = 1;
+const TCA_CONNMARK_TM: u16 = 2;
+// const TCA_CONNMARK_PAD: u16 = 3;
+
+/// Options for the `TcActionConnmark` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionConnmarkOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the connmark action.
+    Parms(TcConnmark),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionConnmarkOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcConnmarkBuffer>(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_CONNMARK_TM,
+            Self::Parms(_) => TCA_CONNMARK_PARMS,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionConnmarkOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CONNMARK_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_CONNMARK_PARMS => Self::Parms(TcConnmark::parse(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the connmark action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcConnmark {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    /// Conntrack zone of the connection.
+    pub zone: u16,
+}
+
+// kernel struct `tc_connmark`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcConnmarkBuffer {
+    generic: TcActionGenericBuffer,
+    zone: u16,
+    // padding to align the struct on 32 bits
+    pad: [u8; 2],
+}
+
+impl TcConnmark {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcConnmarkBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcConnmarkBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            zone: raw.zone,
+        })
+    }
+}
+
+impl From<&TcConnmark> for TcConnmarkBuffer {
+    fn from(connmark: &TcConnmark) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&connmark.generic),
+            zone: connmark.zone,
+            pad: [0; 2],
+        }
+    }
+}
+
+impl Emitable for TcConnmark {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcConnmarkBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcConnmarkBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: MIT
+
+/// Csum action
+///
+/// The csum action recalculates the checksums of the selected headers.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to update packet checksums.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionCsum {}
+impl TcActionCsum {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "csum";
+}
+
+const TCA_CSUM_PARMS: u16 = 1;
+const TCA_CSUM_TM: u16 = 2;
+// const TCA_CSUM_PAD: u16 = 3;
+
+/// Options for the `TcActionCsum` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionCsumOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the csum action.
+    Parms(TcCsum),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionCsumOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcCsumBuffer>(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_CSUM_TM,
+            Self::Parms(_) => TCA_CSUM_PARMS,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionCsumOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CSUM_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_CSUM_PARMS => Self::Parms(TcCsum::parse(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the csum action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcCsum {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    /// Headers whose checksum is updated.
+    pub update_flags: TcCsumUpdateFlags,
+}
+
+// kernel struct `tc_csum`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcCsumBuffer {
+    generic: TcActionGenericBuffer,
+    update_flags: u32,
+}
+
+impl TcCsum {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcCsumBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcCsumBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            update_flags: TcCsumUpdateFlags::from_bits_retain(raw.update_flags),
+        })
+    }
+}
+
+impl From<&TcCsum> for TcCsumBuffer {
+    fn from(csum: &TcCsum) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&csum.generic),
+            update_flags: csum.update_flags.bits(),
+        }
+    }
+}
+
+impl Emitable for TcCsum {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcCsumBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcCsumBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCA_CSUM_UPDATE_FLAG_IPV4HDR: u32 = 1;
+const TCA_CSUM_UPDATE_FLAG_ICMP: u32 = 2;
+const TCA_CSUM_UPDATE_FLAG_IGMP: u32 = 4;
+const TCA_CSUM_UPDATE_FLAG_TCP: u32 = 8;
+const TCA_CSUM_UPDATE_FLAG_UDP: u32 = 16;
+const TCA_CSUM_UPDATE_FLAG_UDPLITE: u32 = 32;
+const TCA_CSUM_UPDATE_FLAG_SCTP: u32 = 64;
+
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcCsumUpdateFlags: u32 {
+        const Ipv4Hdr = TCA_CSUM_UPDATE_FLAG_IPV4HDR;
+        const Icmp = TCA_CSUM_UPDATE_FLAG_ICMP;
+        const Igmp = TCA_CSUM_UPDATE_FLAG_IGMP;
+        const Tcp = TCA_CSUM_UPDATE_FLAG_TCP;
+        const Udp = TCA_CSUM_UPDATE_FLAG_UDP;
+        const UdpLite = TCA_CSUM_UPDATE_FLAG_UDPLITE;
+        const Sctp = TCA_CSUM_UPDATE_FLAG_SCTP;
+        const _ = !0;
+    }
+}
@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: MIT
+
+/// Connection tracking action
+///
+/// The ct action sends packets through the connection tracker, optionally
+/// committing the connection, setting its mark and labels, or applying
+/// NAT to it.
+use std::{
+    mem::size_of,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use netlink_packet_core::{
+    emit_u16, emit_u16_be, emit_u32, parse_string, parse_u16, parse_u16_be,
+    parse_u32, parse_u8, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, Parseable,
+};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+use crate::{
+    ip::{parse_ipv4_addr, parse_ipv6_addr},
+    AddressFamily, IpProtocol,
+};
+
+/// Traffic control action used to interact with connection tracking.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionCt {}
+impl TcActionCt {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "ct";
+}
+
+const TCA_CT_PARMS: u16 = 1;
+const TCA_CT_TM: u16 = 2;
+const TCA_CT_ACTION: u16 = 3;
+const TCA_CT_ZONE: u16 = 4;
+const TCA_CT_MARK: u16 = 5;
+const TCA_CT_MARK_MASK: u16 = 6;
+const TCA_CT_LABELS: u16 = 7;
+const TCA_CT_LABELS_MASK: u16 = 8;
+const TCA_CT_NAT_IPV4_MIN: u16 = 9;
+const TCA_CT_NAT_IPV4_MAX: u16 = 10;
+const TCA_CT_NAT_IPV6_MIN: u16 = 11;
+const TCA_CT_NAT_IPV6_MAX: u16 = 12;
+const TCA_CT_NAT_PORT_MIN: u16 = 13;
+const TCA_CT_NAT_PORT_MAX: u16 = 14;
+// const TCA_CT_PAD: u16 = 15;
+const TCA_CT_HELPER_NAME: u16 = 16;
+const TCA_CT_HELPER_FAMILY: u16 = 17;
+const TCA_CT_HELPER_PROTO: u16 = 18;
+
+/// Options for the `TcActionCt` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionCtOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Generic parameters for the ct action.
+    Parms(TcActionGeneric),
+    /// What the ct action does besides the connection lookup.
+    Action(TcCtActionFlags),
+    /// Conntrack zone of the connection.
+    Zone(u16),
+    /// Mark set on the committed connection.
+    Mark(u32),
+    MarkMask(u32),
+    /// Labels set on the committed connection.
+    Labels([u8; 16]),
+    LabelsMask([u8; 16]),
+    /// Lower bound of the IPv4 NAT address range.
+    NatIpv4Min(Ipv4Addr),
+    /// Upper bound of the IPv4 NAT address range.
+    NatIpv4Max(Ipv4Addr),
+    /// Lower bound of the IPv6 NAT address range.
+    NatIpv6Min(Ipv6Addr),
+    /// Upper bound of the IPv6 NAT address range.
+    NatIpv6Max(Ipv6Addr),
+    /// Lower bound of the NAT port range.
+    NatPortMin(u16),
+    /// Upper bound of the NAT port range.
+    NatPortMax(u16),
+    /// Name of the conntrack helper attached to the connection.
+    HelperName(String),
+    /// Address family of the conntrack helper.
+    HelperFamily(AddressFamily),
+    /// Layer 4 protocol of the conntrack helper.
+    HelperProto(IpProtocol),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionCtOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::Action(_)
+            | Self::Zone(_)
+            | Self::NatPortMin(_)
+            | Self::NatPortMax(_) => 2,
+            Self::Mark(_)
+            | Self::MarkMask(_)
+            | Self::NatIpv4Min(_)
+            | Self::NatIpv4Max(_) => 4,
+            Self::Labels(_)
+            | Self::LabelsMask(_)
+            | Self::NatIpv6Min(_)
+            | Self::NatIpv6Max(_) => 16,
+            Self::HelperName(s) => s.len() + 1,
+            Self::HelperFamily(_) | Self::HelperProto(_) => 1,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Action(f) => emit_u16(buffer, f.bits()).unwrap(),
+            Self::Zone(i) => emit_u16(buffer, *i).unwrap(),
+            Self::Mark(i) | Self::MarkMask(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Labels(v) | Self::LabelsMask(v) => {
+                buffer.copy_from_slice(v.as_slice())
+            }
+            Self::NatIpv4Min(ip) | Self::NatIpv4Max(ip) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::NatIpv6Min(ip) | Self::NatIpv6Max(ip) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::NatPortMin(i) | Self::NatPortMax(i) => {
+                emit_u16_be(buffer, *i).unwrap()
+            }
+            Self::HelperName(s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            Self::HelperFamily(v) => buffer[0] = u8::from(*v),
+            Self::HelperProto(v) => buffer[0] = u8::from(*v),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_CT_TM,
+            Self::Parms(_) => TCA_CT_PARMS,
+            Self::Action(_) => TCA_CT_ACTION,
+            Self::Zone(_) => TCA_CT_ZONE,
+            Self::Mark(_) => TCA_CT_MARK,
+            Self::MarkMask(_) => TCA_CT_MARK_MASK,
+            Self::Labels(_) => TCA_CT_LABELS,
+            Self::LabelsMask(_) => TCA_CT_LABELS_MASK,
+            Self::NatIpv4Min(_) => TCA_CT_NAT_IPV4_MIN,
+            Self::NatIpv4Max(_) => TCA_CT_NAT_IPV4_MAX,
+            Self::NatIpv6Min(_) => TCA_CT_NAT_IPV6_MIN,
+            Self::NatIpv6Max(_) => TCA_CT_NAT_IPV6_MAX,
+            Self::NatPortMin(_) => TCA_CT_NAT_PORT_MIN,
+            Self::NatPortMax(_) => TCA_CT_NAT_PORT_MAX,
+            Self::HelperName(_) => TCA_CT_HELPER_NAME,
+            Self::HelperFamily(_) => TCA_CT_HELPER_FAMILY,
+            Self::HelperProto(_) => TCA_CT_HELPER_PROTO,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+fn parse_labels(payload: &[u8]) -> Result<[u8; 16], DecodeError> {
+    payload.try_into().map_err(|_| {
+        DecodeError::from(format!(
+            "Invalid ct labels length, expecting 16 bytes, got {}",
+            payload.len()
+        ))
+    })
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionCtOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CT_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_CT_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_CT_ACTION => Self::Action(TcCtActionFlags::from_bits_retain(
+                parse_u16(payload).context("failed to parse TCA_CT_ACTION")?,
+            )),
+            TCA_CT_ZONE => Self::Zone(
+                parse_u16(payload).context("failed to parse TCA_CT_ZONE")?,
+            ),
+            TCA_CT_MARK => Self::Mark(
+                parse_u32(payload).context("failed to parse TCA_CT_MARK")?,
+            ),
+            TCA_CT_MARK_MASK => Self::MarkMask(
+                parse_u32(payload)
+                    .context("failed to parse TCA_CT_MARK_MASK")?,
+            ),
+            TCA_CT_LABELS => Self::Labels(
+                parse_labels(payload)
+                    .context("failed to parse TCA_CT_LABELS")?,
+            ),
+            TCA_CT_LABELS_MASK => Self::LabelsMask(
+                parse_labels(payload)
+                    .context("failed to parse TCA_CT_LABELS_MASK")?,
+            ),
+            TCA_CT_NAT_IPV4_MIN => Self::NatIpv4Min(
+                parse_ipv4_addr(payload)
+                    .context("failed to parse TCA_CT_NAT_IPV4_MIN")?,
+            ),
+            TCA_CT_NAT_IPV4_MAX => Self::NatIpv4Max(
+                parse_ipv4_addr(payload)
+                    .context("failed to parse TCA_CT_NAT_IPV4_MAX")?,
+            ),
+            TCA_CT_NAT_IPV6_MIN => Self::NatIpv6Min(
+                parse_ipv6_addr(payload)
+                    .context("failed to parse TCA_CT_NAT_IPV6_MIN")?,
+            ),
+            TCA_CT_NAT_IPV6_MAX => Self::NatIpv6Max(
+                parse_ipv6_addr(payload)
+                    .context("failed to parse TCA_CT_NAT_IPV6_MAX")?,
+            ),
+            TCA_CT_NAT_PORT_MIN => Self::NatPortMin(
+                parse_u16_be(payload)
+                    .context("failed to parse TCA_CT_NAT_PORT_MIN")?,
+            ),
+            TCA_CT_NAT_PORT_MAX => Self::NatPortMax(
+                parse_u16_be(payload)
+                    .context("failed to parse TCA_CT_NAT_PORT_MAX")?,
+            ),
+            TCA_CT_HELPER_NAME => Self::HelperName(
+                parse_string(payload)
+                    .context("failed to parse TCA_CT_HELPER_NAME")?,
+            ),
+            TCA_CT_HELPER_FAMILY => Self::HelperFamily(
+                parse_u8(payload)
+                    .context("failed to parse TCA_CT_HELPER_FAMILY")?
+                    .into(),
+            ),
+            TCA_CT_HELPER_PROTO => Self::HelperProto(
+                parse_u8(payload)
+                    .context("failed to parse TCA_CT_HELPER_PROTO")?
+                    .into(),
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const TCA_CT_ACT_COMMIT: u16 = 1 << 0;
+const TCA_CT_ACT_FORCE: u16 = 1 << 1;
+const TCA_CT_ACT_CLEAR: u16 = 1 << 2;
+const TCA_CT_ACT_NAT: u16 = 1 << 3;
+const TCA_CT_ACT_NAT_SRC: u16 = 1 << 4;
+const TCA_CT_ACT_NAT_DST: u16 = 1 << 5;
+
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcCtActionFlags: u16 {
+        /// Commit the connection to the conntrack table
+        const Commit = TCA_CT_ACT_COMMIT;
+        /// Replace a committed connection going in the other direction
+        const Force = TCA_CT_ACT_FORCE;
+        /// Clear the conntrack state of the packet
+        const Clear = TCA_CT_ACT_CLEAR;
+        /// Apply NAT to the connection
+        const Nat = TCA_CT_ACT_NAT;
+        /// Apply source NAT
+        const NatSrc = TCA_CT_ACT_NAT_SRC;
+        /// Apply destination NAT
+        const NatDst = TCA_CT_ACT_NAT_DST;
+        const _ = !0;
+    }
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+
+/// Ctinfo action
+///
+/// The ctinfo action restores information stored in the conntrack mark of
+/// a connection: the DSCP of IPv4/IPv6 packets, or the packet mark.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, emit_u32, emit_u64, parse_u16, parse_u32, parse_u64, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to restore conntrack mark information.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionCtInfo {}
+impl TcActionCtInfo {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "ctinfo";
+}
+
+// const TCA_CTINFO_PAD: u16 = 1;
+const TCA_CTINFO_TM: u16 = 2;
+const TCA_CTINFO_ACT: u16 = 3;
+const TCA_CTINFO_ZONE: u16 = 4;
+const TCA_CTINFO_PARMS_DSCP_MASK: u16 = 5;
+const TCA_CTINFO_PARMS_DSCP_STATEMASK: u16 = 6;
+const TCA_CTINFO_PARMS_CPMARK_MASK: u16 = 7;
+const TCA_CTINFO_STATS_DSCP_SET: u16 = 8;
+const TCA_CTINFO_STATS_DSCP_ERROR: u16 = 9;
+const TCA_CTINFO_STATS_CPMARK_SET: u16 = 10;
+
+/// Options for the `TcActionCtInfo` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionCtInfoOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Generic parameters for the ctinfo action.
+    Act(TcActionGeneric),
+    /// Conntrack zone of the connection.
+    Zone(u16),
+    /// Bits of the conntrack mark holding the DSCP to restore.
+    DscpMask(u32),
+    /// Bits of the conntrack mark which must be set for the DSCP to be
+    /// restored.
+    DscpStateMask(u32),
+    /// Bits of the conntrack mark copied to the packet mark.
+    CpmarkMask(u32),
+    /// Number of packets whose DSCP was restored.
+    DscpSet(u64),
+    /// Number of packets whose DSCP could not be restored.
+    DscpError(u64),
+    /// Number of packets whose mark was restored.
+    CpmarkSet(u64),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionCtInfoOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Act(_) => size_of::<TcActionGenericBuffer>(),
+            Self::Zone(_) => 2,
+            Self::DscpMask(_)
+            | Self::DscpStateMask(_)
+            | Self::CpmarkMask(_) => 4,
+            Self::DscpSet(_) | Self::DscpError(_) | Self::CpmarkSet(_) => 8,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Act(p) => p.emit(buffer),
+            Self::Zone(i) => emit_u16(buffer, *i).unwrap(),
+            Self::DscpMask(i)
+            | Self::DscpStateMask(i)
+            | Self::CpmarkMask(i) => emit_u32(buffer, *i).unwrap(),
+            Self::DscpSet(i) | Self::DscpError(i) | Self::CpmarkSet(i) => {
+                emit_u64(buffer, *i).unwrap()
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_CTINFO_TM,
+            Self::Act(_) => TCA_CTINFO_ACT,
+            Self::Zone(_) => TCA_CTINFO_ZONE,
+            Self::DscpMask(_) => TCA_CTINFO_PARMS_DSCP_MASK,
+            Self::DscpStateMask(_) => TCA_CTINFO_PARMS_DSCP_STATEMASK,
+            Self::CpmarkMask(_) => TCA_CTINFO_PARMS_CPMARK_MASK,
+            Self::DscpSet(_) => TCA_CTINFO_STATS_DSCP_SET,
+            Self::DscpError(_) => TCA_CTINFO_STATS_DSCP_ERROR,
+            Self::CpmarkSet(_) => TCA_CTINFO_STATS_CPMARK_SET,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionCtInfoOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CTINFO_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_CTINFO_ACT => Self::Act(TcActionGeneric::parse(payload)?),
+            TCA_CTINFO_ZONE => Self::Zone(
+                parse_u16(payload)
+                    .context("failed to parse TCA_CTINFO_ZONE")?,
+            ),
+            TCA_CTINFO_PARMS_DSCP_MASK => Self::DscpMask(
+                parse_u32(payload)
+                    .context("failed to parse TCA_CTINFO_PARMS_DSCP_MASK")?,
+            ),
+            TCA_CTINFO_PARMS_DSCP_STATEMASK => {
+                Self::DscpStateMask(parse_u32(payload).context(
+                    "failed to parse TCA_CTINFO_PARMS_DSCP_STATEMASK",
+                )?)
+            }
+            TCA_CTINFO_PARMS_CPMARK_MASK => Self::CpmarkMask(
+                parse_u32(payload)
+                    .context("failed to parse TCA_CTINFO_PARMS_CPMARK_MASK")?,
+            ),
+            TCA_CTINFO_STATS_DSCP_SET => Self::DscpSet(
+                parse_u64(payload)
+                    .context("failed to parse TCA_CTINFO_STATS_DSCP_SET")?,
+            ),
+            TCA_CTINFO_STATS_DSCP_ERROR => Self::DscpError(
+                parse_u64(payload)
+                    .context("failed to parse TCA_CTINFO_STATS_DSCP_ERROR")?,
+            ),
+            TCA_CTINFO_STATS_CPMARK_SET => Self::CpmarkSet(
+                parse_u64(payload)
+                    .context("failed to parse TCA_CTINFO_STATS_CPMARK_SET")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
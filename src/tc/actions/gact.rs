@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: MIT
+
+/// Generic action
+///
+/// The gact action performs a control action (e.g. drop, pass or goto
+/// chain) on every packet, optionally replacing it by another control
+/// action with a random or deterministic probability.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{
+    TcActionGeneric, TcActionGenericBuffer, TcActionType, Tcf, TcfBuffer,
+};
+
+/// Traffic control action used to apply a control action to packets.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionGact {}
+impl TcActionGact {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "gact";
+}
+
+const TCA_GACT_TM: u16 = 1;
+const TCA_GACT_PARMS: u16 = 2;
+const TCA_GACT_PROB: u16 = 3;
+
+/// Options for the `TcActionGact` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionGactOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the gact action, the control action is
+    /// [`TcActionGeneric::action`].
+    Parms(TcActionGeneric),
+    /// Probability of replacing the control action.
+    Prob(TcGactProb),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionGactOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::Prob(_) => size_of::<TcGactProbBuffer>(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Prob(p) => p.emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_GACT_TM,
+            Self::Parms(_) => TCA_GACT_PARMS,
+            Self::Prob(_) => TCA_GACT_PROB,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionGactOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GACT_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_GACT_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_GACT_PROB => Self::Prob(TcGactProb::parse(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Probability parameters for the gact action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcGactProb {
+    /// How [`pval`] is interpreted.
+    ///
+    /// [`pval`]: #structfield.pval
+    pub ptype: TcGactProbType,
+    /// With [`TcGactProbType::Random`], [`paction`] is taken with a
+    /// probability of `1 / pval`.
+    /// With [`TcGactProbType::Determ`], [`paction`] is taken for every
+    /// `pval`-th packet.
+    ///
+    /// [`paction`]: #structfield.paction
+    pub pval: u16,
+    /// Control action used instead of [`TcActionGeneric::action`].
+    pub paction: TcActionType,
+}
+
+// kernel struct `tc_gact_p`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcGactProbBuffer {
+    ptype: u16,
+    pval: u16,
+    paction: i32,
+}
+
+impl TcGactProb {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcGactProbBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcGactProbBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            ptype: raw.ptype.into(),
+            pval: raw.pval,
+            paction: raw.paction.into(),
+        })
+    }
+}
+
+impl From<&TcGactProb> for TcGactProbBuffer {
+    fn from(prob: &TcGactProb) -> Self {
+        Self {
+            ptype: prob.ptype.into(),
+            pval: prob.pval,
+            paction: prob.paction.into(),
+        }
+    }
+}
+
+impl Emitable for TcGactProb {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcGactProbBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcGactProbBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const PGACT_NONE: u16 = 0;
+const PGACT_NETRAND: u16 = 1;
+const PGACT_DETERM: u16 = 2;
+
+/// Probability type of the gact action.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcGactProbType {
+    /// No probability, always take [`TcActionGeneric::action`].
+    #[default]
+    None,
+    /// Random probability, `random netrand` in tc-gact(8).
+    Random,
+    /// Deterministic probability, `random determ` in tc-gact(8).
+    Determ,
+    /// Other probability type unknown at the time of writing.
+    Other(u16),
+}
+
+impl From<u16> for TcGactProbType {
+    fn from(d: u16) -> Self {
+        match d {
+            PGACT_NONE => Self::None,
+            PGACT_NETRAND => Self::Random,
+            PGACT_DETERM => Self::Determ,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcGactProbType> for u16 {
+    fn from(v: TcGactProbType) -> u16 {
+        match v {
+            TcGactProbType::None => PGACT_NONE,
+            TcGactProbType::Random => PGACT_NETRAND,
+            TcGactProbType::Determ => PGACT_DETERM,
+            TcGactProbType::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT
+
+/// Gate action
+///
+/// The gate action implements the IEEE 802.1Qci per-stream filtering and
+/// policing: packets are accepted or dropped depending on a cyclic
+/// schedule of open and closed gate intervals.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_i32, emit_u32, emit_u64, parse_i32, parse_u32, parse_u64, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, NlasIterator,
+    Parseable,
+};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to gate packets on a time schedule.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionGate {}
+impl TcActionGate {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "gate";
+}
+
+const TCA_GATE_TM: u16 = 1;
+const TCA_GATE_PARMS: u16 = 2;
+// const TCA_GATE_PAD: u16 = 3;
+const TCA_GATE_PRIORITY: u16 = 4;
+const TCA_GATE_ENTRY_LIST: u16 = 5;
+const TCA_GATE_BASE_TIME: u16 = 6;
+const TCA_GATE_CYCLE_TIME: u16 = 7;
+const TCA_GATE_CYCLE_TIME_EXT: u16 = 8;
+const TCA_GATE_FLAGS: u16 = 9;
+const TCA_GATE_CLOCKID: u16 = 10;
+
+/// Options for the `TcActionGate` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionGateOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Generic parameters for the gate action.
+    Parms(TcActionGeneric),
+    /// Priority of the stream filter.
+    Priority(i32),
+    /// Schedule of the gate, in order.
+    EntryList(Vec<TcGateEntry>),
+    /// Start time of the schedule, in nanoseconds.
+    BaseTime(u64),
+    /// Duration of the schedule cycle, in nanoseconds.
+    CycleTime(u64),
+    /// Extension of the last cycle before a schedule change, in
+    /// nanoseconds.
+    CycleTimeExt(u64),
+    Flags(u32),
+    /// Clock used by the schedule, e.g. `CLOCK_TAI` (11).
+    ClockId(i32),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionGateOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::Priority(_) | Self::Flags(_) | Self::ClockId(_) => 4,
+            Self::EntryList(v) => v.as_slice().buffer_len(),
+            Self::BaseTime(_) | Self::CycleTime(_) | Self::CycleTimeExt(_) => 8,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Priority(i) | Self::ClockId(i) => {
+                emit_i32(buffer, *i).unwrap()
+            }
+            Self::Flags(i) => emit_u32(buffer, *i).unwrap(),
+            Self::EntryList(v) => v.as_slice().emit(buffer),
+            Self::BaseTime(i) | Self::CycleTime(i) | Self::CycleTimeExt(i) => {
+                emit_u64(buffer, *i).unwrap()
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_GATE_TM,
+            Self::Parms(_) => TCA_GATE_PARMS,
+            Self::Priority(_) => TCA_GATE_PRIORITY,
+            Self::EntryList(_) => TCA_GATE_ENTRY_LIST,
+            Self::BaseTime(_) => TCA_GATE_BASE_TIME,
+            Self::CycleTime(_) => TCA_GATE_CYCLE_TIME,
+            Self::CycleTimeExt(_) => TCA_GATE_CYCLE_TIME_EXT,
+            Self::Flags(_) => TCA_GATE_FLAGS,
+            Self::ClockId(_) => TCA_GATE_CLOCKID,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionGateOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GATE_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_GATE_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_GATE_PRIORITY => Self::Priority(
+                parse_i32(payload)
+                    .context("failed to parse TCA_GATE_PRIORITY")?,
+            ),
+            TCA_GATE_ENTRY_LIST => {
+                let mut entries = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid TCA_GATE_ENTRY_LIST value")?;
+                    entries.push(TcGateEntry::parse(&nla)?);
+                }
+                Self::EntryList(entries)
+            }
+            TCA_GATE_BASE_TIME => Self::BaseTime(
+                parse_u64(payload)
+                    .context("failed to parse TCA_GATE_BASE_TIME")?,
+            ),
+            TCA_GATE_CYCLE_TIME => Self::CycleTime(
+                parse_u64(payload)
+                    .context("failed to parse TCA_GATE_CYCLE_TIME")?,
+            ),
+            TCA_GATE_CYCLE_TIME_EXT => Self::CycleTimeExt(
+                parse_u64(payload)
+                    .context("failed to parse TCA_GATE_CYCLE_TIME_EXT")?,
+            ),
+            TCA_GATE_FLAGS => Self::Flags(
+                parse_u32(payload).context("failed to parse TCA_GATE_FLAGS")?,
+            ),
+            TCA_GATE_CLOCKID => Self::ClockId(
+                parse_i32(payload)
+                    .context("failed to parse TCA_GATE_CLOCKID")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const TCA_GATE_ONE_ENTRY: u16 = 1;
+
+/// Entry of the gate schedule.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcGateEntry {
+    Entry(Vec<TcGateEntryAttribute>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcGateEntry {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Entry(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Entry(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Entry(_) => TCA_GATE_ONE_ENTRY,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcGateEntry {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GATE_ONE_ENTRY => {
+                let mut attrs = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid TCA_GATE_ONE_ENTRY value")?;
+                    attrs.push(TcGateEntryAttribute::parse(&nla)?);
+                }
+                Self::Entry(attrs)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const TCA_GATE_ENTRY_INDEX: u16 = 1;
+const TCA_GATE_ENTRY_GATE: u16 = 2;
+const TCA_GATE_ENTRY_INTERVAL: u16 = 3;
+const TCA_GATE_ENTRY_IPV: u16 = 4;
+const TCA_GATE_ENTRY_MAX_OCTETS: u16 = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcGateEntryAttribute {
+    /// Position of the entry in the schedule.
+    Index(u32),
+    /// The gate is open during this entry. The gate is closed when this
+    /// attribute is absent.
+    GateOpen,
+    /// Duration of the entry, in nanoseconds.
+    Interval(u32),
+    /// Internal priority value assigned to the packets, -1 to keep their
+    /// priority.
+    Ipv(i32),
+    /// Maximum number of octets allowed through the gate during this
+    /// entry, -1 for no limit.
+    MaxOctets(i32),
+    Other(DefaultNla),
+}
+
+impl Nla for TcGateEntryAttribute {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::GateOpen => 0,
+            Self::Index(_)
+            | Self::Interval(_)
+            | Self::Ipv(_)
+            | Self::MaxOctets(_) => 4,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::GateOpen => (),
+            Self::Index(i) | Self::Interval(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Ipv(i) | Self::MaxOctets(i) => emit_i32(buffer, *i).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Index(_) => TCA_GATE_ENTRY_INDEX,
+            Self::GateOpen => TCA_GATE_ENTRY_GATE,
+            Self::Interval(_) => TCA_GATE_ENTRY_INTERVAL,
+            Self::Ipv(_) => TCA_GATE_ENTRY_IPV,
+            Self::MaxOctets(_) => TCA_GATE_ENTRY_MAX_OCTETS,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcGateEntryAttribute
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GATE_ENTRY_INDEX => Self::Index(
+                parse_u32(payload)
+                    .context("failed to parse TCA_GATE_ENTRY_INDEX")?,
+            ),
+            TCA_GATE_ENTRY_GATE => Self::GateOpen,
+            TCA_GATE_ENTRY_INTERVAL => Self::Interval(
+                parse_u32(payload)
+                    .context("failed to parse TCA_GATE_ENTRY_INTERVAL")?,
+            ),
+            TCA_GATE_ENTRY_IPV => Self::Ipv(
+                parse_i32(payload)
+                    .context("failed to parse TCA_GATE_ENTRY_IPV")?,
+            ),
+            TCA_GATE_ENTRY_MAX_OCTETS => Self::MaxOctets(
+                parse_i32(payload)
+                    .context("failed to parse TCA_GATE_ENTRY_MAX_OCTETS")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: MIT
+
+/// Ife action
+///
+/// The ife action encapsulates packets with metadata (mark, priority,
+/// tcindex) in an Inter-FE (RFC 8013) header, or decodes such packets and
+/// restores their metadata.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, emit_u32, parse_mac, parse_u16, parse_u32, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, NlasIterator,
+    Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to carry metadata between hosts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionIfe {}
+impl TcActionIfe {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "ife";
+}
+
+const TCA_IFE_PARMS: u16 = 1;
+const TCA_IFE_TM: u16 = 2;
+const TCA_IFE_DMAC: u16 = 3;
+const TCA_IFE_SMAC: u16 = 4;
+const TCA_IFE_TYPE: u16 = 5;
+const TCA_IFE_METALST: u16 = 6;
+// const TCA_IFE_PAD: u16 = 7;
+
+/// Options for the `TcActionIfe` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionIfeOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the ife action.
+    Parms(TcIfe),
+    /// Destination MAC address of the encapsulating header.
+    Dmac([u8; 6]),
+    /// Source MAC address of the encapsulating header.
+    Smac([u8; 6]),
+    /// EtherType of the encapsulating header.
+    Type(u16),
+    /// Metadata encoded in, or decoded from, the packets.
+    MetaList(Vec<TcIfeMeta>),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionIfeOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcIfeBuffer>(),
+            Self::Dmac(_) | Self::Smac(_) => 6,
+            Self::Type(_) => 2,
+            Self::MetaList(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Dmac(mac) | Self::Smac(mac) => {
+                buffer.copy_from_slice(mac.as_slice())
+            }
+            Self::Type(i) => emit_u16(buffer, *i).unwrap(),
+            Self::MetaList(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_IFE_TM,
+            Self::Parms(_) => TCA_IFE_PARMS,
+            Self::Dmac(_) => TCA_IFE_DMAC,
+            Self::Smac(_) => TCA_IFE_SMAC,
+            Self::Type(_) => TCA_IFE_TYPE,
+            Self::MetaList(_) => TCA_IFE_METALST,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionIfeOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_IFE_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_IFE_PARMS => Self::Parms(TcIfe::parse(payload)?),
+            TCA_IFE_DMAC => Self::Dmac(
+                parse_mac(payload).context("failed to parse TCA_IFE_DMAC")?,
+            ),
+            TCA_IFE_SMAC => Self::Smac(
+                parse_mac(payload).context("failed to parse TCA_IFE_SMAC")?,
+            ),
+            TCA_IFE_TYPE => Self::Type(
+                parse_u16(payload).context("failed to parse TCA_IFE_TYPE")?,
+            ),
+            TCA_IFE_METALST => {
+                let mut metas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_IFE_METALST value")?;
+                    metas.push(TcIfeMeta::parse(&nla)?);
+                }
+                Self::MetaList(metas)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the ife action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcIfe {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    pub flags: TcIfeFlags,
+}
+
+// kernel struct `tc_ife`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcIfeBuffer {
+    generic: TcActionGenericBuffer,
+    flags: u16,
+    // padding to align the struct on 32 bits
+    pad: [u8; 2],
+}
+
+impl TcIfe {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcIfeBuffer::ref_from_prefix(payload).map_err(|_| {
+            DecodeError::buffer_too_small(
+                payload.len(),
+                size_of::<TcIfeBuffer>(),
+            )
+        })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            flags: TcIfeFlags::from_bits_retain(raw.flags),
+        })
+    }
+}
+
+impl From<&TcIfe> for TcIfeBuffer {
+    fn from(ife: &TcIfe) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&ife.generic),
+            flags: ife.flags.bits(),
+            pad: [0; 2],
+        }
+    }
+}
+
+impl Emitable for TcIfe {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcIfeBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcIfeBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const IFE_ENCODE: u16 = 1;
+
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcIfeFlags: u16 {
+        /// Encode the metadata into the packets. Metadata are decoded
+        /// from the packets when this flag is not set.
+        const Encode = IFE_ENCODE;
+        const _ = !0;
+    }
+}
+
+const IFE_META_SKBMARK: u16 = 1;
+const IFE_META_PRIO: u16 = 3;
+const IFE_META_TCINDEX: u16 = 5;
+
+/// Metadata carried by the ife action.
+///
+/// Without a value, the metadata of each packet is encoded. With a value,
+/// this value is encoded instead.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcIfeMeta {
+    /// Packet mark.
+    SkbMark(Option<u32>),
+    /// Packet priority.
+    Prio(Option<u32>),
+    /// Packet tcindex.
+    TcIndex(Option<u16>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcIfeMeta {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::SkbMark(v) | Self::Prio(v) => v.map_or(0, |_| 4),
+            Self::TcIndex(v) => v.map_or(0, |_| 2),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::SkbMark(v) | Self::Prio(v) => {
+                if let Some(v) = v {
+                    emit_u32(buffer, *v).unwrap()
+                }
+            }
+            Self::TcIndex(v) => {
+                if let Some(v) = v {
+                    emit_u16(buffer, *v).unwrap()
+                }
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::SkbMark(_) => IFE_META_SKBMARK,
+            Self::Prio(_) => IFE_META_PRIO,
+            Self::TcIndex(_) => IFE_META_TCINDEX,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcIfeMeta {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFE_META_SKBMARK => Self::SkbMark(if payload.is_empty() {
+                None
+            } else {
+                Some(
+                    parse_u32(payload)
+                        .context("failed to parse IFE_META_SKBMARK")?,
+                )
+            }),
+            IFE_META_PRIO => Self::Prio(if payload.is_empty() {
+                None
+            } else {
+                Some(
+                    parse_u32(payload)
+                        .context("failed to parse IFE_META_PRIO")?,
+                )
+            }),
+            IFE_META_TCINDEX => Self::TcIndex(if payload.is_empty() {
+                None
+            } else {
+                Some(
+                    parse_u16(payload)
+                        .context("failed to parse IFE_META_TCINDEX")?,
+                )
+            }),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
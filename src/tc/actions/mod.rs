@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: MIT
 
+pub use act_flags::{TcActionFlags, TcActionHwStats};
 pub use nat_flag::TcNatFlags;
 
 pub use self::{
@@ -7,7 +8,30 @@ pub use self::{
         TcAction, TcActionAttribute, TcActionGeneric, TcActionGenericBuffer,
         TcActionOption, TcActionType, Tcf, TcfBuffer,
     },
+    bpf::{
+        TcActionBpf, TcActionBpfOption, TcBpfSockFilter, TcBpfSockFilterBuffer,
+    },
+    connmark::{
+        TcActionConnmark, TcActionConnmarkOption, TcConnmark, TcConnmarkBuffer,
+    },
+    csum::{
+        TcActionCsum, TcActionCsumOption, TcCsum, TcCsumBuffer,
+        TcCsumUpdateFlags,
+    },
+    ct::{TcActionCt, TcActionCtOption, TcCtActionFlags},
+    ctinfo::{TcActionCtInfo, TcActionCtInfoOption},
+    gact::{
+        TcActionGact, TcActionGactOption, TcGactProb, TcGactProbBuffer,
+        TcGactProbType,
+    },
+    gate::{
+        TcActionGate, TcActionGateOption, TcGateEntry, TcGateEntryAttribute,
+    },
     header::{TcActionMessageBuffer, TcActionMessageHeader},
+    ife::{
+        TcActionIfe, TcActionIfeOption, TcIfe, TcIfeBuffer, TcIfeFlags,
+        TcIfeMeta,
+    },
     message::{
         TcActionMessage, TcActionMessageAttribute, TcActionMessageFlags,
         TcActionMessageFlagsWithSelector,
@@ -16,17 +40,58 @@ pub use self::{
         TcActionMirror, TcActionMirrorOption, TcMirror, TcMirrorActionType,
         TcMirrorBuffer,
     },
+    mpls::{
+        TcActionMpls, TcActionMplsOption, TcMpls, TcMplsActionType,
+        TcMplsBuffer,
+    },
     nat::{TcActionNat, TcActionNatOption, TcNat, TcNatBuffer},
-    tunnel_key::{TcActionTunnelKey, TcActionTunnelKeyOption, TcTunnelKey},
+    pedit::{
+        TcActionPedit, TcActionPeditOption, TcPeditCmd, TcPeditEdit,
+        TcPeditHeaderType, TcPeditKey, TcPeditKeyBuffer, TcPeditKeyEx,
+        TcPeditKeyExAttribute, TcPeditSel, TcPeditSelBuffer,
+    },
+    police::{TcActionPolice, TcActionPoliceOption, TcPolice, TcPoliceBuffer},
+    sample::{TcActionSample, TcActionSampleOption},
+    skbedit::{
+        TcActionSkbEdit, TcActionSkbEditOption, TcSkbEditFlags, TcSkbEditPtype,
+    },
+    skbmod::{
+        TcActionSkbMod, TcActionSkbModOption, TcSkbMod, TcSkbModBuffer,
+        TcSkbModFlags,
+    },
+    tunnel_key::{
+        TcActionTunnelKey, TcActionTunnelKeyOption, TcTunnelKey,
+        TcTunnelKeyEncOption,
+    },
+    vlan::{
+        TcActionVlan, TcActionVlanOption, TcVlan, TcVlanActionType,
+        TcVlanBuffer,
+    },
 };
 
+mod act_flags;
 mod action;
+mod bpf;
+mod connmark;
+mod csum;
+mod ct;
+mod ctinfo;
+mod gact;
+mod gate;
 mod header;
+mod ife;
 mod message;
 mod mirror;
+mod mpls;
 mod nat;
 mod nat_flag;
+mod pedit;
+mod police;
+mod sample;
+mod skbedit;
+mod skbmod;
 mod tunnel_key;
+mod vlan;
 
 #[cfg(test)]
 pub mod tests;
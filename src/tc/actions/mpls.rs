@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MIT
+
+/// Mpls action
+///
+/// The mpls action pushes, pops or modifies MPLS label stack entries of
+/// packets.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16_be, emit_u32, parse_u16_be, parse_u32, parse_u8, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to manipulate MPLS headers.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionMpls {}
+impl TcActionMpls {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "mpls";
+}
+
+const TCA_MPLS_TM: u16 = 1;
+const TCA_MPLS_PARMS: u16 = 2;
+// const TCA_MPLS_PAD: u16 = 3;
+const TCA_MPLS_PROTO: u16 = 4;
+const TCA_MPLS_LABEL: u16 = 5;
+const TCA_MPLS_TC: u16 = 6;
+const TCA_MPLS_TTL: u16 = 7;
+const TCA_MPLS_BOS: u16 = 8;
+
+/// Options for the `TcActionMpls` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionMplsOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the mpls action.
+    Parms(TcMpls),
+    /// Protocol (EtherType) of the packet after the MPLS header was pushed
+    /// or popped.
+    Proto(u16),
+    /// MPLS label.
+    Label(u32),
+    /// MPLS traffic class.
+    Tc(u8),
+    /// MPLS time to live.
+    Ttl(u8),
+    /// MPLS bottom of stack bit.
+    Bos(u8),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionMplsOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcMplsBuffer>(),
+            Self::Proto(_) => 2,
+            Self::Label(_) => 4,
+            Self::Tc(_) | Self::Ttl(_) | Self::Bos(_) => 1,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Proto(i) => emit_u16_be(buffer, *i).unwrap(),
+            Self::Label(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Tc(i) | Self::Ttl(i) | Self::Bos(i) => buffer[0] = *i,
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_MPLS_TM,
+            Self::Parms(_) => TCA_MPLS_PARMS,
+            Self::Proto(_) => TCA_MPLS_PROTO,
+            Self::Label(_) => TCA_MPLS_LABEL,
+            Self::Tc(_) => TCA_MPLS_TC,
+            Self::Ttl(_) => TCA_MPLS_TTL,
+            Self::Bos(_) => TCA_MPLS_BOS,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionMplsOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_MPLS_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_MPLS_PARMS => Self::Parms(TcMpls::parse(payload)?),
+            TCA_MPLS_PROTO => Self::Proto(
+                parse_u16_be(payload)
+                    .context("failed to parse TCA_MPLS_PROTO")?,
+            ),
+            TCA_MPLS_LABEL => Self::Label(
+                parse_u32(payload).context("failed to parse TCA_MPLS_LABEL")?,
+            ),
+            TCA_MPLS_TC => Self::Tc(
+                parse_u8(payload).context("failed to parse TCA_MPLS_TC")?,
+            ),
+            TCA_MPLS_TTL => Self::Ttl(
+                parse_u8(payload).context("failed to parse TCA_MPLS_TTL")?,
+            ),
+            TCA_MPLS_BOS => Self::Bos(
+                parse_u8(payload).context("failed to parse TCA_MPLS_BOS")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the mpls action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcMpls {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    /// Describes how the MPLS header is modified.
+    pub m_action: TcMplsActionType,
+}
+
+// kernel struct `tc_mpls`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcMplsBuffer {
+    generic: TcActionGenericBuffer,
+    m_action: i32,
+}
+
+impl TcMpls {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcMplsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcMplsBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            m_action: raw.m_action.into(),
+        })
+    }
+}
+
+impl From<&TcMpls> for TcMplsBuffer {
+    fn from(mpls: &TcMpls) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&mpls.generic),
+            m_action: mpls.m_action.into(),
+        }
+    }
+}
+
+impl Emitable for TcMpls {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcMplsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcMplsBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCA_MPLS_ACT_POP: i32 = 1;
+const TCA_MPLS_ACT_PUSH: i32 = 2;
+const TCA_MPLS_ACT_MODIFY: i32 = 3;
+const TCA_MPLS_ACT_DEC_TTL: i32 = 4;
+const TCA_MPLS_ACT_MAC_PUSH: i32 = 5;
+
+/// Type of mpls action.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcMplsActionType {
+    /// Pop the outermost MPLS label stack entry.
+    #[default]
+    Pop,
+    /// Push an MPLS label stack entry after the Ethernet header and VLAN
+    /// tags.
+    Push,
+    /// Modify the outermost MPLS label stack entry.
+    Modify,
+    /// Decrement the TTL of the outermost MPLS label stack entry.
+    DecTtl,
+    /// Push an MPLS label stack entry before the Ethernet header.
+    MacPush,
+    /// Other action type unknown at the time of writing.
+    Other(i32),
+}
+
+impl From<i32> for TcMplsActionType {
+    fn from(d: i32) -> Self {
+        match d {
+            TCA_MPLS_ACT_POP => Self::Pop,
+            TCA_MPLS_ACT_PUSH => Self::Push,
+            TCA_MPLS_ACT_MODIFY => Self::Modify,
+            TCA_MPLS_ACT_DEC_TTL => Self::DecTtl,
+            TCA_MPLS_ACT_MAC_PUSH => Self::MacPush,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcMplsActionType> for i32 {
+    fn from(v: TcMplsActionType) -> i32 {
+        match v {
+            TcMplsActionType::Pop => TCA_MPLS_ACT_POP,
+            TcMplsActionType::Push => TCA_MPLS_ACT_PUSH,
+            TcMplsActionType::Modify => TCA_MPLS_ACT_MODIFY,
+            TcMplsActionType::DecTtl => TCA_MPLS_ACT_DEC_TTL,
+            TcMplsActionType::MacPush => TCA_MPLS_ACT_MAC_PUSH,
+            TcMplsActionType::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,588 @@
+// SPDX-License-Identifier: MIT
+
+/// Pedit action
+///
+/// The pedit action edits arbitrary packet data. Each key masks a 32 bit
+/// word of the packet at a given offset and XORs a value into it. With the
+/// extended attributes, the offset of each key is relative to a typed
+/// header (Ethernet, IPv4, IPv6, TCP or UDP) and the value can be added
+/// instead of being set.
+use std::mem::size_of;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_core::{
+    emit_u16, parse_u16, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, NlasIterator, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to edit packet data.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionPedit {}
+impl TcActionPedit {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "pedit";
+}
+
+const TCA_PEDIT_TM: u16 = 1;
+const TCA_PEDIT_PARMS: u16 = 2;
+// const TCA_PEDIT_PAD: u16 = 3;
+const TCA_PEDIT_PARMS_EX: u16 = 4;
+const TCA_PEDIT_KEYS_EX: u16 = 5;
+
+/// Options for the `TcActionPedit` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionPeditOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters and keys of the pedit action.
+    Parms(TcPeditSel),
+    /// Parameters and keys of the pedit action, used along with
+    /// `KeysEx`.
+    ParmsEx(TcPeditSel),
+    /// Header type and command of each key of `ParmsEx`, in the same
+    /// order.
+    KeysEx(Vec<TcPeditKeyEx>),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl TcActionPeditOption {
+    /// Generate the `ParmsEx` and `KeysEx` options applying the specified
+    /// edits. Fail when the edits need more than [`TcPeditSel::MAX_KEYS`]
+    /// keys.
+    pub fn from_edits(
+        generic: TcActionGeneric,
+        edits: &[TcPeditEdit],
+    ) -> Result<Vec<Self>, DecodeError> {
+        let mut keys = Vec::new();
+        let mut keys_ex = Vec::new();
+        for edit in edits {
+            let htype = edit.header_type();
+            for key in edit.keys() {
+                keys.push(key);
+                keys_ex.push(TcPeditKeyEx::KeyEx(vec![
+                    TcPeditKeyExAttribute::Htype(htype),
+                    TcPeditKeyExAttribute::Cmd(TcPeditCmd::Set),
+                ]));
+            }
+        }
+        if keys.len() > TcPeditSel::MAX_KEYS {
+            return Err(DecodeError::from(format!(
+                "pedit edits need {} keys, more than the maximum {}",
+                keys.len(),
+                TcPeditSel::MAX_KEYS
+            )));
+        }
+        Ok(vec![
+            Self::ParmsEx(TcPeditSel {
+                generic,
+                flags: 0,
+                keys,
+            }),
+            Self::KeysEx(keys_ex),
+        ])
+    }
+}
+
+impl Nla for TcActionPeditOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(v) | Self::ParmsEx(v) => v.buffer_len(),
+            Self::KeysEx(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) | Self::ParmsEx(p) => p.emit(buffer),
+            Self::KeysEx(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_PEDIT_TM,
+            Self::Parms(_) => TCA_PEDIT_PARMS,
+            Self::ParmsEx(_) => TCA_PEDIT_PARMS_EX,
+            Self::KeysEx(_) => TCA_PEDIT_KEYS_EX,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionPeditOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_PEDIT_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_PEDIT_PARMS => Self::Parms(
+                TcPeditSel::parse(payload)
+                    .context("failed to parse TCA_PEDIT_PARMS")?,
+            ),
+            TCA_PEDIT_PARMS_EX => Self::ParmsEx(
+                TcPeditSel::parse(payload)
+                    .context("failed to parse TCA_PEDIT_PARMS_EX")?,
+            ),
+            TCA_PEDIT_KEYS_EX => {
+                let mut keys = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_PEDIT_KEYS_EX value")?;
+                    keys.push(TcPeditKeyEx::parse(&nla)?);
+                }
+                Self::KeysEx(keys)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters and keys of the pedit action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcPeditSel {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    pub flags: u8,
+    /// Edits applied to the packet, in order. Only the first
+    /// [`TcPeditSel::MAX_KEYS`] keys are emitted.
+    pub keys: Vec<TcPeditKey>,
+}
+
+// kernel struct `tc_pedit_sel`, followed by `nkeys` `tc_pedit_key`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcPeditSelBuffer {
+    generic: TcActionGenericBuffer,
+    nkeys: u8,
+    flags: u8,
+    // padding to align the keys on 32 bits
+    pad: [u8; 2],
+}
+
+impl TcPeditSel {
+    /// Maximum number of keys, counted by the 8 bits `nkeys` of the kernel
+    /// structure.
+    pub const MAX_KEYS: usize = u8::MAX as usize;
+
+    // Keys actually emitted, matching the emitted `nkeys`
+    fn emitted_keys(&self) -> &[TcPeditKey] {
+        &self.keys[..self.keys.len().min(Self::MAX_KEYS)]
+    }
+
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, mut keys_payload) =
+            TcPeditSelBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcPeditSelBuffer>(),
+                )
+            })?;
+        let mut keys = Vec::with_capacity(raw.nkeys as usize);
+        for _ in 0..raw.nkeys {
+            let (key, rest) = TcPeditKeyBuffer::ref_from_prefix(keys_payload)
+                .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    keys_payload.len(),
+                    size_of::<TcPeditKeyBuffer>(),
+                )
+            })?;
+            keys.push(TcPeditKey::from(key));
+            keys_payload = rest;
+        }
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            flags: raw.flags,
+            keys,
+        })
+    }
+}
+
+impl Emitable for TcPeditSel {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcPeditSelBuffer>()
+            + self.emitted_keys().len() * size_of::<TcPeditKeyBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcPeditSelBuffer {
+            generic: TcActionGenericBuffer::from(&self.generic),
+            nkeys: self.emitted_keys().len() as u8,
+            flags: self.flags,
+            pad: [0; 2],
+        };
+        buffer[..size_of::<TcPeditSelBuffer>()].copy_from_slice(raw.as_bytes());
+        for (key, chunk) in self.emitted_keys().iter().zip(
+            buffer[size_of::<TcPeditSelBuffer>()..]
+                .chunks_mut(size_of::<TcPeditKeyBuffer>()),
+        ) {
+            chunk.copy_from_slice(TcPeditKeyBuffer::from(key).as_bytes());
+        }
+    }
+}
+
+/// A single edit of the pedit action.
+///
+/// The 32 bit word of the packet at `off` is replaced by
+/// `(word & mask) ^ val`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcPeditKey {
+    /// Bits of the packet word to keep.
+    pub mask: u32,
+    /// Value XORed into the packet word after masking.
+    pub val: u32,
+    /// Offset of the packet word, relative to the network header or to
+    /// the header type of the matching `TcPeditKeyEx`.
+    pub off: i32,
+    /// Offset of the byte used to compute an additional offset.
+    pub at: u32,
+    /// Mask applied to the byte at `at`.
+    pub offmask: u32,
+    /// Right shift applied to the masked byte at `at`.
+    pub shift: u32,
+}
+
+// kernel struct `tc_pedit_key`: `mask` and `val` are compared to the raw
+// packet data, so they are stored big-endian like the packet.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcPeditKeyBuffer {
+    mask: u32,
+    val: u32,
+    off: i32,
+    at: u32,
+    offmask: u32,
+    shift: u32,
+}
+
+impl From<&TcPeditKeyBuffer> for TcPeditKey {
+    fn from(raw: &TcPeditKeyBuffer) -> Self {
+        Self {
+            mask: u32::from_be(raw.mask),
+            val: u32::from_be(raw.val),
+            off: raw.off,
+            at: raw.at,
+            offmask: raw.offmask,
+            shift: raw.shift,
+        }
+    }
+}
+
+impl From<&TcPeditKey> for TcPeditKeyBuffer {
+    fn from(key: &TcPeditKey) -> Self {
+        Self {
+            mask: key.mask.to_be(),
+            val: key.val.to_be(),
+            off: key.off,
+            at: key.at,
+            offmask: key.offmask,
+            shift: key.shift,
+        }
+    }
+}
+
+const TCA_PEDIT_KEY_EX: u16 = 6;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcPeditKeyEx {
+    KeyEx(Vec<TcPeditKeyExAttribute>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcPeditKeyEx {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::KeyEx(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::KeyEx(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::KeyEx(_) => TCA_PEDIT_KEY_EX,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcPeditKeyEx {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_PEDIT_KEY_EX => {
+                let mut attrs = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_PEDIT_KEY_EX value")?;
+                    attrs.push(TcPeditKeyExAttribute::parse(&nla)?);
+                }
+                Self::KeyEx(attrs)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const TCA_PEDIT_KEY_EX_HTYPE: u16 = 1;
+const TCA_PEDIT_KEY_EX_CMD: u16 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcPeditKeyExAttribute {
+    /// Header the offset of the key is relative to.
+    Htype(TcPeditHeaderType),
+    /// How the value of the key is applied.
+    Cmd(TcPeditCmd),
+    Other(DefaultNla),
+}
+
+impl Nla for TcPeditKeyExAttribute {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Htype(_) | Self::Cmd(_) => 2,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Htype(v) => emit_u16(buffer, (*v).into()).unwrap(),
+            Self::Cmd(v) => emit_u16(buffer, (*v).into()).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Htype(_) => TCA_PEDIT_KEY_EX_HTYPE,
+            Self::Cmd(_) => TCA_PEDIT_KEY_EX_CMD,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcPeditKeyExAttribute
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_PEDIT_KEY_EX_HTYPE => Self::Htype(
+                parse_u16(payload)
+                    .context("failed to parse TCA_PEDIT_KEY_EX_HTYPE")?
+                    .into(),
+            ),
+            TCA_PEDIT_KEY_EX_CMD => Self::Cmd(
+                parse_u16(payload)
+                    .context("failed to parse TCA_PEDIT_KEY_EX_CMD")?
+                    .into(),
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const TCA_PEDIT_KEY_EX_HDR_TYPE_NETWORK: u16 = 0;
+const TCA_PEDIT_KEY_EX_HDR_TYPE_ETH: u16 = 1;
+const TCA_PEDIT_KEY_EX_HDR_TYPE_IP4: u16 = 2;
+const TCA_PEDIT_KEY_EX_HDR_TYPE_IP6: u16 = 3;
+const TCA_PEDIT_KEY_EX_HDR_TYPE_TCP: u16 = 4;
+const TCA_PEDIT_KEY_EX_HDR_TYPE_UDP: u16 = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum TcPeditHeaderType {
+    Network,
+    Eth,
+    Ip4,
+    Ip6,
+    Tcp,
+    Udp,
+    Other(u16),
+}
+
+impl From<u16> for TcPeditHeaderType {
+    fn from(d: u16) -> Self {
+        match d {
+            TCA_PEDIT_KEY_EX_HDR_TYPE_NETWORK => Self::Network,
+            TCA_PEDIT_KEY_EX_HDR_TYPE_ETH => Self::Eth,
+            TCA_PEDIT_KEY_EX_HDR_TYPE_IP4 => Self::Ip4,
+            TCA_PEDIT_KEY_EX_HDR_TYPE_IP6 => Self::Ip6,
+            TCA_PEDIT_KEY_EX_HDR_TYPE_TCP => Self::Tcp,
+            TCA_PEDIT_KEY_EX_HDR_TYPE_UDP => Self::Udp,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcPeditHeaderType> for u16 {
+    fn from(v: TcPeditHeaderType) -> u16 {
+        match v {
+            TcPeditHeaderType::Network => TCA_PEDIT_KEY_EX_HDR_TYPE_NETWORK,
+            TcPeditHeaderType::Eth => TCA_PEDIT_KEY_EX_HDR_TYPE_ETH,
+            TcPeditHeaderType::Ip4 => TCA_PEDIT_KEY_EX_HDR_TYPE_IP4,
+            TcPeditHeaderType::Ip6 => TCA_PEDIT_KEY_EX_HDR_TYPE_IP6,
+            TcPeditHeaderType::Tcp => TCA_PEDIT_KEY_EX_HDR_TYPE_TCP,
+            TcPeditHeaderType::Udp => TCA_PEDIT_KEY_EX_HDR_TYPE_UDP,
+            TcPeditHeaderType::Other(d) => d,
+        }
+    }
+}
+
+const TCA_PEDIT_KEY_EX_CMD_SET: u16 = 0;
+const TCA_PEDIT_KEY_EX_CMD_ADD: u16 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum TcPeditCmd {
+    /// Replace the masked bits of the packet word by the value.
+    Set,
+    /// Add the value to the packet word.
+    Add,
+    Other(u16),
+}
+
+impl From<u16> for TcPeditCmd {
+    fn from(d: u16) -> Self {
+        match d {
+            TCA_PEDIT_KEY_EX_CMD_SET => Self::Set,
+            TCA_PEDIT_KEY_EX_CMD_ADD => Self::Add,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcPeditCmd> for u16 {
+    fn from(v: TcPeditCmd) -> u16 {
+        match v {
+            TcPeditCmd::Set => TCA_PEDIT_KEY_EX_CMD_SET,
+            TcPeditCmd::Add => TCA_PEDIT_KEY_EX_CMD_ADD,
+            TcPeditCmd::Other(d) => d,
+        }
+    }
+}
+
+/// Header field rewrite, converted into pedit keys by
+/// [`TcActionPeditOption::from_edits`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum TcPeditEdit {
+    EthDst([u8; 6]),
+    EthSrc([u8; 6]),
+    EthType(u16),
+    Ipv4Tos(u8),
+    Ipv4Ttl(u8),
+    Ipv4Src(Ipv4Addr),
+    Ipv4Dst(Ipv4Addr),
+    Ipv6HopLimit(u8),
+    Ipv6Src(Ipv6Addr),
+    Ipv6Dst(Ipv6Addr),
+    TcpSport(u16),
+    TcpDport(u16),
+    UdpSport(u16),
+    UdpDport(u16),
+}
+
+impl TcPeditEdit {
+    /// Header the field belongs to.
+    pub fn header_type(&self) -> TcPeditHeaderType {
+        match self {
+            Self::EthDst(_) | Self::EthSrc(_) | Self::EthType(_) => {
+                TcPeditHeaderType::Eth
+            }
+            Self::Ipv4Tos(_)
+            | Self::Ipv4Ttl(_)
+            | Self::Ipv4Src(_)
+            | Self::Ipv4Dst(_) => TcPeditHeaderType::Ip4,
+            Self::Ipv6HopLimit(_) | Self::Ipv6Src(_) | Self::Ipv6Dst(_) => {
+                TcPeditHeaderType::Ip6
+            }
+            Self::TcpSport(_) | Self::TcpDport(_) => TcPeditHeaderType::Tcp,
+            Self::UdpSport(_) | Self::UdpDport(_) => TcPeditHeaderType::Udp,
+        }
+    }
+
+    /// Keys setting the field, with offsets relative to its header.
+    pub fn keys(&self) -> Vec<TcPeditKey> {
+        match self {
+            Self::EthDst(v) => set_keys(0, v),
+            Self::EthSrc(v) => set_keys(6, v),
+            Self::EthType(v) => set_keys(12, &v.to_be_bytes()),
+            Self::Ipv4Tos(v) => set_keys(1, &[*v]),
+            Self::Ipv4Ttl(v) => set_keys(8, &[*v]),
+            Self::Ipv4Src(v) => set_keys(12, &v.octets()),
+            Self::Ipv4Dst(v) => set_keys(16, &v.octets()),
+            Self::Ipv6HopLimit(v) => set_keys(7, &[*v]),
+            Self::Ipv6Src(v) => set_keys(8, &v.octets()),
+            Self::Ipv6Dst(v) => set_keys(24, &v.octets()),
+            Self::TcpSport(v) | Self::UdpSport(v) => {
+                set_keys(0, &v.to_be_bytes())
+            }
+            Self::TcpDport(v) | Self::UdpDport(v) => {
+                set_keys(2, &v.to_be_bytes())
+            }
+        }
+    }
+}
+
+// Generate the keys setting `value` at `offset`, one per 32 bit word
+// touched.
+fn set_keys(offset: i32, value: &[u8]) -> Vec<TcPeditKey> {
+    let mut keys: Vec<TcPeditKey> = Vec::new();
+    for (i, byte) in value.iter().enumerate() {
+        let pos = offset + i as i32;
+        let off = pos & !3;
+        let shift = 8 * (3 - (pos & 3)) as u32;
+        if keys.last().map(|k| k.off) != Some(off) {
+            keys.push(TcPeditKey {
+                mask: u32::MAX,
+                off,
+                ..Default::default()
+            });
+        }
+        if let Some(key) = keys.last_mut() {
+            key.mask &= !(0xff << shift);
+            key.val |= (*byte as u32) << shift;
+        }
+    }
+    keys
+}
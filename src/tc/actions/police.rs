@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: MIT
+
+/// Police action
+///
+/// The police action limits the rate of the packets it receives by either
+/// bytes or packets per second, applying a control action to the packets
+/// exceeding the configured limits.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u32, emit_u64, parse_u32, parse_u64, DecodeError, DefaultNla,
+    Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionType, Tcf, TcfBuffer};
+use crate::tc::{TcRateSpec, TcRateSpecBuffer};
+
+/// Traffic control action used to police (rate limit) packets.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionPolice {}
+impl TcActionPolice {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "police";
+}
+
+const TCA_POLICE_TBF: u16 = 1;
+const TCA_POLICE_RATE: u16 = 2;
+const TCA_POLICE_PEAKRATE: u16 = 3;
+const TCA_POLICE_AVRATE: u16 = 4;
+const TCA_POLICE_RESULT: u16 = 5;
+const TCA_POLICE_TM: u16 = 6;
+// const TCA_POLICE_PAD: u16 = 7;
+const TCA_POLICE_RATE64: u16 = 8;
+const TCA_POLICE_PEAKRATE64: u16 = 9;
+const TCA_POLICE_PKTRATE64: u16 = 10;
+const TCA_POLICE_PKTBURST64: u16 = 11;
+
+/// Options for the `TcActionPolice` action.
+///
+/// The same options are used by the `Police` attribute of classifiers.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionPoliceOption {
+    /// Token bucket parameters of the police action.
+    Tbf(TcPolice),
+    /// Rate table of [`TcPolice::rate`], only sent by userspace.
+    Rate(Vec<u32>),
+    /// Rate table of [`TcPolice::peakrate`], only sent by userspace.
+    PeakRate(Vec<u32>),
+    /// Average rate in bytes per second, compared against the rate
+    /// estimator of the action.
+    AvRate(u32),
+    /// Control action applied to conforming packets.
+    Result(TcActionType),
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Rate in bytes per second when it does not fit in
+    /// [`TcRateSpec::rate`].
+    Rate64(u64),
+    /// Peak rate in bytes per second when it does not fit in
+    /// [`TcRateSpec::rate`].
+    PeakRate64(u64),
+    /// Rate in packets per second.
+    PktRate64(u64),
+    /// Burst size in packets.
+    PktBurst64(u64),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionPoliceOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tbf(_) => size_of::<TcPoliceBuffer>(),
+            Self::Rate(t) | Self::PeakRate(t) => t.len() * 4,
+            Self::AvRate(_) | Self::Result(_) => 4,
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Rate64(_)
+            | Self::PeakRate64(_)
+            | Self::PktRate64(_)
+            | Self::PktBurst64(_) => 8,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tbf(p) => p.emit(buffer),
+            Self::Rate(t) | Self::PeakRate(t) => {
+                for (i, v) in t.iter().enumerate() {
+                    emit_u32(&mut buffer[i * 4..], *v).unwrap();
+                }
+            }
+            Self::AvRate(v) => emit_u32(buffer, *v).unwrap(),
+            Self::Result(v) => emit_u32(buffer, i32::from(*v) as u32).unwrap(),
+            Self::Tm(p) => p.emit(buffer),
+            Self::Rate64(v)
+            | Self::PeakRate64(v)
+            | Self::PktRate64(v)
+            | Self::PktBurst64(v) => emit_u64(buffer, *v).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tbf(_) => TCA_POLICE_TBF,
+            Self::Rate(_) => TCA_POLICE_RATE,
+            Self::PeakRate(_) => TCA_POLICE_PEAKRATE,
+            Self::AvRate(_) => TCA_POLICE_AVRATE,
+            Self::Result(_) => TCA_POLICE_RESULT,
+            Self::Tm(_) => TCA_POLICE_TM,
+            Self::Rate64(_) => TCA_POLICE_RATE64,
+            Self::PeakRate64(_) => TCA_POLICE_PEAKRATE64,
+            Self::PktRate64(_) => TCA_POLICE_PKTRATE64,
+            Self::PktBurst64(_) => TCA_POLICE_PKTBURST64,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+fn parse_rate_table(payload: &[u8]) -> Result<Vec<u32>, DecodeError> {
+    payload.chunks(4).map(parse_u32).collect()
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionPoliceOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_POLICE_TBF => Self::Tbf(TcPolice::parse(payload)?),
+            TCA_POLICE_RATE => Self::Rate(
+                parse_rate_table(payload)
+                    .context("failed to parse TCA_POLICE_RATE")?,
+            ),
+            TCA_POLICE_PEAKRATE => Self::PeakRate(
+                parse_rate_table(payload)
+                    .context("failed to parse TCA_POLICE_PEAKRATE")?,
+            ),
+            TCA_POLICE_AVRATE => Self::AvRate(
+                parse_u32(payload)
+                    .context("failed to parse TCA_POLICE_AVRATE")?,
+            ),
+            TCA_POLICE_RESULT => Self::Result(
+                (parse_u32(payload)
+                    .context("failed to parse TCA_POLICE_RESULT")?
+                    as i32)
+                    .into(),
+            ),
+            TCA_POLICE_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_POLICE_RATE64 => Self::Rate64(
+                parse_u64(payload)
+                    .context("failed to parse TCA_POLICE_RATE64")?,
+            ),
+            TCA_POLICE_PEAKRATE64 => Self::PeakRate64(
+                parse_u64(payload)
+                    .context("failed to parse TCA_POLICE_PEAKRATE64")?,
+            ),
+            TCA_POLICE_PKTRATE64 => Self::PktRate64(
+                parse_u64(payload)
+                    .context("failed to parse TCA_POLICE_PKTRATE64")?,
+            ),
+            TCA_POLICE_PKTBURST64 => Self::PktBurst64(
+                parse_u64(payload)
+                    .context("failed to parse TCA_POLICE_PKTBURST64")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Token bucket parameters for the police action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcPolice {
+    /// Index of the action, see [`TcActionGeneric::index`].
+    ///
+    /// [`TcActionGeneric::index`]: super::TcActionGeneric::index
+    pub index: u32,
+    /// Control action applied to packets exceeding the limits.
+    pub action: TcActionType,
+    /// Queue limit, unused by the police action.
+    pub limit: u32,
+    /// Size of the token bucket in time units (`PSCHED_TICKS`).
+    pub burst: u32,
+    /// Maximum packet size, larger packets exceed the limits.
+    pub mtu: u32,
+    pub rate: TcRateSpec,
+    pub peakrate: TcRateSpec,
+    pub refcnt: i32,
+    pub bindcnt: i32,
+    pub capab: u32,
+}
+
+// kernel struct `tc_police`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcPoliceBuffer {
+    index: u32,
+    action: i32,
+    limit: u32,
+    burst: u32,
+    mtu: u32,
+    rate: TcRateSpecBuffer,
+    peakrate: TcRateSpecBuffer,
+    refcnt: i32,
+    bindcnt: i32,
+    capab: u32,
+}
+
+impl TcPolice {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcPoliceBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcPoliceBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            index: raw.index,
+            action: raw.action.into(),
+            limit: raw.limit,
+            burst: raw.burst,
+            mtu: raw.mtu,
+            rate: TcRateSpec::parse(raw.rate.as_bytes())?,
+            peakrate: TcRateSpec::parse(raw.peakrate.as_bytes())?,
+            refcnt: raw.refcnt,
+            bindcnt: raw.bindcnt,
+            capab: raw.capab,
+        })
+    }
+}
+
+impl From<&TcPolice> for TcPoliceBuffer {
+    fn from(police: &TcPolice) -> Self {
+        Self {
+            index: police.index,
+            action: police.action.into(),
+            limit: police.limit,
+            burst: police.burst,
+            mtu: police.mtu,
+            rate: TcRateSpecBuffer::from(&police.rate),
+            peakrate: TcRateSpecBuffer::from(&police.peakrate),
+            refcnt: police.refcnt,
+            bindcnt: police.bindcnt,
+            capab: police.capab,
+        }
+    }
+}
+
+impl Emitable for TcPolice {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcPoliceBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcPoliceBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
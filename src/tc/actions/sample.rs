@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+
+/// Sample action
+///
+/// The sample action sends a random sample of the packets to a psample
+/// group.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u32, parse_u32, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, Parseable,
+};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to sample packets.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionSample {}
+impl TcActionSample {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "sample";
+}
+
+const TCA_SAMPLE_TM: u16 = 1;
+const TCA_SAMPLE_PARMS: u16 = 2;
+const TCA_SAMPLE_RATE: u16 = 3;
+const TCA_SAMPLE_TRUNC_SIZE: u16 = 4;
+const TCA_SAMPLE_PSAMPLE_GROUP: u16 = 5;
+// const TCA_SAMPLE_PAD: u16 = 6;
+
+/// Options for the `TcActionSample` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionSampleOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Generic parameters for the sample action.
+    Parms(TcActionGeneric),
+    /// One packet out of `rate` is sampled.
+    Rate(u32),
+    /// Size the sampled packets are truncated to.
+    TruncSize(u32),
+    /// Psample group the sampled packets are sent to.
+    PsampleGroup(u32),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionSampleOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::Rate(_) | Self::TruncSize(_) | Self::PsampleGroup(_) => 4,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Rate(i) | Self::TruncSize(i) | Self::PsampleGroup(i) => {
+                emit_u32(buffer, *i).unwrap()
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_SAMPLE_TM,
+            Self::Parms(_) => TCA_SAMPLE_PARMS,
+            Self::Rate(_) => TCA_SAMPLE_RATE,
+            Self::TruncSize(_) => TCA_SAMPLE_TRUNC_SIZE,
+            Self::PsampleGroup(_) => TCA_SAMPLE_PSAMPLE_GROUP,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionSampleOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_SAMPLE_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_SAMPLE_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_SAMPLE_RATE => Self::Rate(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SAMPLE_RATE")?,
+            ),
+            TCA_SAMPLE_TRUNC_SIZE => Self::TruncSize(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SAMPLE_TRUNC_SIZE")?,
+            ),
+            TCA_SAMPLE_PSAMPLE_GROUP => Self::PsampleGroup(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SAMPLE_PSAMPLE_GROUP")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT
+
+/// Skbedit action
+///
+/// The skbedit action changes packet metadata: priority, transmit queue,
+/// firewall mark and packet type.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, emit_u32, emit_u64, parse_u16, parse_u32, parse_u64, DecodeError,
+    DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+use crate::tc::TcHandle;
+
+/// Traffic control action used to edit packet metadata.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionSkbEdit {}
+impl TcActionSkbEdit {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "skbedit";
+}
+
+const TCA_SKBEDIT_TM: u16 = 1;
+const TCA_SKBEDIT_PARMS: u16 = 2;
+const TCA_SKBEDIT_PRIORITY: u16 = 3;
+const TCA_SKBEDIT_QUEUE_MAPPING: u16 = 4;
+const TCA_SKBEDIT_MARK: u16 = 5;
+// const TCA_SKBEDIT_PAD: u16 = 6;
+const TCA_SKBEDIT_PTYPE: u16 = 7;
+const TCA_SKBEDIT_MASK: u16 = 8;
+const TCA_SKBEDIT_FLAGS: u16 = 9;
+const TCA_SKBEDIT_QUEUE_MAPPING_MAX: u16 = 10;
+
+/// Options for the `TcActionSkbEdit` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionSkbEditOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the skbedit action.
+    Parms(TcActionGeneric),
+    /// Packet priority, usually the class of a classful qdisc.
+    Priority(TcHandle),
+    /// Transmit queue, or the first queue of the range when
+    /// [`QueueMappingMax`] is set.
+    ///
+    /// [`QueueMappingMax`]: #variant.QueueMappingMax
+    QueueMapping(u16),
+    /// Last transmit queue of the range selected by packet hash.
+    QueueMappingMax(u16),
+    /// Firewall mark.
+    Mark(u32),
+    /// Mask of the bits of [`Mark`] to change.
+    ///
+    /// [`Mark`]: #variant.Mark
+    Mask(u32),
+    /// Packet type.
+    Ptype(TcSkbEditPtype),
+    Flags(TcSkbEditFlags),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionSkbEditOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcActionGenericBuffer>(),
+            Self::QueueMapping(_)
+            | Self::QueueMappingMax(_)
+            | Self::Ptype(_) => 2,
+            Self::Priority(_) | Self::Mark(_) | Self::Mask(_) => 4,
+            Self::Flags(_) => 8,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Priority(h) => emit_u32(buffer, (*h).into()).unwrap(),
+            Self::QueueMapping(i) | Self::QueueMappingMax(i) => {
+                emit_u16(buffer, *i).unwrap()
+            }
+            Self::Mark(i) | Self::Mask(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Ptype(p) => emit_u16(buffer, (*p).into()).unwrap(),
+            Self::Flags(f) => emit_u64(buffer, f.bits()).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_SKBEDIT_TM,
+            Self::Parms(_) => TCA_SKBEDIT_PARMS,
+            Self::Priority(_) => TCA_SKBEDIT_PRIORITY,
+            Self::QueueMapping(_) => TCA_SKBEDIT_QUEUE_MAPPING,
+            Self::QueueMappingMax(_) => TCA_SKBEDIT_QUEUE_MAPPING_MAX,
+            Self::Mark(_) => TCA_SKBEDIT_MARK,
+            Self::Mask(_) => TCA_SKBEDIT_MASK,
+            Self::Ptype(_) => TCA_SKBEDIT_PTYPE,
+            Self::Flags(_) => TCA_SKBEDIT_FLAGS,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionSkbEditOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_SKBEDIT_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_SKBEDIT_PARMS => Self::Parms(TcActionGeneric::parse(payload)?),
+            TCA_SKBEDIT_PRIORITY => Self::Priority(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SKBEDIT_PRIORITY")?
+                    .into(),
+            ),
+            TCA_SKBEDIT_QUEUE_MAPPING => Self::QueueMapping(
+                parse_u16(payload)
+                    .context("failed to parse TCA_SKBEDIT_QUEUE_MAPPING")?,
+            ),
+            TCA_SKBEDIT_QUEUE_MAPPING_MAX => Self::QueueMappingMax(
+                parse_u16(payload)
+                    .context("failed to parse TCA_SKBEDIT_QUEUE_MAPPING_MAX")?,
+            ),
+            TCA_SKBEDIT_MARK => Self::Mark(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SKBEDIT_MARK")?,
+            ),
+            TCA_SKBEDIT_MASK => Self::Mask(
+                parse_u32(payload)
+                    .context("failed to parse TCA_SKBEDIT_MASK")?,
+            ),
+            TCA_SKBEDIT_PTYPE => Self::Ptype(
+                parse_u16(payload)
+                    .context("failed to parse TCA_SKBEDIT_PTYPE")?
+                    .into(),
+            ),
+            TCA_SKBEDIT_FLAGS => Self::Flags(TcSkbEditFlags::from_bits_retain(
+                parse_u64(payload)
+                    .context("failed to parse TCA_SKBEDIT_FLAGS")?,
+            )),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+const SKBEDIT_F_PRIORITY: u64 = 0x1;
+const SKBEDIT_F_QUEUE_MAPPING: u64 = 0x2;
+const SKBEDIT_F_MARK: u64 = 0x4;
+const SKBEDIT_F_PTYPE: u64 = 0x8;
+const SKBEDIT_F_MASK: u64 = 0x10;
+const SKBEDIT_F_INHERITDSFIELD: u64 = 0x20;
+const SKBEDIT_F_TXQ_SKBHASH: u64 = 0x40;
+
+bitflags! {
+    /// Flags of the skbedit action.
+    ///
+    /// Only `InheritDsfield` and `TxqSkbhash` are carried by
+    /// `TCA_SKBEDIT_FLAGS`, the others are implied by the presence of the
+    /// matching attribute.
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcSkbEditFlags: u64 {
+        const Priority = SKBEDIT_F_PRIORITY;
+        const QueueMapping = SKBEDIT_F_QUEUE_MAPPING;
+        const Mark = SKBEDIT_F_MARK;
+        const Ptype = SKBEDIT_F_PTYPE;
+        const Mask = SKBEDIT_F_MASK;
+        /// Set the priority from the DS field of the IP header
+        const InheritDsfield = SKBEDIT_F_INHERITDSFIELD;
+        /// Pick the transmit queue from the queue range by packet hash
+        const TxqSkbhash = SKBEDIT_F_TXQ_SKBHASH;
+        const _ = !0;
+    }
+}
+
+const PACKET_HOST: u16 = 0;
+const PACKET_BROADCAST: u16 = 1;
+const PACKET_MULTICAST: u16 = 2;
+const PACKET_OTHERHOST: u16 = 3;
+
+/// Packet type set by the skbedit action.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcSkbEditPtype {
+    /// To us.
+    #[default]
+    Host,
+    /// To all.
+    Broadcast,
+    /// To group.
+    Multicast,
+    /// To someone else.
+    OtherHost,
+    /// Other packet type unknown at the time of writing.
+    Other(u16),
+}
+
+impl From<u16> for TcSkbEditPtype {
+    fn from(d: u16) -> Self {
+        match d {
+            PACKET_HOST => Self::Host,
+            PACKET_BROADCAST => Self::Broadcast,
+            PACKET_MULTICAST => Self::Multicast,
+            PACKET_OTHERHOST => Self::OtherHost,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcSkbEditPtype> for u16 {
+    fn from(v: TcSkbEditPtype) -> u16 {
+        match v {
+            TcSkbEditPtype::Host => PACKET_HOST,
+            TcSkbEditPtype::Broadcast => PACKET_BROADCAST,
+            TcSkbEditPtype::Multicast => PACKET_MULTICAST,
+            TcSkbEditPtype::OtherHost => PACKET_OTHERHOST,
+            TcSkbEditPtype::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT
+
+/// Skbmod action
+///
+/// The skbmod action rewrites the Ethernet header of packets: destination
+/// and source MAC addresses and EtherType, or swaps the MAC addresses.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, parse_mac, parse_u16, DecodeError, DefaultNla, Emitable,
+    ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to modify the Ethernet header of packets.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionSkbMod {}
+impl TcActionSkbMod {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "skbmod";
+}
+
+const TCA_SKBMOD_TM: u16 = 1;
+const TCA_SKBMOD_PARMS: u16 = 2;
+const TCA_SKBMOD_DMAC: u16 = 3;
+const TCA_SKBMOD_SMAC: u16 = 4;
+const TCA_SKBMOD_ETYPE: u16 = 5;
+
+/// Options for the `TcActionSkbMod` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionSkbModOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the skbmod action.
+    Parms(TcSkbMod),
+    /// New destination MAC address.
+    Dmac([u8; 6]),
+    /// New source MAC address.
+    Smac([u8; 6]),
+    /// New EtherType.
+    Etype(u16),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionSkbModOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcSkbModBuffer>(),
+            Self::Dmac(_) | Self::Smac(_) => 6,
+            Self::Etype(_) => 2,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::Dmac(mac) | Self::Smac(mac) => {
+                buffer.copy_from_slice(mac.as_slice())
+            }
+            Self::Etype(i) => emit_u16(buffer, *i).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_SKBMOD_TM,
+            Self::Parms(_) => TCA_SKBMOD_PARMS,
+            Self::Dmac(_) => TCA_SKBMOD_DMAC,
+            Self::Smac(_) => TCA_SKBMOD_SMAC,
+            Self::Etype(_) => TCA_SKBMOD_ETYPE,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionSkbModOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_SKBMOD_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_SKBMOD_PARMS => Self::Parms(TcSkbMod::parse(payload)?),
+            TCA_SKBMOD_DMAC => Self::Dmac(
+                parse_mac(payload)
+                    .context("failed to parse TCA_SKBMOD_DMAC")?,
+            ),
+            TCA_SKBMOD_SMAC => Self::Smac(
+                parse_mac(payload)
+                    .context("failed to parse TCA_SKBMOD_SMAC")?,
+            ),
+            TCA_SKBMOD_ETYPE => Self::Etype(
+                parse_u16(payload)
+                    .context("failed to parse TCA_SKBMOD_ETYPE")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the skbmod action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcSkbMod {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    /// Which parts of the Ethernet header are modified.
+    pub flags: TcSkbModFlags,
+}
+
+// kernel struct `tc_skbmod`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcSkbModBuffer {
+    generic: TcActionGenericBuffer,
+    // padding to align `flags` on 64 bits
+    pad: [u8; 4],
+    flags: u64,
+}
+
+impl TcSkbMod {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcSkbModBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcSkbModBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            flags: TcSkbModFlags::from_bits_retain(raw.flags),
+        })
+    }
+}
+
+impl From<&TcSkbMod> for TcSkbModBuffer {
+    fn from(skbmod: &TcSkbMod) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&skbmod.generic),
+            pad: [0; 4],
+            flags: skbmod.flags.bits(),
+        }
+    }
+}
+
+impl Emitable for TcSkbMod {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcSkbModBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcSkbModBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const SKBMOD_F_DMAC: u64 = 0x1;
+const SKBMOD_F_SMAC: u64 = 0x2;
+const SKBMOD_F_ETYPE: u64 = 0x4;
+const SKBMOD_F_SWAPMAC: u64 = 0x8;
+const SKBMOD_F_ECN: u64 = 0x10;
+
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcSkbModFlags: u64 {
+        const Dmac = SKBMOD_F_DMAC;
+        const Smac = SKBMOD_F_SMAC;
+        const Etype = SKBMOD_F_ETYPE;
+        /// Swap the destination and source MAC addresses
+        const SwapMac = SKBMOD_F_SWAPMAC;
+        /// Mark ECN capable packets as congestion experienced
+        const Ecn = SKBMOD_F_ECN;
+        const _ = !0;
+    }
+}
@@ -1,10 +1,14 @@
 // SPDX-License-Identifier: MIT
 
-use netlink_packet_core::{Emitable, NlaBuffer, Parseable};
+use std::time::Duration;
+
+use netlink_packet_core::{
+    Emitable, NlaBuffer, Parseable, ParseableParametrized,
+};
 
 use crate::tc::{
-    TcAction, TcActionAttribute, TcActionGeneric, TcActionType, TcStats2,
-    TcStatsBasic,
+    TcAction, TcActionAttribute, TcActionFlags, TcActionGeneric,
+    TcActionHwStats, TcActionType, TcStats2, TcStatsBasic, Tcf,
 };
 
 #[test]
@@ -65,3 +69,68 @@ fn tc_action_parse_back_example() {
             .unwrap();
     assert_eq!(orig, parsed);
 }
+
+#[test]
+fn tc_action_parse_back_hw_offload() {
+    let orig = TcAction {
+        tab: 1,
+        attributes: vec![
+            TcActionAttribute::Kind("gact".into()),
+            TcActionAttribute::Flags(
+                TcActionFlags::NoPercpuStats | TcActionFlags::SkipSw,
+            ),
+            TcActionAttribute::HwStats(TcActionHwStats::Delayed),
+            TcActionAttribute::UsedHwStats(TcActionHwStats::Delayed),
+            TcActionAttribute::InHwCount(1),
+        ],
+    };
+    let mut buffer = vec![0; orig.buffer_len()];
+    orig.emit(&mut buffer);
+    let parsed =
+        TcAction::parse(&NlaBuffer::new_checked(buffer.as_slice()).unwrap())
+            .unwrap();
+    assert_eq!(orig, parsed);
+}
+
+#[test]
+fn tc_action_hw_stats_bitfield32() {
+    // Dump of `hw_stats delayed`: value is delayed, selector is any
+    let raw = [
+        0x0c, 0x00, // length 12
+        0x08, 0x00, // TCA_ACT_HW_STATS
+        0x02, 0x00, 0x00, 0x00, // value: TCA_ACT_HW_STATS_DELAYED
+        0x03, 0x00, 0x00, 0x00, // selector: TCA_ACT_HW_STATS_ANY
+    ];
+    let attr = TcActionAttribute::HwStats(TcActionHwStats::Delayed);
+    let mut buffer = vec![0; attr.buffer_len()];
+    attr.emit(&mut buffer);
+    assert_eq!(buffer, raw);
+
+    // Bits missing from the selector are ignored
+    let raw = [
+        0x0c, 0x00, // length 12
+        0x07, 0x00, // TCA_ACT_FLAGS
+        0x06, 0x00, 0x00, 0x00, // value: skip_hw, skip_sw
+        0x02, 0x00, 0x00, 0x00, // selector: skip_hw
+    ];
+    let parsed = TcActionAttribute::parse_with_param(
+        &NlaBuffer::new_checked(&raw[..]).unwrap(),
+        "gact",
+    )
+    .unwrap();
+    assert_eq!(parsed, TcActionAttribute::Flags(TcActionFlags::SkipHw));
+}
+
+#[test]
+fn tc_action_tcf_durations() {
+    let tm = Tcf {
+        install: 912,
+        lastuse: 514,
+        expires: 0,
+        firstuse: 0,
+    };
+    assert_eq!(tm.since_install(), Duration::from_millis(9120));
+    assert_eq!(tm.since_lastuse(), Duration::from_millis(5140));
+    assert_eq!(tm.since_firstuse(), None);
+    assert_eq!(Tcf::ticks_to_duration(1), Duration::from_millis(10));
+}
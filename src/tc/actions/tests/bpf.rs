@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionBpfOption::{Ops, OpsLen, Parms},
+        TcActionGeneric,
+        TcActionOption::Bpf,
+        TcActionType, TcBpfSockFilter,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action bpf bytecode '1,6 0 0 4294967295,' index 1
+/// ```
+const TC_ACTION_BPF_CLASSIC: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x01, 0x00, 0x3c, 0x00, 0x01, 0x00,
+    0x08, 0x00, 0x01, 0x00, 0x62, 0x70, 0x66, 0x00, 0x30, 0x00, 0x02, 0x80,
+    0x06, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x04, 0x00,
+    0x06, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x18, 0x00, 0x02, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_bpf_classic() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("bpf".into()),
+                Options(vec![
+                    Bpf(OpsLen(1)),
+                    Bpf(Ops(vec![TcBpfSockFilter::new(6, 0, 0, u32::MAX)])),
+                    Bpf(Parms(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_bpf_classic() {
+    let buf = TC_ACTION_BPF_CLASSIC;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_bpf_classic());
+}
+
+#[test]
+fn emit_tc_action_bpf_classic() {
+    let example = tc_action_message_bpf_classic();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_BPF_CLASSIC);
+}
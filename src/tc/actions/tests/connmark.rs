@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionConnmarkOption::Parms,
+        TcActionGeneric,
+        TcActionOption::Connmark,
+        TcActionType, TcConnmark,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action connmark zone 3 index 1
+/// ```
+const TC_ACTION_CONNMARK: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x38, 0x00, 0x01, 0x00, 0x34, 0x00, 0x01, 0x00,
+    0x0d, 0x00, 0x01, 0x00, 0x63, 0x6f, 0x6e, 0x6e, 0x6d, 0x61, 0x72, 0x6b,
+    0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x02, 0x80, 0x1c, 0x00, 0x01, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_connmark() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("connmark".into()),
+                Options(vec![Connmark(Parms(TcConnmark {
+                    generic: TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    },
+                    zone: 3,
+                }))]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_connmark() {
+    let buf = TC_ACTION_CONNMARK;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_connmark());
+}
+
+#[test]
+fn emit_tc_action_connmark() {
+    let example = tc_action_message_connmark();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_CONNMARK);
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionCsumOption::Parms,
+        TcActionGeneric,
+        TcActionOption::Csum,
+        TcActionType, TcCsum, TcCsumUpdateFlags,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action csum iph and tcp index 1
+/// ```
+const TC_ACTION_CSUM: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x34, 0x00, 0x01, 0x00, 0x30, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x63, 0x73, 0x75, 0x6d, 0x00, 0x00, 0x00, 0x00,
+    0x20, 0x00, 0x02, 0x80, 0x1c, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_csum() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("csum".into()),
+                Options(vec![Csum(Parms(TcCsum {
+                    generic: TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Ok,
+                        ..Default::default()
+                    },
+                    update_flags: TcCsumUpdateFlags::Ipv4Hdr
+                        | TcCsumUpdateFlags::Tcp,
+                }))]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_csum() {
+    let buf = TC_ACTION_CSUM;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_csum());
+}
+
+#[test]
+fn emit_tc_action_csum() {
+    let example = tc_action_message_csum();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_CSUM);
+}
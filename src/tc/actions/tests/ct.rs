@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv4Addr;
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionCtInfoOption, TcActionCtOption, TcActionGeneric,
+        TcActionOption::{Ct, CtInfo},
+        TcActionType, TcCtActionFlags,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action ct commit zone 5 mark 0x10/0xff \
+///     nat src addr 192.0.2.1-192.0.2.10 port 1000-2000 index 1
+/// ```
+const TC_ACTION_CT_NAT: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x6c, 0x00, 0x01, 0x00, 0x68, 0x00, 0x01, 0x00,
+    0x07, 0x00, 0x01, 0x00, 0x63, 0x74, 0x00, 0x00, 0x5c, 0x00, 0x02, 0x80,
+    0x06, 0x00, 0x04, 0x00, 0x05, 0x00, 0x00, 0x00, 0x08, 0x00, 0x05, 0x00,
+    0x10, 0x00, 0x00, 0x00, 0x08, 0x00, 0x06, 0x00, 0xff, 0x00, 0x00, 0x00,
+    0x08, 0x00, 0x09, 0x00, 0xc0, 0x00, 0x02, 0x01, 0x08, 0x00, 0x0a, 0x00,
+    0xc0, 0x00, 0x02, 0x0a, 0x06, 0x00, 0x0d, 0x00, 0x03, 0xe8, 0x00, 0x00,
+    0x06, 0x00, 0x0e, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x06, 0x00, 0x03, 0x00,
+    0x19, 0x00, 0x00, 0x00, 0x18, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_ct_nat() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("ct".into()),
+                Options(vec![
+                    Ct(TcActionCtOption::Zone(5)),
+                    Ct(TcActionCtOption::Mark(0x10)),
+                    Ct(TcActionCtOption::MarkMask(0xff)),
+                    Ct(TcActionCtOption::NatIpv4Min(Ipv4Addr::new(
+                        192, 0, 2, 1,
+                    ))),
+                    Ct(TcActionCtOption::NatIpv4Max(Ipv4Addr::new(
+                        192, 0, 2, 10,
+                    ))),
+                    Ct(TcActionCtOption::NatPortMin(1000)),
+                    Ct(TcActionCtOption::NatPortMax(2000)),
+                    Ct(TcActionCtOption::Action(
+                        TcCtActionFlags::Commit
+                            | TcCtActionFlags::Nat
+                            | TcCtActionFlags::NatSrc,
+                    )),
+                    Ct(TcActionCtOption::Parms(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_ct_nat() {
+    let buf = TC_ACTION_CT_NAT;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_ct_nat());
+}
+
+#[test]
+fn emit_tc_action_ct_nat() {
+    let example = tc_action_message_ct_nat();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_CT_NAT);
+}
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action ctinfo dscp 0xfc000000 0x01000000 \
+///     cpmark 0x00ffffff zone 2 index 1
+/// ```
+const TC_ACTION_CTINFO: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x01, 0x00, 0x4c, 0x00, 0x01, 0x00,
+    0x0b, 0x00, 0x01, 0x00, 0x63, 0x74, 0x69, 0x6e, 0x66, 0x6f, 0x00, 0x00,
+    0x3c, 0x00, 0x02, 0x80, 0x18, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x04, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x08, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0xfc, 0x08, 0x00, 0x06, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x08, 0x00, 0x07, 0x00, 0xff, 0xff, 0xff, 0x00,
+];
+
+fn tc_action_message_ctinfo() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("ctinfo".into()),
+                Options(vec![
+                    CtInfo(TcActionCtInfoOption::Act(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                    CtInfo(TcActionCtInfoOption::Zone(2)),
+                    CtInfo(TcActionCtInfoOption::DscpMask(0xfc000000)),
+                    CtInfo(TcActionCtInfoOption::DscpStateMask(0x01000000)),
+                    CtInfo(TcActionCtInfoOption::CpmarkMask(0x00ffffff)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_ctinfo() {
+    let buf = TC_ACTION_CTINFO;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_ctinfo());
+}
+
+#[test]
+fn emit_tc_action_ctinfo() {
+    let example = tc_action_message_ctinfo();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_CTINFO);
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGactOption::{Parms, Prob},
+        TcActionGeneric,
+        TcActionOption::Gact,
+        TcActionType, TcGactProb, TcGactProbType,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action gact drop random determ pass 10 index 1
+/// ```
+const TC_ACTION_GACT_EXAMPLE1: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x3c, 0x00, 0x01, 0x00, 0x38, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x67, 0x61, 0x63, 0x74, 0x00, 0x00, 0x00, 0x00,
+    0x28, 0x00, 0x02, 0x80, 0x18, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x03, 0x00, 0x02, 0x00, 0x0a, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_gact_example1() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("gact".into()),
+                Options(vec![
+                    Gact(Parms(TcActionGeneric {
+                        index: 1,
+                        capab: 0,
+                        action: TcActionType::Shot,
+                        refcnt: 0,
+                        bindcnt: 0,
+                    })),
+                    Gact(Prob(TcGactProb {
+                        ptype: TcGactProbType::Determ,
+                        pval: 10,
+                        paction: TcActionType::Ok,
+                    })),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_gact_example1() {
+    let buf = TC_ACTION_GACT_EXAMPLE1;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_gact_example1());
+}
+
+#[test]
+fn emit_tc_action_gact_example1() {
+    let example = tc_action_message_gact_example1();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_GACT_EXAMPLE1);
+}
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action gact goto chain 5 index 2
+/// ```
+const TC_ACTION_GACT_GOTO_CHAIN: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x01, 0x00, 0x2c, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x67, 0x61, 0x63, 0x74, 0x00, 0x00, 0x00, 0x00,
+    0x1c, 0x00, 0x02, 0x80, 0x18, 0x00, 0x02, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_gact_goto_chain() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("gact".into()),
+                Options(vec![Gact(Parms(TcActionGeneric {
+                    index: 2,
+                    capab: 0,
+                    action: TcActionType::GotoChain(5),
+                    refcnt: 0,
+                    bindcnt: 0,
+                }))]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_gact_goto_chain() {
+    let buf = TC_ACTION_GACT_GOTO_CHAIN;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_gact_goto_chain());
+}
+
+#[test]
+fn emit_tc_action_gact_goto_chain() {
+    let example = tc_action_message_gact_goto_chain();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_GACT_GOTO_CHAIN);
+}
+
+#[test]
+fn tc_action_type_jump() {
+    assert_eq!(TcActionType::from(0x1000_0002), TcActionType::Jump(2));
+    assert_eq!(i32::from(TcActionType::Jump(2)), 0x1000_0002);
+}
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGateOption::{BaseTime, ClockId, EntryList, Parms},
+        TcActionGeneric,
+        TcActionOption::Gate,
+        TcActionType, TcGateEntry,
+        TcGateEntryAttribute::{GateOpen, Interval, Ipv, MaxOctets},
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action gate index 1 clockid CLOCK_TAI \
+///     base-time 1000000000 sched-entry open 200000 -1 -1 \
+///     sched-entry close 100000 -1 -1
+/// ```
+const TC_ACTION_GATE: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x84, 0x00, 0x01, 0x00, 0x80, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x67, 0x61, 0x74, 0x65, 0x00, 0x00, 0x00, 0x00,
+    0x70, 0x00, 0x02, 0x80, 0x18, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x06, 0x00, 0x00, 0xca, 0x9a, 0x3b,
+    0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x0a, 0x00, 0x0b, 0x00, 0x00, 0x00,
+    0x40, 0x00, 0x05, 0x00, 0x20, 0x00, 0x01, 0x00, 0x04, 0x00, 0x02, 0x00,
+    0x08, 0x00, 0x03, 0x00, 0x40, 0x0d, 0x03, 0x00, 0x08, 0x00, 0x04, 0x00,
+    0xff, 0xff, 0xff, 0xff, 0x08, 0x00, 0x05, 0x00, 0xff, 0xff, 0xff, 0xff,
+    0x1c, 0x00, 0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0xa0, 0x86, 0x01, 0x00,
+    0x08, 0x00, 0x04, 0x00, 0xff, 0xff, 0xff, 0xff, 0x08, 0x00, 0x05, 0x00,
+    0xff, 0xff, 0xff, 0xff,
+];
+
+fn tc_action_message_gate() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("gate".into()),
+                Options(vec![
+                    Gate(Parms(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                    Gate(BaseTime(1000000000)),
+                    Gate(ClockId(11)),
+                    Gate(EntryList(vec![
+                        TcGateEntry::Entry(vec![
+                            GateOpen,
+                            Interval(200000),
+                            Ipv(-1),
+                            MaxOctets(-1),
+                        ]),
+                        TcGateEntry::Entry(vec![
+                            Interval(100000),
+                            Ipv(-1),
+                            MaxOctets(-1),
+                        ]),
+                    ])),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_gate() {
+    let buf = TC_ACTION_GATE;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_gate());
+}
+
+#[test]
+fn emit_tc_action_gate() {
+    let example = tc_action_message_gate();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_GATE);
+}
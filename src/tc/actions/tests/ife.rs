@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionIfeOption::{Dmac, MetaList, Parms, Type},
+        TcActionOption::Ife,
+        TcActionType, TcIfe, TcIfeFlags, TcIfeMeta,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action ife encode allow mark use prio 5 \
+///     dst 02:00:00:00:00:01 type 0xED3E index 1
+/// ```
+const TC_ACTION_IFE_ENCODE: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x54, 0x00, 0x01, 0x00, 0x50, 0x00, 0x01, 0x00,
+    0x08, 0x00, 0x01, 0x00, 0x69, 0x66, 0x65, 0x00, 0x44, 0x00, 0x02, 0x80,
+    0x1c, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x03, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x00, 0x01, 0x00, 0x00, 0x06, 0x00, 0x05, 0x00, 0x3e, 0xed, 0x00, 0x00,
+    0x10, 0x00, 0x06, 0x00, 0x04, 0x00, 0x01, 0x00, 0x08, 0x00, 0x03, 0x00,
+    0x05, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_ife_encode() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("ife".into()),
+                Options(vec![
+                    Ife(Parms(TcIfe {
+                        generic: TcActionGeneric {
+                            index: 1,
+                            action: TcActionType::Pipe,
+                            ..Default::default()
+                        },
+                        flags: TcIfeFlags::Encode,
+                    })),
+                    Ife(Dmac([0x02, 0x00, 0x00, 0x00, 0x00, 0x01])),
+                    Ife(Type(0xed3e)),
+                    Ife(MetaList(vec![
+                        TcIfeMeta::SkbMark(None),
+                        TcIfeMeta::Prio(Some(5)),
+                    ])),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_ife_encode() {
+    let buf = TC_ACTION_IFE_ENCODE;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_ife_encode());
+}
+
+#[test]
+fn emit_tc_action_ife_encode() {
+    let example = tc_action_message_ife_encode();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_IFE_ENCODE);
+}
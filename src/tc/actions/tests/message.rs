@@ -21,7 +21,7 @@ use crate::{
 };
 
 mod mirror {
-    use netlink_packet_core::{DefaultNla, Parseable};
+    use netlink_packet_core::Parseable;
 
     use crate::{
         tc::{
@@ -33,8 +33,8 @@ mod mirror {
                 TcActionMessageHeader,
             },
             TcAction,
-            TcActionAttribute::{InHwCount, Kind, Options, Other, Stats},
-            TcActionGeneric,
+            TcActionAttribute::{InHwCount, Kind, Options, Stats, UsedHwStats},
+            TcActionGeneric, TcActionHwStats,
             TcActionMirrorOption::{Parms, Tm},
             TcActionOption::Mirror,
             TcActionType::{Pipe, Stolen},
@@ -209,10 +209,7 @@ mod mirror {
                                     overlimits: 0,
                                 }),
                             ]),
-                            Other(DefaultNla::new(
-                                9,
-                                vec![0, 0, 0, 0, 3, 0, 0, 0],
-                            )),
+                            UsedHwStats(TcActionHwStats::empty()),
                             InHwCount(0),
                             Options(vec![
                                 Mirror(Parms(TcMirror {
@@ -256,10 +253,7 @@ mod mirror {
                                     overlimits: 0,
                                 }),
                             ]),
-                            Other(DefaultNla::new(
-                                9,
-                                vec![0, 0, 0, 0, 3, 0, 0, 0],
-                            )),
+                            UsedHwStats(TcActionHwStats::empty()),
                             InHwCount(0),
                             Options(vec![
                                 Mirror(Parms(TcMirror {
@@ -1,8 +1,22 @@
 // SPDX-License-Identifier: MIT
 
 pub mod action;
+pub mod bpf;
+pub mod connmark;
+pub mod csum;
+pub mod ct;
+pub mod gact;
+pub mod gate;
 pub mod header;
+pub mod ife;
 pub mod message;
 pub mod mirror;
+pub mod mpls;
 pub mod nat;
+pub mod pedit;
+pub mod police;
+pub mod sample;
+pub mod skbedit;
+pub mod skbmod;
 pub mod tunnel_key;
+pub mod vlan;
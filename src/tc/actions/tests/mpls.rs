@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionMplsOption::{Label, Parms, Proto, Tc, Ttl},
+        TcActionOption::Mpls,
+        TcActionType, TcMpls, TcMplsActionType,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action mpls push protocol mpls_uc label 100 \
+///     tc 3 ttl 64 index 1
+/// ```
+const TC_ACTION_MPLS_PUSH: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x54, 0x00, 0x01, 0x00, 0x50, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x6d, 0x70, 0x6c, 0x73, 0x00, 0x00, 0x00, 0x00,
+    0x40, 0x00, 0x02, 0x80, 0x1c, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x08, 0x00, 0x05, 0x00,
+    0x64, 0x00, 0x00, 0x00, 0x06, 0x00, 0x04, 0x00, 0x88, 0x47, 0x00, 0x00,
+    0x05, 0x00, 0x07, 0x00, 0x40, 0x00, 0x00, 0x00, 0x05, 0x00, 0x06, 0x00,
+    0x03, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_mpls_push() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("mpls".into()),
+                Options(vec![
+                    Mpls(Parms(TcMpls {
+                        generic: TcActionGeneric {
+                            index: 1,
+                            action: TcActionType::Pipe,
+                            ..Default::default()
+                        },
+                        m_action: TcMplsActionType::Push,
+                    })),
+                    Mpls(Label(100)),
+                    Mpls(Proto(0x8847)),
+                    Mpls(Ttl(64)),
+                    Mpls(Tc(3)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_mpls_push() {
+    let buf = TC_ACTION_MPLS_PUSH;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_mpls_push());
+}
+
+#[test]
+fn emit_tc_action_mpls_push() {
+    let example = tc_action_message_mpls_push();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_MPLS_PUSH);
+}
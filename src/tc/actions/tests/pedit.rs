@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric, TcActionOption, TcActionPeditOption, TcActionType,
+        TcPeditCmd, TcPeditEdit, TcPeditHeaderType, TcPeditKey, TcPeditKeyEx,
+        TcPeditKeyExAttribute::{Cmd, Htype},
+        TcPeditSel,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action pedit ex munge ip dst set 10.0.0.1 \
+///     munge tcp dport set 8080 index 1
+/// ```
+const TC_ACTION_PEDIT_EX: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x90, 0x00, 0x01, 0x00, 0x8c, 0x00, 0x01, 0x00,
+    0x0a, 0x00, 0x01, 0x00, 0x70, 0x65, 0x64, 0x69, 0x74, 0x00, 0x00, 0x00,
+    0x7c, 0x00, 0x02, 0x80, 0x4c, 0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x0a, 0x00, 0x00, 0x01, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00,
+    0x00, 0x00, 0x1f, 0x90, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x05, 0x00,
+    0x14, 0x00, 0x06, 0x00, 0x06, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x06, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x00, 0x06, 0x00,
+    0x06, 0x00, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x06, 0x00, 0x02, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+fn generic() -> TcActionGeneric {
+    TcActionGeneric {
+        index: 1,
+        action: TcActionType::Ok,
+        ..Default::default()
+    }
+}
+
+fn tc_action_message_pedit_ex() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("pedit".into()),
+                Options(vec![
+                    TcActionOption::Pedit(TcActionPeditOption::ParmsEx(
+                        TcPeditSel {
+                            generic: generic(),
+                            flags: 0,
+                            keys: vec![
+                                TcPeditKey {
+                                    mask: 0,
+                                    val: 0x0a000001,
+                                    off: 16,
+                                    ..Default::default()
+                                },
+                                TcPeditKey {
+                                    mask: 0xffff0000,
+                                    val: 8080,
+                                    off: 0,
+                                    ..Default::default()
+                                },
+                            ],
+                        },
+                    )),
+                    TcActionOption::Pedit(TcActionPeditOption::KeysEx(vec![
+                        TcPeditKeyEx::KeyEx(vec![
+                            Htype(TcPeditHeaderType::Ip4),
+                            Cmd(TcPeditCmd::Set),
+                        ]),
+                        TcPeditKeyEx::KeyEx(vec![
+                            Htype(TcPeditHeaderType::Tcp),
+                            Cmd(TcPeditCmd::Set),
+                        ]),
+                    ])),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_pedit_ex() {
+    let buf = TC_ACTION_PEDIT_EX;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_pedit_ex());
+}
+
+#[test]
+fn emit_tc_action_pedit_ex() {
+    let example = tc_action_message_pedit_ex();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_PEDIT_EX);
+}
+
+#[test]
+fn tc_action_pedit_from_edits() {
+    let options = TcActionPeditOption::from_edits(
+        generic(),
+        &[
+            TcPeditEdit::Ipv4Dst(Ipv4Addr::new(10, 0, 0, 1)),
+            TcPeditEdit::TcpDport(8080),
+        ],
+    )
+    .unwrap();
+    let expected = tc_action_message_pedit_ex();
+    let Actions(actions) = &expected.attributes[0] else {
+        panic!("expected actions");
+    };
+    let Options(expected_options) = &actions[0].attributes[1] else {
+        panic!("expected options");
+    };
+    assert_eq!(
+        options
+            .into_iter()
+            .map(TcActionOption::Pedit)
+            .collect::<Vec<_>>(),
+        *expected_options
+    );
+}
+
+#[test]
+fn tc_action_pedit_too_many_keys() {
+    // Each IPv6 address rewrite needs 4 keys
+    let edits = vec![TcPeditEdit::Ipv6Src(Ipv6Addr::LOCALHOST); 64];
+    assert!(TcActionPeditOption::from_edits(generic(), &edits).is_err());
+    assert!(TcActionPeditOption::from_edits(generic(), &edits[1..]).is_ok());
+
+    // The key count of the kernel structure cannot go past MAX_KEYS
+    let sel = TcPeditSel {
+        keys: vec![TcPeditKey::default(); TcPeditSel::MAX_KEYS + 1],
+        ..Default::default()
+    };
+    let mut buf = vec![0; sel.buffer_len()];
+    sel.emit(&mut buf);
+    let parsed = TcPeditSel::parse(&buf).unwrap();
+    assert_eq!(parsed.keys.len(), TcPeditSel::MAX_KEYS);
+}
+
+#[test]
+fn tc_action_pedit_edit_eth_src_spans_two_words() {
+    assert_eq!(
+        TcPeditEdit::EthSrc([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]).keys(),
+        vec![
+            TcPeditKey {
+                mask: 0xffff0000,
+                val: 0x0200,
+                off: 4,
+                ..Default::default()
+            },
+            TcPeditKey {
+                mask: 0,
+                val: 0x00000001,
+                off: 8,
+                ..Default::default()
+            },
+        ]
+    );
+}
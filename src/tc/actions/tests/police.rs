@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, NlaBuffer, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionOption::Police,
+        TcActionPoliceOption,
+        TcActionPoliceOption::{PktBurst64, PktRate64, Result, Tbf},
+        TcActionType, TcLinkLayer, TcPolice, TcRateSpec,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action police pkts_rate 1000 pkts_burst 100 \
+///     conform-exceed drop/pipe index 1
+/// ```
+const TC_ACTION_POLICE_PPS: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x74, 0x00, 0x01, 0x00, 0x70, 0x00, 0x01, 0x00,
+    0x0b, 0x00, 0x01, 0x00, 0x70, 0x6f, 0x6c, 0x69, 0x63, 0x65, 0x00, 0x00,
+    0x60, 0x00, 0x02, 0x80, 0x3c, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x05, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x0c, 0x00, 0x0a, 0x00, 0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x0c, 0x00, 0x0b, 0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_police_pps() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("police".into()),
+                Options(vec![
+                    Police(Tbf(TcPolice {
+                        index: 1,
+                        action: TcActionType::Shot,
+                        ..Default::default()
+                    })),
+                    Police(Result(TcActionType::Pipe)),
+                    Police(PktRate64(1000)),
+                    Police(PktBurst64(100)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_police_pps() {
+    let buf = TC_ACTION_POLICE_PPS;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_police_pps());
+}
+
+#[test]
+fn emit_tc_action_police_pps() {
+    let example = tc_action_message_police_pps();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_POLICE_PPS);
+}
+
+#[test]
+fn tc_action_police_rate_parse_back() {
+    let rate = TcRateSpec {
+        cell_log: 3,
+        linklayer: TcLinkLayer::Ethernet,
+        overhead: 14,
+        cell_align: -1,
+        mpu: 64,
+        rate: 125000,
+    };
+    let options = vec![
+        TcActionPoliceOption::Tbf(TcPolice {
+            index: 2,
+            action: TcActionType::Reclassify,
+            burst: 800000,
+            mtu: 2040,
+            rate,
+            ..Default::default()
+        }),
+        TcActionPoliceOption::Rate((0..256).collect()),
+        TcActionPoliceOption::Rate64(10_000_000_000),
+    ];
+    for orig in options {
+        let mut buffer = vec![0; orig.buffer_len()];
+        orig.emit(&mut buffer);
+        let parsed = TcActionPoliceOption::parse(
+            &NlaBuffer::new_checked(buffer.as_slice()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(orig, parsed);
+    }
+}
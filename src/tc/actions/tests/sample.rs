@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionOption::Sample,
+        TcActionSampleOption::{Parms, PsampleGroup, Rate, TruncSize},
+        TcActionType,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action sample rate 100 group 5 trunc 128 index 1
+/// ```
+const TC_ACTION_SAMPLE: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x48, 0x00, 0x01, 0x00, 0x44, 0x00, 0x01, 0x00,
+    0x0b, 0x00, 0x01, 0x00, 0x73, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x00, 0x00,
+    0x34, 0x00, 0x02, 0x80, 0x18, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x03, 0x00, 0x64, 0x00, 0x00, 0x00,
+    0x08, 0x00, 0x04, 0x00, 0x80, 0x00, 0x00, 0x00, 0x08, 0x00, 0x05, 0x00,
+    0x05, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_sample() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("sample".into()),
+                Options(vec![
+                    Sample(Parms(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                    Sample(Rate(100)),
+                    Sample(TruncSize(128)),
+                    Sample(PsampleGroup(5)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_sample() {
+    let buf = TC_ACTION_SAMPLE;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_sample());
+}
+
+#[test]
+fn emit_tc_action_sample() {
+    let example = tc_action_message_sample();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_SAMPLE);
+}
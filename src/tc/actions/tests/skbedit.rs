@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionOption::SkbEdit,
+        TcActionSkbEditOption::{Mark, Mask, Parms, Priority},
+        TcActionType, TcHandle,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action skbedit priority 1:1 mark 16/0xff index 1
+/// ```
+const TC_ACTION_SKBEDIT: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x48, 0x00, 0x01, 0x00, 0x44, 0x00, 0x01, 0x00,
+    0x0c, 0x00, 0x01, 0x00, 0x73, 0x6b, 0x62, 0x65, 0x64, 0x69, 0x74, 0x00,
+    0x34, 0x00, 0x02, 0x80, 0x18, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x03, 0x00, 0x01, 0x00, 0x01, 0x00,
+    0x08, 0x00, 0x05, 0x00, 0x10, 0x00, 0x00, 0x00, 0x08, 0x00, 0x08, 0x00,
+    0xff, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_skbedit() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("skbedit".into()),
+                Options(vec![
+                    SkbEdit(Parms(TcActionGeneric {
+                        index: 1,
+                        action: TcActionType::Pipe,
+                        ..Default::default()
+                    })),
+                    SkbEdit(Priority(TcHandle { major: 1, minor: 1 })),
+                    SkbEdit(Mark(16)),
+                    SkbEdit(Mask(0xff)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_skbedit() {
+    let buf = TC_ACTION_SKBEDIT;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_skbedit());
+}
+
+#[test]
+fn emit_tc_action_skbedit() {
+    let example = tc_action_message_skbedit();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_SKBEDIT);
+}
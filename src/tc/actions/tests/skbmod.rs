@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionOption::SkbMod,
+        TcActionSkbModOption::{Dmac, Parms, Smac},
+        TcActionType, TcSkbMod, TcSkbModFlags,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action skbmod set dmac 02:00:00:00:00:01 \
+///     set smac 02:00:00:00:00:02 index 1
+/// ```
+const TC_ACTION_SKBMOD: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x54, 0x00, 0x01, 0x00, 0x50, 0x00, 0x01, 0x00,
+    0x0b, 0x00, 0x01, 0x00, 0x73, 0x6b, 0x62, 0x6d, 0x6f, 0x64, 0x00, 0x00,
+    0x40, 0x00, 0x02, 0x80, 0x24, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x03, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x04, 0x00, 0x02, 0x00, 0x00, 0x00,
+    0x00, 0x02, 0x00, 0x00,
+];
+
+fn tc_action_message_skbmod() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("skbmod".into()),
+                Options(vec![
+                    SkbMod(Parms(TcSkbMod {
+                        generic: TcActionGeneric {
+                            index: 1,
+                            action: TcActionType::Pipe,
+                            ..Default::default()
+                        },
+                        flags: TcSkbModFlags::Dmac | TcSkbModFlags::Smac,
+                    })),
+                    SkbMod(Dmac([0x02, 0x00, 0x00, 0x00, 0x00, 0x01])),
+                    SkbMod(Smac([0x02, 0x00, 0x00, 0x00, 0x00, 0x02])),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_skbmod() {
+    let buf = TC_ACTION_SKBMOD;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_skbmod());
+}
+
+#[test]
+fn emit_tc_action_skbmod() {
+    let example = tc_action_message_skbmod();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_SKBMOD);
+}
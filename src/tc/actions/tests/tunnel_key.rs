@@ -6,8 +6,9 @@ use netlink_packet_core::{Emitable, NlaBuffer, Parseable};
 
 use crate::tc::{
     TcAction, TcActionAttribute, TcActionGeneric, TcActionOption,
-    TcActionTunnelKeyOption, TcActionType, TcStats2, TcStatsBasic,
-    TcStatsQueue, TcTunnelKey, Tcf,
+    TcActionTunnelKeyOption, TcActionType, TcFilterFlowerEncOptGeneve,
+    TcStats2, TcStatsBasic, TcStatsQueue, TcTunnelKey, TcTunnelKeyEncOption,
+    Tcf,
 };
 
 //      > tc actions add action tunnel_key set id 33 src_ip 1.2.3.4 dst_ip
@@ -205,3 +206,33 @@ fn test_action_tunnel_key_vxlan_ipv6() {
 
     assert_eq!(buf, raw);
 }
+
+//      > tc actions add action tunnel_key set id 1 src_ip 1.2.3.4 \
+//      > dst_ip 2.3.4.5 geneve_opts 0102:80:11223344
+//      Only the TCA_TUNNEL_KEY_ENC_OPTS attribute is kept.
+#[test]
+fn test_action_tunnel_key_geneve_enc_opts() {
+    let raw = vec![
+        0x20, 0x00, 0x0b, 0x00, 0x1c, 0x00, 0x01, 0x00, 0x06, 0x00, 0x01, 0x00,
+        0x01, 0x02, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00, 0x80, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x03, 0x00, 0x11, 0x22, 0x33, 0x44,
+    ];
+
+    let expected =
+        TcActionTunnelKeyOption::EncOpts(vec![TcTunnelKeyEncOption::Geneve(
+            vec![
+                TcFilterFlowerEncOptGeneve::Class(0x0102),
+                TcFilterFlowerEncOptGeneve::Type(0x80),
+                TcFilterFlowerEncOptGeneve::Data(vec![0x11, 0x22, 0x33, 0x44]),
+            ],
+        )]);
+
+    assert_eq!(
+        expected,
+        TcActionTunnelKeyOption::parse(&NlaBuffer::new(&raw)).unwrap()
+    );
+
+    let mut buf = vec![0; expected.buffer_len()];
+    expected.emit(&mut buf);
+    assert_eq!(buf, raw);
+}
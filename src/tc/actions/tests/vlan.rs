@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        actions::{
+            message::{TcActionMessage, TcActionMessageAttribute::Actions},
+            TcActionMessageHeader,
+        },
+        TcAction,
+        TcActionAttribute::{Kind, Options},
+        TcActionGeneric,
+        TcActionOption::Vlan,
+        TcActionType,
+        TcActionVlanOption::{
+            Parms, PushVlanId, PushVlanPriority, PushVlanProtocol,
+        },
+        TcVlan, TcVlanActionType,
+    },
+    AddressFamily,
+};
+
+/// Hand-built request equivalent to
+///
+/// ```bash
+/// tc actions add action vlan push id 100 protocol 802.1Q priority 3 index 1
+/// ```
+const TC_ACTION_VLAN_PUSH: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x4c, 0x00, 0x01, 0x00, 0x48, 0x00, 0x01, 0x00,
+    0x09, 0x00, 0x01, 0x00, 0x76, 0x6c, 0x61, 0x6e, 0x00, 0x00, 0x00, 0x00,
+    0x38, 0x00, 0x02, 0x80, 0x1c, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x06, 0x00, 0x03, 0x00,
+    0x64, 0x00, 0x00, 0x00, 0x06, 0x00, 0x04, 0x00, 0x81, 0x00, 0x00, 0x00,
+    0x05, 0x00, 0x06, 0x00, 0x03, 0x00, 0x00, 0x00,
+];
+
+fn tc_action_message_vlan_push() -> TcActionMessage {
+    TcActionMessage {
+        header: TcActionMessageHeader {
+            family: AddressFamily::Unspec,
+        },
+        attributes: vec![Actions(vec![TcAction {
+            tab: 1,
+            attributes: vec![
+                Kind("vlan".into()),
+                Options(vec![
+                    Vlan(Parms(TcVlan {
+                        generic: TcActionGeneric {
+                            index: 1,
+                            action: TcActionType::Pipe,
+                            ..Default::default()
+                        },
+                        v_action: TcVlanActionType::Push,
+                    })),
+                    Vlan(PushVlanId(100)),
+                    Vlan(PushVlanProtocol(0x8100)),
+                    Vlan(PushVlanPriority(3)),
+                ]),
+            ],
+        }])],
+    }
+}
+
+#[test]
+fn parse_tc_action_vlan_push() {
+    let buf = TC_ACTION_VLAN_PUSH;
+    let parsed = TcActionMessage::parse(buf).unwrap();
+    assert_eq!(parsed, tc_action_message_vlan_push());
+}
+
+#[test]
+fn emit_tc_action_vlan_push() {
+    let example = tc_action_message_vlan_push();
+    let mut buf = vec![0; example.buffer_len()];
+    example.emit(&mut buf);
+    assert_eq!(buf.as_slice(), TC_ACTION_VLAN_PUSH);
+}
@@ -7,7 +7,8 @@ use std::{
 
 use netlink_packet_core::{
     emit_u16_be, emit_u32_be, parse_u16_be, parse_u32_be, parse_u8,
-    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable,
 };
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 
@@ -16,7 +17,13 @@ use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
 ///
 /// The set_tunnel action allows to set tunnel encap applied
 /// at the last stage of action processing
-use crate::ip::{parse_ipv4_addr, parse_ipv6_addr};
+use crate::{
+    ip::{parse_ipv4_addr, parse_ipv6_addr},
+    tc::{
+        TcFilterFlowerEncOptErspan, TcFilterFlowerEncOptGeneve,
+        TcFilterFlowerEncOptGtp, TcFilterFlowerEncOptVxlan,
+    },
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
@@ -35,7 +42,7 @@ const TCA_TUNNEL_KEY_ENC_KEY_ID: u16 = 7;
 // const TCA_TUNNEL_KEY_PAD: u16 = 8;
 const TCA_TUNNEL_KEY_ENC_DST_PORT: u16 = 9;
 const TCA_TUNNEL_KEY_NO_CSUM: u16 = 10;
-// const TCA_TUNNEL_KEY_ENC_OPTS: u16 = 11;
+const TCA_TUNNEL_KEY_ENC_OPTS: u16 = 11;
 const TCA_TUNNEL_KEY_ENC_TOS: u16 = 12;
 const TCA_TUNNEL_KEY_ENC_TTL: u16 = 13;
 // const TCA_TUNNEL_KEY_NO_FRAG: u16 = 14;
@@ -54,6 +61,7 @@ pub enum TcActionTunnelKeyOption {
     EncTos(u8),
     EncTtl(u8),
     NoCsum(bool),
+    EncOpts(Vec<TcTunnelKeyEncOption>),
     Other(DefaultNla),
 }
 
@@ -68,6 +76,7 @@ impl Nla for TcActionTunnelKeyOption {
             Self::EncDstPort(_) => 2,
             Self::EncTos(_) | Self::EncTtl(_) => 1,
             Self::NoCsum(_) => 1,
+            Self::EncOpts(v) => v.as_slice().buffer_len(),
             Self::Other(attr) => attr.value_len(),
         }
     }
@@ -87,6 +96,7 @@ impl Nla for TcActionTunnelKeyOption {
             Self::EncTos(i) => buffer[0] = *i,
             Self::EncTtl(i) => buffer[0] = *i,
             Self::NoCsum(i) => buffer[0] = *i as u8,
+            Self::EncOpts(v) => v.as_slice().emit(buffer),
             Self::Other(attr) => attr.emit_value(buffer),
         }
     }
@@ -103,6 +113,7 @@ impl Nla for TcActionTunnelKeyOption {
             Self::EncTos(_) => TCA_TUNNEL_KEY_ENC_TOS,
             Self::EncTtl(_) => TCA_TUNNEL_KEY_ENC_TTL,
             Self::NoCsum(_) => TCA_TUNNEL_KEY_NO_CSUM,
+            Self::EncOpts(_) => TCA_TUNNEL_KEY_ENC_OPTS,
             Self::Other(nla) => nla.kind(),
         }
     }
@@ -153,6 +164,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                     .context("invalid TCA_TUNNEL_KEY_NO_CSUM value")?
                     != 0,
             ),
+            TCA_TUNNEL_KEY_ENC_OPTS => {
+                let mut opts = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid TCA_TUNNEL_KEY_ENC_OPTS value")?;
+                    opts.push(TcTunnelKeyEncOption::parse(&nla)?);
+                }
+                Self::EncOpts(opts)
+            }
             _ => Self::Other(DefaultNla::parse(buf)?),
         })
     }
@@ -219,3 +239,95 @@ impl Emitable for TcTunnelKey {
         buffer.copy_from_slice(raw.as_bytes());
     }
 }
+
+const TCA_TUNNEL_KEY_ENC_OPTS_GENEVE: u16 = 1;
+const TCA_TUNNEL_KEY_ENC_OPTS_VXLAN: u16 = 2;
+const TCA_TUNNEL_KEY_ENC_OPTS_ERSPAN: u16 = 3;
+const TCA_TUNNEL_KEY_ENC_OPTS_GTP: u16 = 4;
+
+/// Tunnel options set on the encapsulated packets.
+///
+/// Each Geneve option TLV is in its own `Geneve` nest, other tunnel types
+/// have at most one. The attributes of each nest are the same as the
+/// ones matched by the flower classifier.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcTunnelKeyEncOption {
+    Geneve(Vec<TcFilterFlowerEncOptGeneve>),
+    Vxlan(Vec<TcFilterFlowerEncOptVxlan>),
+    Erspan(Vec<TcFilterFlowerEncOptErspan>),
+    Gtp(Vec<TcFilterFlowerEncOptGtp>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcTunnelKeyEncOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Geneve(attr) => attr.as_slice().buffer_len(),
+            Self::Vxlan(attr) => attr.as_slice().buffer_len(),
+            Self::Erspan(attr) => attr.as_slice().buffer_len(),
+            Self::Gtp(attr) => attr.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Geneve(attr) => attr.as_slice().emit(buffer),
+            Self::Vxlan(attr) => attr.as_slice().emit(buffer),
+            Self::Erspan(attr) => attr.as_slice().emit(buffer),
+            Self::Gtp(attr) => attr.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Geneve(_) => TCA_TUNNEL_KEY_ENC_OPTS_GENEVE,
+            Self::Vxlan(_) => TCA_TUNNEL_KEY_ENC_OPTS_VXLAN,
+            Self::Erspan(_) => TCA_TUNNEL_KEY_ENC_OPTS_ERSPAN,
+            Self::Gtp(_) => TCA_TUNNEL_KEY_ENC_OPTS_GTP,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+fn parse_nested<T>(payload: &[u8]) -> Result<Vec<T>, DecodeError>
+where
+    T: for<'b> Parseable<NlaBuffer<&'b [u8]>>,
+{
+    let mut nlas = vec![];
+    for nla in NlasIterator::new(payload) {
+        nlas.push(T::parse(&nla?)?);
+    }
+    Ok(nlas)
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcTunnelKeyEncOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_TUNNEL_KEY_ENC_OPTS_GENEVE => {
+                Self::Geneve(parse_nested(payload).context(
+                    "failed to parse TCA_TUNNEL_KEY_ENC_OPTS_GENEVE",
+                )?)
+            }
+            TCA_TUNNEL_KEY_ENC_OPTS_VXLAN => Self::Vxlan(
+                parse_nested(payload)
+                    .context("failed to parse TCA_TUNNEL_KEY_ENC_OPTS_VXLAN")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_OPTS_ERSPAN => {
+                Self::Erspan(parse_nested(payload).context(
+                    "failed to parse TCA_TUNNEL_KEY_ENC_OPTS_ERSPAN",
+                )?)
+            }
+            TCA_TUNNEL_KEY_ENC_OPTS_GTP => Self::Gtp(
+                parse_nested(payload)
+                    .context("failed to parse TCA_TUNNEL_KEY_ENC_OPTS_GTP")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
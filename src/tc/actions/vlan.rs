@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: MIT
+
+/// Vlan action
+///
+/// The vlan action pushes, pops or modifies the outermost VLAN tag of
+/// packets, or pushes and pops an Ethernet header.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u16, emit_u16_be, parse_mac, parse_u16, parse_u16_be, parse_u8,
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcActionGeneric, TcActionGenericBuffer, Tcf, TcfBuffer};
+
+/// Traffic control action used to push or pop VLAN tags.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcActionVlan {}
+impl TcActionVlan {
+    /// The `TcActionAttribute::Kind` of this action.
+    pub const KIND: &'static str = "vlan";
+}
+
+const TCA_VLAN_TM: u16 = 1;
+const TCA_VLAN_PARMS: u16 = 2;
+const TCA_VLAN_PUSH_VLAN_ID: u16 = 3;
+const TCA_VLAN_PUSH_VLAN_PROTOCOL: u16 = 4;
+// const TCA_VLAN_PAD: u16 = 5;
+const TCA_VLAN_PUSH_VLAN_PRIORITY: u16 = 6;
+const TCA_VLAN_PUSH_ETH_DST: u16 = 7;
+const TCA_VLAN_PUSH_ETH_SRC: u16 = 8;
+
+/// Options for the `TcActionVlan` action.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcActionVlanOption {
+    /// Rule installation and usage time
+    Tm(Tcf),
+    /// Parameters for the vlan action.
+    Parms(TcVlan),
+    /// VLAN identifier of the pushed or modified tag.
+    PushVlanId(u16),
+    /// VLAN protocol (EtherType) of the pushed or modified tag.
+    PushVlanProtocol(u16),
+    /// Priority of the pushed or modified tag.
+    PushVlanPriority(u8),
+    /// Destination MAC address of the pushed Ethernet header.
+    PushEthDst([u8; 6]),
+    /// Source MAC address of the pushed Ethernet header.
+    PushEthSrc([u8; 6]),
+    /// Other attributes unknown at the time of writing.
+    Other(DefaultNla),
+}
+
+impl Nla for TcActionVlanOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Tm(_) => size_of::<TcfBuffer>(),
+            Self::Parms(_) => size_of::<TcVlanBuffer>(),
+            Self::PushVlanId(_) | Self::PushVlanProtocol(_) => 2,
+            Self::PushVlanPriority(_) => 1,
+            Self::PushEthDst(_) | Self::PushEthSrc(_) => 6,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Tm(p) => p.emit(buffer),
+            Self::Parms(p) => p.emit(buffer),
+            Self::PushVlanId(i) => emit_u16(buffer, *i).unwrap(),
+            Self::PushVlanProtocol(i) => emit_u16_be(buffer, *i).unwrap(),
+            Self::PushVlanPriority(i) => buffer[0] = *i,
+            Self::PushEthDst(mac) | Self::PushEthSrc(mac) => {
+                buffer.copy_from_slice(mac.as_slice())
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Tm(_) => TCA_VLAN_TM,
+            Self::Parms(_) => TCA_VLAN_PARMS,
+            Self::PushVlanId(_) => TCA_VLAN_PUSH_VLAN_ID,
+            Self::PushVlanProtocol(_) => TCA_VLAN_PUSH_VLAN_PROTOCOL,
+            Self::PushVlanPriority(_) => TCA_VLAN_PUSH_VLAN_PRIORITY,
+            Self::PushEthDst(_) => TCA_VLAN_PUSH_ETH_DST,
+            Self::PushEthSrc(_) => TCA_VLAN_PUSH_ETH_SRC,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcActionVlanOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_VLAN_TM => Self::Tm(Tcf::parse(payload)?),
+            TCA_VLAN_PARMS => Self::Parms(TcVlan::parse(payload)?),
+            TCA_VLAN_PUSH_VLAN_ID => Self::PushVlanId(
+                parse_u16(payload)
+                    .context("failed to parse TCA_VLAN_PUSH_VLAN_ID")?,
+            ),
+            TCA_VLAN_PUSH_VLAN_PROTOCOL => Self::PushVlanProtocol(
+                parse_u16_be(payload)
+                    .context("failed to parse TCA_VLAN_PUSH_VLAN_PROTOCOL")?,
+            ),
+            TCA_VLAN_PUSH_VLAN_PRIORITY => Self::PushVlanPriority(
+                parse_u8(payload)
+                    .context("failed to parse TCA_VLAN_PUSH_VLAN_PRIORITY")?,
+            ),
+            TCA_VLAN_PUSH_ETH_DST => Self::PushEthDst(
+                parse_mac(payload)
+                    .context("failed to parse TCA_VLAN_PUSH_ETH_DST")?,
+            ),
+            TCA_VLAN_PUSH_ETH_SRC => Self::PushEthSrc(
+                parse_mac(payload)
+                    .context("failed to parse TCA_VLAN_PUSH_ETH_SRC")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Parameters for the vlan action.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcVlan {
+    /// Generic action parameters.
+    pub generic: TcActionGeneric,
+    /// Describes how the VLAN tag or Ethernet header is modified.
+    pub v_action: TcVlanActionType,
+}
+
+// kernel struct `tc_vlan`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcVlanBuffer {
+    generic: TcActionGenericBuffer,
+    v_action: i32,
+}
+
+impl TcVlan {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcVlanBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcVlanBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            generic: TcActionGeneric::parse(
+                &payload[..size_of::<TcActionGenericBuffer>()],
+            )?,
+            v_action: raw.v_action.into(),
+        })
+    }
+}
+
+impl From<&TcVlan> for TcVlanBuffer {
+    fn from(vlan: &TcVlan) -> Self {
+        Self {
+            generic: TcActionGenericBuffer::from(&vlan.generic),
+            v_action: vlan.v_action.into(),
+        }
+    }
+}
+
+impl Emitable for TcVlan {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcVlanBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcVlanBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCA_VLAN_ACT_POP: i32 = 1;
+const TCA_VLAN_ACT_PUSH: i32 = 2;
+const TCA_VLAN_ACT_MODIFY: i32 = 3;
+const TCA_VLAN_ACT_POP_ETH: i32 = 4;
+const TCA_VLAN_ACT_PUSH_ETH: i32 = 5;
+
+/// Type of vlan action.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcVlanActionType {
+    /// Pop the outermost VLAN tag.
+    #[default]
+    Pop,
+    /// Push a VLAN tag.
+    Push,
+    /// Modify the outermost VLAN tag.
+    Modify,
+    /// Pop the Ethernet header.
+    PopEth,
+    /// Push an Ethernet header.
+    PushEth,
+    /// Other action type unknown at the time of writing.
+    Other(i32),
+}
+
+impl From<i32> for TcVlanActionType {
+    fn from(d: i32) -> Self {
+        match d {
+            TCA_VLAN_ACT_POP => Self::Pop,
+            TCA_VLAN_ACT_PUSH => Self::Push,
+            TCA_VLAN_ACT_MODIFY => Self::Modify,
+            TCA_VLAN_ACT_POP_ETH => Self::PopEth,
+            TCA_VLAN_ACT_PUSH_ETH => Self::PushEth,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcVlanActionType> for i32 {
+    fn from(v: TcVlanActionType) -> i32 {
+        match v {
+            TcVlanActionType::Pop => TCA_VLAN_ACT_POP,
+            TcVlanActionType::Push => TCA_VLAN_ACT_PUSH,
+            TcVlanActionType::Modify => TCA_VLAN_ACT_MODIFY,
+            TcVlanActionType::PopEth => TCA_VLAN_ACT_POP_ETH,
+            TcVlanActionType::PushEth => TCA_VLAN_ACT_PUSH_ETH,
+            TcVlanActionType::Other(d) => d,
+        }
+    }
+}
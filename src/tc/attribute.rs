@@ -6,10 +6,13 @@ use netlink_packet_core::{
     ParseableParametrized,
 };
 
-use super::{TcOption, TcStats, TcStats2, TcXstats, VecTcOption};
+use super::{
+    options::{emit_tc_options, tc_options_len},
+    TcEstimator, TcOption, TcStab, TcStats, TcStats2, TcXstats, VecTcOption,
+};
 
 const TCA_KIND: u16 = 1;
-const TCA_OPTIONS: u16 = 2;
+pub(crate) const TCA_OPTIONS: u16 = 2;
 const TCA_STATS: u16 = 3;
 const TCA_XSTATS: u16 = 4;
 const TCA_RATE: u16 = 5;
@@ -34,11 +37,12 @@ pub enum TcAttribute {
     Stats(TcStats),
     /// Module-specific statistics
     Xstats(TcXstats),
-    /// Rate limit
-    Rate(Vec<u8>),
+    /// Rate estimator
+    Rate(TcEstimator),
     Fcnt(Vec<u8>),
     Stats2(Vec<TcStats2>),
-    Stab(Vec<u8>),
+    /// Size table
+    Stab(Vec<TcStab>),
     Chain(u32),
     HwOffload(u8),
     DumpInvisible(bool),
@@ -48,16 +52,16 @@ pub enum TcAttribute {
 impl Nla for TcAttribute {
     fn value_len(&self) -> usize {
         match *self {
-            Self::Rate(ref bytes)
-            | Self::Fcnt(ref bytes)
-            | Self::Stab(ref bytes) => bytes.len(),
+            Self::Fcnt(ref bytes) => bytes.len(),
+            Self::Rate(ref v) => v.buffer_len(),
+            Self::Stab(ref v) => v.as_slice().buffer_len(),
             Self::Chain(_) => 4,
             Self::Xstats(ref v) => v.buffer_len(),
             Self::HwOffload(_) => 1,
             Self::Stats2(ref v) => v.as_slice().buffer_len(),
             Self::Stats(ref v) => v.buffer_len(),
             Self::Kind(ref string) => string.len() + 1,
-            Self::Options(ref opt) => opt.as_slice().buffer_len(),
+            Self::Options(ref opt) => tc_options_len(opt),
             Self::DumpInvisible(_) => 0, // The existence of NLA means true
             Self::Other(ref attr) => attr.value_len(),
         }
@@ -65,9 +69,9 @@ impl Nla for TcAttribute {
 
     fn emit_value(&self, buffer: &mut [u8]) {
         match *self {
-            Self::Rate(ref bytes)
-            | Self::Fcnt(ref bytes)
-            | Self::Stab(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Self::Fcnt(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Self::Rate(ref v) => v.emit(buffer),
+            Self::Stab(ref v) => v.as_slice().emit(buffer),
             Self::Chain(v) => emit_u32(buffer, v).unwrap(),
             Self::Xstats(ref v) => v.emit(buffer),
             Self::HwOffload(ref val) => buffer[0] = *val,
@@ -77,7 +81,7 @@ impl Nla for TcAttribute {
                 buffer[..string.len()].copy_from_slice(string.as_bytes());
                 buffer[string.len()] = 0;
             }
-            Self::Options(ref opt) => opt.as_slice().emit(buffer),
+            Self::Options(ref opt) => emit_tc_options(opt, buffer),
             Self::DumpInvisible(_) => (),
             Self::Other(ref attr) => attr.emit_value(buffer),
         }
@@ -107,6 +111,16 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str>
     fn parse_with_param(
         buf: &NlaBuffer<&'a T>,
         kind: &str,
+    ) -> Result<Self, DecodeError> {
+        Self::parse_with_class(buf, kind, false)
+    }
+}
+
+impl TcAttribute {
+    pub(crate) fn parse_with_class<T: AsRef<[u8]> + ?Sized>(
+        buf: &NlaBuffer<&T>,
+        kind: &str,
+        class: bool,
     ) -> Result<Self, DecodeError> {
         let payload = buf.value();
         Ok(match buf.kind() {
@@ -122,22 +136,39 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str>
                 TcStats::parse(payload).context("failed to parse TCA_STATS")?,
             ),
             TCA_XSTATS => TcAttribute::Xstats(
-                TcXstats::parse_with_param(buf, kind)
+                TcXstats::parse_with_class(buf, kind, class)
                     .context("invalid TCA_XSTATS")?,
             ),
-            TCA_RATE => TcAttribute::Rate(payload.to_vec()),
+            TCA_RATE => TcAttribute::Rate(
+                TcEstimator::parse(payload)
+                    .context("failed to parse TCA_RATE")?,
+            ),
             TCA_FCNT => TcAttribute::Fcnt(payload.to_vec()),
             TCA_STATS2 => {
                 let mut nlas = vec![];
                 for nla in NlasIterator::new(payload) {
                     let nla = nla.context("invalid TCA_STATS2")?;
-                    nlas.push(TcStats2::parse_with_param(&nla, kind).context(
-                        format!("failed to parse TCA_STATS2 for kind {kind}"),
-                    )?);
+                    nlas.push(
+                        TcStats2::parse_with_class(&nla, kind, class).context(
+                            format!(
+                                "failed to parse TCA_STATS2 for kind {kind}"
+                            ),
+                        )?,
+                    );
                 }
                 TcAttribute::Stats2(nlas)
             }
-            TCA_STAB => TcAttribute::Stab(payload.to_vec()),
+            TCA_STAB => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_STAB")?;
+                    nlas.push(
+                        TcStab::parse(&nla)
+                            .context("failed to parse TCA_STAB")?,
+                    );
+                }
+                TcAttribute::Stab(nlas)
+            }
             TCA_CHAIN => TcAttribute::Chain(
                 parse_u32(payload).context("failed to parse TCA_CHAIN")?,
             ),
@@ -156,12 +187,21 @@ pub(crate) struct VecTcAttribute(pub(crate) Vec<TcAttribute>);
 
 impl Parseable<[u8]> for VecTcAttribute {
     fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        Self::parse_with_class(buf, false)
+    }
+}
+
+impl VecTcAttribute {
+    pub(crate) fn parse_with_class(
+        buf: &[u8],
+        class: bool,
+    ) -> Result<Self, DecodeError> {
         let mut attributes = vec![];
         let mut kind = String::new();
 
         for nla_buf in NlasIterator::new(buf) {
             let attribute =
-                TcAttribute::parse_with_param(&nla_buf?, kind.as_str())?;
+                TcAttribute::parse_with_class(&nla_buf?, kind.as_str(), class)?;
             if let TcAttribute::Kind(s) = &attribute {
                 kind = s.to_string();
             }
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+/// Filter chain template
+///
+/// A chain created by RTM_NEWCHAIN may carry a classifier template: the
+/// TCA_KIND and TCA_OPTIONS of a filter holding only the keys (and their
+/// masks) every filter of this chain will match on. This allows hardware
+/// offload drivers to reserve their lookup tables before filters are
+/// inserted. RTM_GETCHAIN dumps the template back.
+use netlink_packet_core::DecodeError;
+
+use super::{TcAttribute, TcFilterFlower, TcHeader, TcMessage, TcOption};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcChainTemplate {
+    /// Chain index
+    pub chain: u32,
+    /// Classifier kind, only `flower` supports chain templates
+    pub kind: String,
+    /// Classifier options holding the template keys
+    pub options: Vec<TcOption>,
+}
+
+impl TcChainTemplate {
+    pub fn new(chain: u32, kind: &str, options: Vec<TcOption>) -> Self {
+        Self {
+            chain,
+            kind: kind.to_string(),
+            options,
+        }
+    }
+
+    /// Extract the chain template from a RTM_NEWCHAIN or RTM_GETCHAIN
+    /// message. Return `None` when the chain has no template.
+    pub fn from_message(msg: &TcMessage) -> Option<Self> {
+        let mut chain = 0;
+        let mut kind = None;
+        let mut options = Vec::new();
+        for attr in &msg.attributes {
+            match attr {
+                TcAttribute::Chain(v) => chain = *v,
+                TcAttribute::Kind(v) => kind = Some(v.as_str()),
+                TcAttribute::Options(v) => options.clone_from(v),
+                _ => (),
+            }
+        }
+        kind.map(|kind| Self::new(chain, kind, options))
+    }
+
+    /// Build the RTM_NEWCHAIN message carrying this template. The `header`
+    /// should hold the interface index (or block index) and the parent
+    /// qdisc of the chain.
+    pub fn to_message(&self, header: TcHeader) -> TcMessage {
+        let mut attributes = vec![
+            TcAttribute::Chain(self.chain),
+            TcAttribute::Kind(self.kind.clone()),
+        ];
+        if !self.options.is_empty() {
+            attributes.push(TcAttribute::Options(self.options.clone()));
+        }
+        TcMessage::from_parts(header, attributes)
+    }
+
+    /// Check that the template uses a classifier the kernel accepts in
+    /// chain templates and that all its options belong to this classifier.
+    ///
+    /// Only flower implements chain templates, the kernel rejects other
+    /// classifiers with EOPNOTSUPP.
+    pub fn validate(&self) -> Result<(), DecodeError> {
+        if self.kind != TcFilterFlower::KIND {
+            return Err(DecodeError::from(format!(
+                "Classifier {} does not support chain templates, only {} does",
+                self.kind,
+                TcFilterFlower::KIND
+            )));
+        }
+        for option in &self.options {
+            if !matches!(option, TcOption::Flower(_)) {
+                return Err(DecodeError::from(format!(
+                    "Chain template option {option:?} is not a {} option",
+                    self.kind
+                )));
+            }
+        }
+        Ok(())
+    }
+}
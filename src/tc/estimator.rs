@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+/// Generic rate estimator attached to a qdisc or class via TCA_RATE.
+///
+/// The estimator samples the traffic every `2^(interval + 2)` seconds
+/// (interval ranges from -2 to 3, meaning 250ms to 8s) and computes an
+/// exponentially weighted moving average with `1/2^ewma_log` as weight.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEstimator {
+    /// Sampling period, `2^(interval + 2)` seconds
+    pub interval: i8,
+    /// Logarithm of the averaging time constant
+    pub ewma_log: u8,
+}
+
+// kernel struct `tc_estimator`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEstimatorBuffer {
+    interval: i8,
+    ewma_log: u8,
+}
+
+impl TcEstimator {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcEstimatorBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEstimatorBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            interval: raw.interval,
+            ewma_log: raw.ewma_log,
+        })
+    }
+}
+
+impl From<&TcEstimator> for TcEstimatorBuffer {
+    fn from(value: &TcEstimator) -> Self {
+        Self {
+            interval: value.interval,
+            ewma_log: value.ewma_log,
+        }
+    }
+}
+
+impl Emitable for TcEstimator {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEstimatorBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEstimatorBuffer::from(self);
+        buffer[..size_of::<TcEstimatorBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: MIT
+
+/// Basic filter
+///
+/// Matches packets using an extended match expression and performs the
+/// attached actions on them.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u32, parse_u32, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, NlasIterator, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use crate::tc::{TcAction, TcActionPoliceOption, TcEmatchTree, TcHandle};
+
+const TCA_BASIC_CLASSID: u16 = 1;
+const TCA_BASIC_EMATCHES: u16 = 2;
+const TCA_BASIC_ACT: u16 = 3;
+const TCA_BASIC_POLICE: u16 = 4;
+const TCA_BASIC_PCNT: u16 = 5;
+// const TCA_BASIC_PAD: u16 = 6;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcFilterBasic {}
+impl TcFilterBasic {
+    pub const KIND: &'static str = "basic";
+}
+
+/// Options of the basic filter
+///
+/// The kernel has no TCA_BASIC_MARK_MASK attribute: the packet mark is
+/// matched by a meta ematch on [`TcEmatchMetaId::Nfmark`].
+///
+/// [`TcEmatchMetaId::Nfmark`]: crate::tc::TcEmatchMetaId::Nfmark
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterBasicOption {
+    ClassId(TcHandle),
+    /// Extended match expression
+    Ematches(Vec<TcEmatchTree>),
+    Action(Vec<TcAction>),
+    Police(Vec<TcActionPoliceOption>),
+    /// Performance counters, only provided by kernel
+    Pcnt(TcBasicPcnt),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterBasicOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::ClassId(_) => 4,
+            Self::Ematches(v) => v.as_slice().buffer_len(),
+            Self::Action(acts) => acts.as_slice().buffer_len(),
+            Self::Police(p) => p.as_slice().buffer_len(),
+            Self::Pcnt(v) => v.buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::ClassId(i) => emit_u32(buffer, (*i).into()).unwrap(),
+            Self::Ematches(v) => v.as_slice().emit(buffer),
+            Self::Action(acts) => acts.as_slice().emit(buffer),
+            Self::Police(p) => p.as_slice().emit(buffer),
+            Self::Pcnt(v) => v.emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::ClassId(_) => TCA_BASIC_CLASSID,
+            Self::Ematches(_) => TCA_BASIC_EMATCHES,
+            Self::Action(_) => TCA_BASIC_ACT,
+            Self::Police(_) => TCA_BASIC_POLICE,
+            Self::Pcnt(_) => TCA_BASIC_PCNT,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterBasicOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_BASIC_CLASSID => Self::ClassId(
+                parse_u32(payload)
+                    .context("failed to parse TCA_BASIC_CLASSID")?
+                    .into(),
+            ),
+            TCA_BASIC_EMATCHES => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_BASIC_EMATCHES")?;
+                    nlas.push(
+                        TcEmatchTree::parse(&nla)
+                            .context("failed to parse TCA_BASIC_EMATCHES")?,
+                    );
+                }
+                Self::Ematches(nlas)
+            }
+            TCA_BASIC_ACT => {
+                let mut acts = vec![];
+                for act in NlasIterator::new(payload) {
+                    let act = act.context("invalid TCA_BASIC_ACT")?;
+                    acts.push(
+                        TcAction::parse(&act)
+                            .context("failed to parse TCA_BASIC_ACT")?,
+                    );
+                }
+                Self::Action(acts)
+            }
+            TCA_BASIC_POLICE => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_BASIC_POLICE")?;
+                    nlas.push(
+                        TcActionPoliceOption::parse(&nla)
+                            .context("failed to parse TCA_BASIC_POLICE")?,
+                    );
+                }
+                Self::Police(nlas)
+            }
+            TCA_BASIC_PCNT => Self::Pcnt(
+                TcBasicPcnt::parse(payload)
+                    .context("failed to parse TCA_BASIC_PCNT")?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse basic nla")?,
+            ),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcBasicPcnt {
+    /// Number of packets looked up
+    pub rcnt: u64,
+    /// Number of packets matched
+    pub rhit: u64,
+}
+
+// kernel struct `tc_basic_pcnt`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcBasicPcntBuffer {
+    rcnt: u64,
+    rhit: u64,
+}
+
+impl TcBasicPcnt {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcBasicPcntBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcBasicPcntBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            rcnt: raw.rcnt,
+            rhit: raw.rhit,
+        })
+    }
+}
+
+impl From<&TcBasicPcnt> for TcBasicPcntBuffer {
+    fn from(value: &TcBasicPcnt) -> Self {
+        Self {
+            rcnt: value.rcnt,
+            rhit: value.rhit,
+        }
+    }
+}
+
+impl Emitable for TcBasicPcnt {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcBasicPcntBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcBasicPcntBuffer::from(self);
+        buffer[..size_of::<TcBasicPcntBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MIT
+
+/// Control group filter
+///
+/// Classifies packets based on the net_cls class identifier of the control
+/// group of the sending socket.
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable,
+};
+
+use crate::tc::{TcAction, TcActionPoliceOption, TcEmatchTree};
+
+const TCA_CGROUP_ACT: u16 = 1;
+const TCA_CGROUP_POLICE: u16 = 2;
+const TCA_CGROUP_EMATCHES: u16 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcFilterCgroup {}
+impl TcFilterCgroup {
+    pub const KIND: &'static str = "cgroup";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterCgroupOption {
+    Action(Vec<TcAction>),
+    Police(Vec<TcActionPoliceOption>),
+    /// Extended match expression
+    Ematches(Vec<TcEmatchTree>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterCgroupOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Action(acts) => acts.as_slice().buffer_len(),
+            Self::Police(p) => p.as_slice().buffer_len(),
+            Self::Ematches(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Action(acts) => acts.as_slice().emit(buffer),
+            Self::Police(p) => p.as_slice().emit(buffer),
+            Self::Ematches(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Action(_) => TCA_CGROUP_ACT,
+            Self::Police(_) => TCA_CGROUP_POLICE,
+            Self::Ematches(_) => TCA_CGROUP_EMATCHES,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterCgroupOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CGROUP_ACT => {
+                let mut acts = vec![];
+                for act in NlasIterator::new(payload) {
+                    let act = act.context("invalid TCA_CGROUP_ACT")?;
+                    acts.push(
+                        TcAction::parse(&act)
+                            .context("failed to parse TCA_CGROUP_ACT")?,
+                    );
+                }
+                Self::Action(acts)
+            }
+            TCA_CGROUP_POLICE => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_CGROUP_POLICE")?;
+                    nlas.push(
+                        TcActionPoliceOption::parse(&nla)
+                            .context("failed to parse TCA_CGROUP_POLICE")?,
+                    );
+                }
+                Self::Police(nlas)
+            }
+            TCA_CGROUP_EMATCHES => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_CGROUP_EMATCHES")?;
+                    nlas.push(
+                        TcEmatchTree::parse(&nla)
+                            .context("failed to parse TCA_CGROUP_EMATCHES")?,
+                    );
+                }
+                Self::Ematches(nlas)
+            }
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse cgroup nla")?,
+            ),
+        })
+    }
+}
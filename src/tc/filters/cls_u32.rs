@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: MIT
 
-use std::mem::size_of;
+use std::{
+    mem::size_of,
+    net::{Ipv4Addr, Ipv6Addr},
+};
 
 use netlink_packet_core::ErrorContext;
 /// U32 filter
@@ -11,13 +14,19 @@ use netlink_packet_core::ErrorContext;
 /// until the first match occurs, and then the associated action is
 /// performed.
 use netlink_packet_core::{
-    emit_u32, parse_u32, DecodeError, Emitable, Parseable,
+    emit_u32, parse_string, parse_u32, DecodeError, Emitable, Parseable,
     {DefaultNla, Nla, NlaBuffer, NlasIterator},
 };
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 
-use super::u32_flags::{TcU32OptionFlags, TcU32SelectorFlags};
-use crate::tc::{TcAction, TcHandle};
+use super::{
+    u32_flags::{TcU32OptionFlags, TcU32SelectorFlags},
+    u32_hash::TcU32Handle,
+};
+use crate::{
+    tc::{TcAction, TcActionPoliceOption, TcHandle},
+    IpProtocol,
+};
 
 const TCA_U32_CLASSID: u16 = 1;
 const TCA_U32_HASH: u16 = 2;
@@ -43,15 +52,21 @@ impl TcFilterU32 {
 #[non_exhaustive]
 pub enum TcFilterU32Option {
     ClassId(TcHandle),
-    Hash(u32),
-    Link(u32),
+    /// Hash table and bucket this filter is added to.
+    Hash(TcU32Handle),
+    /// Hash table looked up when the selector matches.
+    Link(TcU32Handle),
+    /// Number of buckets of the hash table being created.
     Divisor(u32),
     Selector(TcU32Selector),
-    Police(Vec<u8>),
+    Police(Vec<TcActionPoliceOption>),
     Action(Vec<TcAction>),
-    Indev(Vec<u8>),
-    Pnct(Vec<u8>),
-    Mark(Vec<u8>),
+    /// Name of the input interface the packets must come from.
+    Indev(String),
+    /// Hit counters, only present in dumps.
+    Pcnt(TcU32Pcnt),
+    /// Packet mark the packets must match.
+    Mark(TcU32Mark),
     Flags(TcU32OptionFlags),
     Other(DefaultNla),
 }
@@ -59,10 +74,10 @@ pub enum TcFilterU32Option {
 impl Nla for TcFilterU32Option {
     fn value_len(&self) -> usize {
         match self {
-            Self::Police(b)
-            | Self::Indev(b)
-            | Self::Pnct(b)
-            | Self::Mark(b) => b.len(),
+            Self::Police(p) => p.as_slice().buffer_len(),
+            Self::Indev(s) => s.len() + 1,
+            Self::Pcnt(p) => p.buffer_len(),
+            Self::Mark(m) => m.buffer_len(),
             Self::Hash(_)
             | Self::Link(_)
             | Self::Divisor(_)
@@ -76,13 +91,17 @@ impl Nla for TcFilterU32Option {
 
     fn emit_value(&self, buffer: &mut [u8]) {
         match self {
-            Self::Police(b)
-            | Self::Indev(b)
-            | Self::Pnct(b)
-            | Self::Mark(b) => buffer.copy_from_slice(b.as_slice()),
-            Self::Hash(i) | Self::Link(i) | Self::Divisor(i) => {
-                emit_u32(buffer, *i).unwrap()
+            Self::Police(p) => p.as_slice().emit(buffer),
+            Self::Indev(s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
             }
+            Self::Pcnt(p) => p.emit(buffer),
+            Self::Mark(m) => m.emit(buffer),
+            Self::Hash(h) | Self::Link(h) => {
+                emit_u32(buffer, (*h).into()).unwrap()
+            }
+            Self::Divisor(i) => emit_u32(buffer, *i).unwrap(),
             Self::Flags(f) => emit_u32(buffer, f.bits()).unwrap(),
             Self::ClassId(i) => emit_u32(buffer, (*i).into()).unwrap(),
             Self::Selector(s) => s.emit(buffer),
@@ -101,7 +120,7 @@ impl Nla for TcFilterU32Option {
             Self::Police(_) => TCA_U32_POLICE,
             Self::Action(_) => TCA_U32_ACT,
             Self::Indev(_) => TCA_U32_INDEV,
-            Self::Pnct(_) => TCA_U32_PCNT,
+            Self::Pcnt(_) => TCA_U32_PCNT,
             Self::Mark(_) => TCA_U32_MARK,
             Self::Flags(_) => TCA_U32_FLAGS,
             Self::Other(attr) => attr.kind(),
@@ -119,12 +138,12 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 parse_u32(payload)
                     .context("failed to parse TCA_U32_CLASSID")?,
             )),
-            TCA_U32_HASH => Self::Hash(
+            TCA_U32_HASH => Self::Hash(TcU32Handle::from(
                 parse_u32(payload).context("failed to parse TCA_U32_HASH")?,
-            ),
-            TCA_U32_LINK => Self::Link(
+            )),
+            TCA_U32_LINK => Self::Link(TcU32Handle::from(
                 parse_u32(payload).context("failed to parse TCA_U32_LINK")?,
-            ),
+            )),
             TCA_U32_DIVISOR => Self::Divisor(
                 parse_u32(payload)
                     .context("failed to parse TCA_U32_DIVISOR")?,
@@ -133,7 +152,17 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 TcU32Selector::parse(payload)
                     .context("failed to parse TCA_U32_SEL")?,
             ),
-            TCA_U32_POLICE => Self::Police(payload.to_vec()),
+            TCA_U32_POLICE => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_U32_POLICE")?;
+                    nlas.push(
+                        TcActionPoliceOption::parse(&nla)
+                            .context("failed to parse TCA_U32_POLICE")?,
+                    );
+                }
+                Self::Police(nlas)
+            }
             TCA_U32_ACT => {
                 let mut acts = vec![];
                 for act in NlasIterator::new(payload) {
@@ -145,9 +174,18 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 }
                 Self::Action(acts)
             }
-            TCA_U32_INDEV => Self::Indev(payload.to_vec()),
-            TCA_U32_PCNT => Self::Pnct(payload.to_vec()),
-            TCA_U32_MARK => Self::Mark(payload.to_vec()),
+            TCA_U32_INDEV => Self::Indev(
+                parse_string(payload)
+                    .context("failed to parse TCA_U32_INDEV")?,
+            ),
+            TCA_U32_PCNT => Self::Pcnt(
+                TcU32Pcnt::parse(payload)
+                    .context("failed to parse TCA_U32_PCNT")?,
+            ),
+            TCA_U32_MARK => Self::Mark(
+                TcU32Mark::parse(payload)
+                    .context("failed to parse TCA_U32_MARK")?,
+            ),
             TCA_U32_FLAGS => Self::Flags(TcU32OptionFlags::from_bits_retain(
                 parse_u32(payload).context("failed to parse TCA_U32_FLAGS")?,
             )),
@@ -290,6 +328,283 @@ impl TcU32Selector {
     }
 }
 
+// Offsets of the IPv4, IPv6 and TCP/UDP fields matched by the selector
+// helpers, as used by iproute2.
+const IP_TOS_OFFSET: i32 = 1;
+const IP_PROTOCOL_OFFSET: i32 = 9;
+const IP_SRC_OFFSET: i32 = 12;
+const IP_DST_OFFSET: i32 = 16;
+const IP_SPORT_OFFSET: i32 = 20;
+const IP_DPORT_OFFSET: i32 = 22;
+const IP6_SRC_OFFSET: i32 = 8;
+const IP6_DST_OFFSET: i32 = 24;
+const L4_SPORT_OFFSET: i32 = 0;
+const L4_DPORT_OFFSET: i32 = 2;
+const TCP_FLAGS_OFFSET: i32 = 13;
+
+/// Selector helpers
+///
+/// These helpers add keys the way the iproute2 `u32` parser does: 8 and 16
+/// bit matches are packed into the 32 bit word containing them, and keys
+/// on the same word are merged when they do not conflict. Keys with
+/// `nexthdr` set are relative to the next header offset computed by
+/// [`TcU32Selector::var_offset`] in the parent hash table link.
+///
+/// Like iproute2, the `match_*()` helpers fail on a misaligned offset, on a
+/// match conflicting with a key already set on the same word, and when the
+/// selector would hold more keys than its 8 bits `nkeys` can count.
+impl TcU32Selector {
+    /// Match `val` under `mask` on the 32 bit word at `off`, which must be
+    /// a multiple of 4.
+    pub fn match_u32(
+        mut self,
+        val: u32,
+        mask: u32,
+        off: i32,
+        nexthdr: bool,
+    ) -> Result<Self, DecodeError> {
+        if off % 4 != 0 {
+            return Err(DecodeError::from(format!(
+                "u32 match offset {off} is not a multiple of 4"
+            )));
+        }
+        let val = val & mask;
+        let offmask = if nexthdr { -1 } else { 0 };
+        match self
+            .keys
+            .iter_mut()
+            .find(|k| k.off == off && k.offmask == offmask)
+        {
+            Some(key) => {
+                if (key.val ^ val) & (key.mask & mask) != 0 {
+                    return Err(DecodeError::from(format!(
+                        "u32 match {val:#010x}/{mask:#010x} at {off} \
+                         conflicts with {:#010x}/{:#010x}",
+                        key.val, key.mask
+                    )));
+                }
+                key.val |= val;
+                key.mask |= mask;
+            }
+            None => {
+                self.nkeys =
+                    u8::try_from(self.keys.len() + 1).map_err(|_| {
+                        DecodeError::from(format!(
+                            "u32 selector cannot hold more than {} keys",
+                            u8::MAX
+                        ))
+                    })?;
+                self.keys.push(TcU32Key {
+                    mask,
+                    val,
+                    off,
+                    offmask,
+                });
+            }
+        }
+        Ok(self)
+    }
+
+    /// Match `val` under `mask` on the 16 bit word at `off`, which must be
+    /// even.
+    pub fn match_u16(
+        self,
+        val: u16,
+        mask: u16,
+        off: i32,
+        nexthdr: bool,
+    ) -> Result<Self, DecodeError> {
+        if off % 2 != 0 {
+            return Err(DecodeError::from(format!(
+                "u16 match offset {off} is not even"
+            )));
+        }
+        let shift = 8 * (2 - (off & 2)) as u32;
+        self.match_u32(
+            (val as u32) << shift,
+            (mask as u32) << shift,
+            off & !3,
+            nexthdr,
+        )
+    }
+
+    /// Match `val` under `mask` on the byte at `off`.
+    pub fn match_u8(
+        self,
+        val: u8,
+        mask: u8,
+        off: i32,
+        nexthdr: bool,
+    ) -> Result<Self, DecodeError> {
+        let shift = 8 * (3 - (off & 3)) as u32;
+        self.match_u32(
+            (val as u32) << shift,
+            (mask as u32) << shift,
+            off & !3,
+            nexthdr,
+        )
+    }
+
+    /// Match the IPv4 source address on a prefix.
+    pub fn match_ip_src(
+        self,
+        addr: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<Self, DecodeError> {
+        self.match_u32(
+            u32::from(addr),
+            prefix_mask(prefix_len),
+            IP_SRC_OFFSET,
+            false,
+        )
+    }
+
+    /// Match the IPv4 destination address on a prefix.
+    pub fn match_ip_dst(
+        self,
+        addr: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<Self, DecodeError> {
+        self.match_u32(
+            u32::from(addr),
+            prefix_mask(prefix_len),
+            IP_DST_OFFSET,
+            false,
+        )
+    }
+
+    /// Match the IPv4 type of service.
+    pub fn match_ip_tos(self, tos: u8, mask: u8) -> Result<Self, DecodeError> {
+        self.match_u8(tos, mask, IP_TOS_OFFSET, false)
+    }
+
+    /// Match the IPv4 protocol.
+    pub fn match_ip_protocol(
+        self,
+        protocol: IpProtocol,
+    ) -> Result<Self, DecodeError> {
+        self.match_u8(protocol.into(), 0xff, IP_PROTOCOL_OFFSET, false)
+    }
+
+    /// Match the source port of an IPv4 packet without options.
+    pub fn match_ip_sport(self, port: u16) -> Result<Self, DecodeError> {
+        self.match_u16(port, 0xffff, IP_SPORT_OFFSET, false)
+    }
+
+    /// Match the destination port of an IPv4 packet without options.
+    pub fn match_ip_dport(self, port: u16) -> Result<Self, DecodeError> {
+        self.match_u16(port, 0xffff, IP_DPORT_OFFSET, false)
+    }
+
+    /// Match the IPv6 source address on a prefix.
+    pub fn match_ip6_src(
+        self,
+        addr: Ipv6Addr,
+        prefix_len: u8,
+    ) -> Result<Self, DecodeError> {
+        self.match_ip6_addr(addr, prefix_len, IP6_SRC_OFFSET)
+    }
+
+    /// Match the IPv6 destination address on a prefix.
+    pub fn match_ip6_dst(
+        self,
+        addr: Ipv6Addr,
+        prefix_len: u8,
+    ) -> Result<Self, DecodeError> {
+        self.match_ip6_addr(addr, prefix_len, IP6_DST_OFFSET)
+    }
+
+    fn match_ip6_addr(
+        mut self,
+        addr: Ipv6Addr,
+        prefix_len: u8,
+        off: i32,
+    ) -> Result<Self, DecodeError> {
+        let octets = addr.octets();
+        for (i, word) in octets.chunks(4).enumerate() {
+            let bits = prefix_len.saturating_sub(32 * i as u8).min(32);
+            if bits == 0 {
+                break;
+            }
+            self = self.match_u32(
+                u32::from_be_bytes([word[0], word[1], word[2], word[3]]),
+                prefix_mask(bits),
+                off + 4 * i as i32,
+                false,
+            )?;
+        }
+        Ok(self)
+    }
+
+    /// Match the TCP or UDP source port in the next header.
+    pub fn match_l4_sport(self, port: u16) -> Result<Self, DecodeError> {
+        self.match_u16(port, 0xffff, L4_SPORT_OFFSET, true)
+    }
+
+    /// Match the TCP or UDP destination port in the next header.
+    pub fn match_l4_dport(self, port: u16) -> Result<Self, DecodeError> {
+        self.match_u16(port, 0xffff, L4_DPORT_OFFSET, true)
+    }
+
+    /// Match the TCP flags in the next header.
+    pub fn match_tcp_flags(
+        self,
+        flags: u8,
+        mask: u8,
+    ) -> Result<Self, DecodeError> {
+        self.match_u8(flags, mask, TCP_FLAGS_OFFSET, true)
+    }
+
+    /// Mark the selector as terminal: a match classifies the packet
+    /// instead of following a link.
+    pub fn terminal(mut self) -> Self {
+        self.flags |= TcU32SelectorFlags::Terminal;
+        self
+    }
+
+    /// Compute the next header offset from the 16 bit word at `at`:
+    /// `(word & mask) >> shift`, plus the `plus` constant.
+    ///
+    /// For IPv4, `var_offset(0, 0x0f00, 6, 0)` skips the IPv4 header
+    /// including its options.
+    pub fn var_offset(
+        mut self,
+        at: u16,
+        mask: u16,
+        shift: u8,
+        plus: u16,
+    ) -> Self {
+        self.flags |= TcU32SelectorFlags::VarOffset;
+        self.offoff = at;
+        self.offmask = mask.to_be();
+        self.offshift = shift;
+        self.off = plus;
+        self
+    }
+
+    /// Consume the next header offset: the keys of the linked hash table
+    /// are relative to the next header.
+    pub fn eat(mut self) -> Self {
+        self.flags |= TcU32SelectorFlags::Eat;
+        self
+    }
+
+    /// Hash the 32 bit word at `at` under `mask` to select the bucket of
+    /// the linked hash table, see [`super::TcU32Node::bucket`].
+    pub fn hash_key(mut self, mask: u32, at: u16) -> Self {
+        self.hmask = mask.to_be();
+        self.hoff = at;
+        self
+    }
+}
+
+fn prefix_mask(prefix_len: u8) -> u32 {
+    match prefix_len {
+        0 => 0,
+        l => u32::MAX << (32 - l.min(32) as u32),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 #[non_exhaustive]
 pub struct TcU32Key {
@@ -359,3 +674,146 @@ impl TcU32Key {
         })
     }
 }
+
+/// Hit counters of a u32 filter.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcU32Pcnt {
+    /// Number of packets the filter was run against.
+    pub rcnt: u64,
+    /// Number of packets matching the filter.
+    pub rhit: u64,
+    /// Number of packets matching each key, in order.
+    pub kcnts: Vec<u64>,
+}
+
+// kernel struct `tc_u32_pcnt`, followed by one counter per key
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcU32PcntBuffer {
+    rcnt: u64,
+    rhit: u64,
+}
+
+impl TcU32Pcnt {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, kcnts) =
+            TcU32PcntBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcU32PcntBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            rcnt: raw.rcnt,
+            rhit: raw.rhit,
+            kcnts: kcnts
+                .chunks_exact(size_of::<u64>())
+                .map(|c| {
+                    u64::from_ne_bytes([
+                        c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7],
+                    ])
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Emitable for TcU32Pcnt {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcU32PcntBuffer>() + self.kcnts.len() * size_of::<u64>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcU32PcntBuffer {
+            rcnt: self.rcnt,
+            rhit: self.rhit,
+        };
+        let (header, kcnts) = buffer.split_at_mut(size_of::<TcU32PcntBuffer>());
+        header.copy_from_slice(raw.as_bytes());
+        for (cnt, chunk) in self
+            .kcnts
+            .iter()
+            .zip(kcnts.chunks_exact_mut(size_of::<u64>()))
+        {
+            chunk.copy_from_slice(&cnt.to_ne_bytes());
+        }
+    }
+}
+
+/// Packet mark matched by a u32 filter.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcU32Mark {
+    pub val: u32,
+    pub mask: u32,
+    /// Number of packets whose mark matched, only set in dumps.
+    pub success: u32,
+}
+
+// kernel struct `tc_u32_mark`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcU32MarkBuffer {
+    val: u32,
+    mask: u32,
+    success: u32,
+}
+
+impl TcU32Mark {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcU32MarkBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcU32MarkBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            val: raw.val,
+            mask: raw.mask,
+            success: raw.success,
+        })
+    }
+}
+
+impl From<&TcU32Mark> for TcU32MarkBuffer {
+    fn from(mark: &TcU32Mark) -> Self {
+        Self {
+            val: mark.val,
+            mask: mark.mask,
+            success: mark.success,
+        }
+    }
+}
+
+impl Emitable for TcU32Mark {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcU32MarkBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcU32MarkBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
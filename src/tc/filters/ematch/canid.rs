@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+/// CAN identifier ematch
+///
+/// Matches CAN frames whose identifier matches any of the filters.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchCanFilter {
+    /// CAN identifier, including the EFF/RTR/ERR flags
+    pub can_id: u32,
+    pub can_mask: u32,
+}
+
+// kernel struct `can_filter`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchCanFilterBuffer {
+    can_id: u32,
+    can_mask: u32,
+}
+
+impl TcEmatchCanFilter {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcEmatchCanFilterBuffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchCanFilterBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            can_id: raw.can_id,
+            can_mask: raw.can_mask,
+        })
+    }
+
+    pub(crate) fn parse_list(payload: &[u8]) -> Result<Vec<Self>, DecodeError> {
+        payload
+            .chunks(size_of::<TcEmatchCanFilterBuffer>())
+            .map(Self::parse)
+            .collect()
+    }
+}
+
+impl From<&TcEmatchCanFilter> for TcEmatchCanFilterBuffer {
+    fn from(value: &TcEmatchCanFilter) -> Self {
+        Self {
+            can_id: value.can_id,
+            can_mask: value.can_mask,
+        }
+    }
+}
+
+impl Emitable for TcEmatchCanFilter {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchCanFilterBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchCanFilterBuffer::from(self);
+        buffer[..size_of::<TcEmatchCanFilterBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
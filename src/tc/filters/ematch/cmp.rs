@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: MIT
+
+/// Simple packet data comparison ematch
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{TcEmatchLayer, TcEmatchOperand};
+
+const TCF_EM_CMP_TRANS: u8 = 1;
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchCmp {
+    /// Value to compare with
+    pub val: u32,
+    /// Mask applied to the packet data before comparison
+    pub mask: u32,
+    /// Offset of the packet data from the start of `layer`
+    pub off: u16,
+    pub align: TcEmatchCmpAlign,
+    /// Convert the packet data from network byte order before comparison
+    pub trans: bool,
+    pub layer: TcEmatchLayer,
+    pub opnd: TcEmatchOperand,
+}
+
+// kernel struct `tcf_em_cmp`, `align` and `flags` share `align_flags` while
+// `layer` and `opnd` share `layer_opnd`, each one using 4 bits.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchCmpBuffer {
+    val: u32,
+    mask: u32,
+    off: u16,
+    align_flags: u8,
+    layer_opnd: u8,
+}
+
+impl TcEmatchCmp {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcEmatchCmpBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchCmpBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            val: raw.val,
+            mask: raw.mask,
+            off: raw.off,
+            align: (raw.align_flags & 0xf).into(),
+            trans: (raw.align_flags >> 4) & TCF_EM_CMP_TRANS != 0,
+            layer: (raw.layer_opnd & 0xf).into(),
+            opnd: (raw.layer_opnd >> 4).into(),
+        })
+    }
+}
+
+impl From<&TcEmatchCmp> for TcEmatchCmpBuffer {
+    fn from(value: &TcEmatchCmp) -> Self {
+        let flags = if value.trans { TCF_EM_CMP_TRANS } else { 0 };
+        Self {
+            val: value.val,
+            mask: value.mask,
+            off: value.off,
+            align_flags: (u8::from(value.align) & 0xf) | (flags << 4),
+            layer_opnd: (u8::from(value.layer) & 0xf)
+                | (u8::from(value.opnd) << 4),
+        }
+    }
+}
+
+impl Emitable for TcEmatchCmp {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchCmpBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchCmpBuffer::from(self);
+        buffer[..size_of::<TcEmatchCmpBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCF_EM_ALIGN_U8: u8 = 1;
+const TCF_EM_ALIGN_U16: u8 = 2;
+const TCF_EM_ALIGN_U32: u8 = 4;
+
+/// Size of the packet data compared by [`TcEmatchCmp`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchCmpAlign {
+    #[default]
+    U8,
+    U16,
+    U32,
+    Other(u8),
+}
+
+impl From<u8> for TcEmatchCmpAlign {
+    fn from(d: u8) -> Self {
+        match d {
+            TCF_EM_ALIGN_U8 => Self::U8,
+            TCF_EM_ALIGN_U16 => Self::U16,
+            TCF_EM_ALIGN_U32 => Self::U32,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchCmpAlign> for u8 {
+    fn from(v: TcEmatchCmpAlign) -> u8 {
+        match v {
+            TcEmatchCmpAlign::U8 => TCF_EM_ALIGN_U8,
+            TcEmatchCmpAlign::U16 => TCF_EM_ALIGN_U16,
+            TcEmatchCmpAlign::U32 => TCF_EM_ALIGN_U32,
+            TcEmatchCmpAlign::Other(d) => d,
+        }
+    }
+}
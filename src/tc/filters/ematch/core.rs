@@ -0,0 +1,513 @@
+// SPDX-License-Identifier: MIT
+
+/// Extended matches
+///
+/// Extended matches (ematches) are small matchers combined into a logic
+/// expression, used by the `basic`, `flow` and `cgroup` classifiers. The
+/// expression is sent as a flat list of ematches, each one linked to the
+/// next one by an AND or OR relation. Container ematches reference another
+/// position in the list, allowing nested expressions.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u32, parse_u32, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, NlasIterator, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::{
+    TcEmatchCanFilter, TcEmatchCmp, TcEmatchIpset, TcEmatchMetaOption,
+    TcEmatchNbyte,
+};
+use crate::tc::TcU32Key;
+
+const TCA_EMATCH_TREE_HDR: u16 = 1;
+const TCA_EMATCH_TREE_LIST: u16 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcEmatchTree {
+    Hdr(TcEmatchTreeHdr),
+    List(Vec<TcEmatch>),
+    Other(DefaultNla),
+}
+
+impl TcEmatchTree {
+    /// Generate the ematch tree attributes for the specified ematches,
+    /// numbering them by their position in the list.
+    pub fn from_ematches(mut ematches: Vec<TcEmatch>) -> Vec<Self> {
+        for (i, ematch) in ematches.iter_mut().enumerate() {
+            ematch.index = i as u16 + 1;
+        }
+        vec![
+            Self::Hdr(TcEmatchTreeHdr {
+                nmatches: ematches.len() as u16,
+                progid: 0,
+            }),
+            Self::List(ematches),
+        ]
+    }
+}
+
+impl Nla for TcEmatchTree {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Hdr(v) => v.buffer_len(),
+            Self::List(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Hdr(v) => v.emit(buffer),
+            Self::List(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Hdr(_) => TCA_EMATCH_TREE_HDR,
+            Self::List(_) => TCA_EMATCH_TREE_LIST,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcEmatchTree {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_EMATCH_TREE_HDR => Self::Hdr(
+                TcEmatchTreeHdr::parse(payload)
+                    .context("failed to parse TCA_EMATCH_TREE_HDR")?,
+            ),
+            TCA_EMATCH_TREE_LIST => {
+                let mut ematches = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_EMATCH_TREE_LIST")?;
+                    ematches.push(
+                        TcEmatch::parse(&nla)
+                            .context("failed to parse TCA_EMATCH_TREE_LIST")?,
+                    );
+                }
+                Self::List(ematches)
+            }
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse ematch nla")?,
+            ),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchTreeHdr {
+    /// Number of ematches in TCA_EMATCH_TREE_LIST
+    pub nmatches: u16,
+    pub progid: u16,
+}
+
+// kernel struct `tcf_ematch_tree_hdr`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchTreeHdrBuffer {
+    nmatches: u16,
+    progid: u16,
+}
+
+impl TcEmatchTreeHdr {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcEmatchTreeHdrBuffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchTreeHdrBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            nmatches: raw.nmatches,
+            progid: raw.progid,
+        })
+    }
+}
+
+impl From<&TcEmatchTreeHdr> for TcEmatchTreeHdrBuffer {
+    fn from(value: &TcEmatchTreeHdr) -> Self {
+        Self {
+            nmatches: value.nmatches,
+            progid: value.progid,
+        }
+    }
+}
+
+impl Emitable for TcEmatchTreeHdr {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchTreeHdrBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchTreeHdrBuffer::from(self);
+        buffer[..size_of::<TcEmatchTreeHdrBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCF_EM_REL_END: u16 = 0;
+const TCF_EM_REL_AND: u16 = 1 << 0;
+const TCF_EM_REL_OR: u16 = 1 << 1;
+const TCF_EM_REL_MASK: u16 = 3;
+const TCF_EM_INVERT: u16 = 1 << 2;
+// const TCF_EM_SIMPLE: u16 = 1 << 3;
+
+/// Relation between an ematch and the following one
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchRelation {
+    /// Last ematch of the expression
+    #[default]
+    End,
+    And,
+    Or,
+    Other(u16),
+}
+
+impl From<u16> for TcEmatchRelation {
+    fn from(d: u16) -> Self {
+        match d {
+            TCF_EM_REL_END => Self::End,
+            TCF_EM_REL_AND => Self::And,
+            TCF_EM_REL_OR => Self::Or,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchRelation> for u16 {
+    fn from(v: TcEmatchRelation) -> u16 {
+        match v {
+            TcEmatchRelation::End => TCF_EM_REL_END,
+            TcEmatchRelation::And => TCF_EM_REL_AND,
+            TcEmatchRelation::Or => TCF_EM_REL_OR,
+            TcEmatchRelation::Other(d) => d,
+        }
+    }
+}
+
+// kernel struct `tcf_ematch_hdr`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchHdrBuffer {
+    matchid: u16,
+    kind: u16,
+    flags: u16,
+    pad: u16,
+}
+
+const TCF_EM_CONTAINER: u16 = 0;
+const TCF_EM_CMP: u16 = 1;
+const TCF_EM_NBYTE: u16 = 2;
+const TCF_EM_U32: u16 = 3;
+const TCF_EM_META: u16 = 4;
+// const TCF_EM_TEXT: u16 = 5;
+// const TCF_EM_VLAN: u16 = 6;
+const TCF_EM_CANID: u16 = 7;
+const TCF_EM_IPSET: u16 = 8;
+// const TCF_EM_IPT: u16 = 9;
+
+/// One ematch of TCA_EMATCH_TREE_LIST
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcEmatch {
+    /// Position in the list, starting from 1
+    pub index: u16,
+    pub matchid: u16,
+    /// Relation to the next ematch
+    pub relation: TcEmatchRelation,
+    /// Negate the result of this ematch
+    pub invert: bool,
+    /// Header flags other than the relation and the inversion, e.g.
+    /// TCF_EM_SIMPLE
+    pub flags: u16,
+    pub matcher: TcEmatchMatcher,
+}
+
+impl TcEmatch {
+    pub fn new(matcher: TcEmatchMatcher) -> Self {
+        Self {
+            index: 0,
+            matchid: 0,
+            relation: TcEmatchRelation::End,
+            invert: false,
+            flags: 0,
+            matcher,
+        }
+    }
+
+    /// Link this ematch to the next one with an AND relation
+    pub fn and(mut self) -> Self {
+        self.relation = TcEmatchRelation::And;
+        self
+    }
+
+    /// Link this ematch to the next one with an OR relation
+    pub fn or(mut self) -> Self {
+        self.relation = TcEmatchRelation::Or;
+        self
+    }
+
+    /// Negate the result of this ematch
+    pub fn invert(mut self) -> Self {
+        self.invert = !self.invert;
+        self
+    }
+}
+
+impl Nla for TcEmatch {
+    fn value_len(&self) -> usize {
+        size_of::<TcEmatchHdrBuffer>() + self.matcher.buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        let mut flags = u16::from(self.relation) | self.flags;
+        if self.invert {
+            flags |= TCF_EM_INVERT;
+        }
+        let raw = TcEmatchHdrBuffer {
+            matchid: self.matchid,
+            kind: self.matcher.kind(),
+            flags,
+            pad: 0,
+        };
+        let hdr_len = size_of::<TcEmatchHdrBuffer>();
+        buffer[..hdr_len].copy_from_slice(raw.as_bytes());
+        self.matcher.emit(&mut buffer[hdr_len..]);
+    }
+
+    fn kind(&self) -> u16 {
+        self.index
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcEmatch {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        let (raw, data) =
+            TcEmatchHdrBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchHdrBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            index: buf.kind(),
+            matchid: raw.matchid,
+            relation: (raw.flags & TCF_EM_REL_MASK).into(),
+            invert: raw.flags & TCF_EM_INVERT != 0,
+            flags: raw.flags & !(TCF_EM_REL_MASK | TCF_EM_INVERT),
+            matcher: TcEmatchMatcher::parse(raw.kind, data)?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcEmatchMatcher {
+    /// Evaluate the sub-expression starting at this position of the list,
+    /// counted from 0
+    Container(u32),
+    Cmp(TcEmatchCmp),
+    Nbyte(TcEmatchNbyte),
+    U32(TcU32Key),
+    Meta(Vec<TcEmatchMetaOption>),
+    Canid(Vec<TcEmatchCanFilter>),
+    Ipset(TcEmatchIpset),
+    /// Matcher kind and its raw data
+    Other(u16, Vec<u8>),
+}
+
+impl TcEmatchMatcher {
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Container(_) => TCF_EM_CONTAINER,
+            Self::Cmp(_) => TCF_EM_CMP,
+            Self::Nbyte(_) => TCF_EM_NBYTE,
+            Self::U32(_) => TCF_EM_U32,
+            Self::Meta(_) => TCF_EM_META,
+            Self::Canid(_) => TCF_EM_CANID,
+            Self::Ipset(_) => TCF_EM_IPSET,
+            Self::Other(kind, _) => *kind,
+        }
+    }
+
+    fn parse(kind: u16, payload: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            TCF_EM_CONTAINER => Self::Container(
+                parse_u32(payload)
+                    .context("failed to parse container ematch")?,
+            ),
+            TCF_EM_CMP => Self::Cmp(
+                TcEmatchCmp::parse(payload)
+                    .context("failed to parse cmp ematch")?,
+            ),
+            TCF_EM_NBYTE => Self::Nbyte(
+                TcEmatchNbyte::parse(payload)
+                    .context("failed to parse nbyte ematch")?,
+            ),
+            TCF_EM_U32 => Self::U32(
+                TcU32Key::parse(payload)
+                    .context("failed to parse u32 ematch")?,
+            ),
+            TCF_EM_META => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid meta ematch")?;
+                    nlas.push(
+                        TcEmatchMetaOption::parse(&nla)
+                            .context("failed to parse meta ematch")?,
+                    );
+                }
+                Self::Meta(nlas)
+            }
+            TCF_EM_CANID => Self::Canid(
+                TcEmatchCanFilter::parse_list(payload)
+                    .context("failed to parse canid ematch")?,
+            ),
+            TCF_EM_IPSET => Self::Ipset(
+                TcEmatchIpset::parse(payload)
+                    .context("failed to parse ipset ematch")?,
+            ),
+            _ => Self::Other(kind, payload.to_vec()),
+        })
+    }
+}
+
+impl Emitable for TcEmatchMatcher {
+    fn buffer_len(&self) -> usize {
+        match self {
+            Self::Container(_) => 4,
+            Self::Cmp(v) => v.buffer_len(),
+            Self::Nbyte(v) => v.buffer_len(),
+            Self::U32(v) => v.buffer_len(),
+            Self::Meta(v) => v.as_slice().buffer_len(),
+            Self::Canid(v) => v.iter().map(|f| f.buffer_len()).sum(),
+            Self::Ipset(v) => v.buffer_len(),
+            Self::Other(_, v) => v.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Container(v) => emit_u32(buffer, *v).unwrap(),
+            Self::Cmp(v) => v.emit(buffer),
+            Self::Nbyte(v) => v.emit(buffer),
+            Self::U32(v) => v.emit(&mut buffer[..v.buffer_len()]),
+            Self::Meta(v) => v.as_slice().emit(buffer),
+            Self::Canid(v) => {
+                let mut offset = 0;
+                for filter in v {
+                    let len = filter.buffer_len();
+                    filter.emit(&mut buffer[offset..offset + len]);
+                    offset += len;
+                }
+            }
+            Self::Ipset(v) => v.emit(buffer),
+            Self::Other(_, v) => buffer[..v.len()].copy_from_slice(v),
+        }
+    }
+}
+
+const TCF_LAYER_LINK: u8 = 0;
+const TCF_LAYER_NETWORK: u8 = 1;
+const TCF_LAYER_TRANSPORT: u8 = 2;
+
+/// Packet header an ematch offset is relative to
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchLayer {
+    #[default]
+    Link,
+    Network,
+    Transport,
+    Other(u8),
+}
+
+impl From<u8> for TcEmatchLayer {
+    fn from(d: u8) -> Self {
+        match d {
+            TCF_LAYER_LINK => Self::Link,
+            TCF_LAYER_NETWORK => Self::Network,
+            TCF_LAYER_TRANSPORT => Self::Transport,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchLayer> for u8 {
+    fn from(v: TcEmatchLayer) -> u8 {
+        match v {
+            TcEmatchLayer::Link => TCF_LAYER_LINK,
+            TcEmatchLayer::Network => TCF_LAYER_NETWORK,
+            TcEmatchLayer::Transport => TCF_LAYER_TRANSPORT,
+            TcEmatchLayer::Other(d) => d,
+        }
+    }
+}
+
+const TCF_EM_OPND_EQ: u8 = 0;
+const TCF_EM_OPND_GT: u8 = 1;
+const TCF_EM_OPND_LT: u8 = 2;
+
+/// Comparison operator of the cmp and meta ematches
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchOperand {
+    #[default]
+    Eq,
+    Gt,
+    Lt,
+    Other(u8),
+}
+
+impl From<u8> for TcEmatchOperand {
+    fn from(d: u8) -> Self {
+        match d {
+            TCF_EM_OPND_EQ => Self::Eq,
+            TCF_EM_OPND_GT => Self::Gt,
+            TCF_EM_OPND_LT => Self::Lt,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchOperand> for u8 {
+    fn from(v: TcEmatchOperand) -> u8 {
+        match v {
+            TcEmatchOperand::Eq => TCF_EM_OPND_EQ,
+            TcEmatchOperand::Gt => TCF_EM_OPND_GT,
+            TcEmatchOperand::Lt => TCF_EM_OPND_LT,
+            TcEmatchOperand::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+/// IP set ematch
+///
+/// Matches packets against an existing ipset(8) set.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchIpset {
+    /// Index of the set, as returned by the ipset subsystem
+    pub index: u16,
+    /// Number of dimensions of the set to match
+    pub dim: u8,
+    /// Bit `n` set means dimension `n` matches the source address or port
+    /// instead of the destination one
+    pub flags: u8,
+}
+
+// kernel struct `xt_set_info`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchIpsetBuffer {
+    index: u16,
+    dim: u8,
+    flags: u8,
+}
+
+impl TcEmatchIpset {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcEmatchIpsetBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchIpsetBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            index: raw.index,
+            dim: raw.dim,
+            flags: raw.flags,
+        })
+    }
+}
+
+impl From<&TcEmatchIpset> for TcEmatchIpsetBuffer {
+    fn from(value: &TcEmatchIpset) -> Self {
+        Self {
+            index: value.index,
+            dim: value.dim,
+            flags: value.flags,
+        }
+    }
+}
+
+impl Emitable for TcEmatchIpset {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchIpsetBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchIpsetBuffer::from(self);
+        buffer[..size_of::<TcEmatchIpsetBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
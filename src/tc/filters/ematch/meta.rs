@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: MIT
+
+/// Metadata ematch
+///
+/// Compares two values, each one being either a constant or packet
+/// metadata such as the firewall mark or the socket priority.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::TcEmatchOperand;
+
+const TCA_EM_META_HDR: u16 = 1;
+const TCA_EM_META_LVALUE: u16 = 2;
+const TCA_EM_META_RVALUE: u16 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcEmatchMetaOption {
+    Hdr(TcEmatchMetaHdr),
+    /// Left value, a native endian `u32` for [`TcEmatchMetaType::Int`] or
+    /// raw bytes for [`TcEmatchMetaType::Var`]. For non constant values of
+    /// integer type, this is the mask applied to the metadata.
+    Lvalue(Vec<u8>),
+    /// Right value, same format as [`TcEmatchMetaOption::Lvalue`]
+    Rvalue(Vec<u8>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcEmatchMetaOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Hdr(v) => v.buffer_len(),
+            Self::Lvalue(v) | Self::Rvalue(v) => v.len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Hdr(v) => v.emit(buffer),
+            Self::Lvalue(v) | Self::Rvalue(v) => {
+                buffer[..v.len()].copy_from_slice(v)
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Hdr(_) => TCA_EM_META_HDR,
+            Self::Lvalue(_) => TCA_EM_META_LVALUE,
+            Self::Rvalue(_) => TCA_EM_META_RVALUE,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcEmatchMetaOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_EM_META_HDR => Self::Hdr(
+                TcEmatchMetaHdr::parse(payload)
+                    .context("failed to parse TCA_EM_META_HDR")?,
+            ),
+            TCA_EM_META_LVALUE => Self::Lvalue(payload.to_vec()),
+            TCA_EM_META_RVALUE => Self::Rvalue(payload.to_vec()),
+            _ => Self::Other(
+                DefaultNla::parse(buf)
+                    .context("failed to parse meta ematch nla")?,
+            ),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchMetaHdr {
+    pub left: TcEmatchMetaValue,
+    pub right: TcEmatchMetaValue,
+}
+
+// kernel struct `tcf_meta_hdr` holding two `struct tcf_meta_val`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchMetaHdrBuffer {
+    left_kind: u16,
+    left_shift: u8,
+    left_op: u8,
+    right_kind: u16,
+    right_shift: u8,
+    right_op: u8,
+}
+
+impl TcEmatchMetaHdr {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcEmatchMetaHdrBuffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchMetaHdrBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            left: TcEmatchMetaValue::new(
+                raw.left_kind,
+                raw.left_shift,
+                raw.left_op,
+            ),
+            right: TcEmatchMetaValue::new(
+                raw.right_kind,
+                raw.right_shift,
+                raw.right_op,
+            ),
+        })
+    }
+}
+
+impl From<&TcEmatchMetaHdr> for TcEmatchMetaHdrBuffer {
+    fn from(value: &TcEmatchMetaHdr) -> Self {
+        Self {
+            left_kind: value.left.raw_kind(),
+            left_shift: value.left.shift,
+            left_op: value.left.op.into(),
+            right_kind: value.right.raw_kind(),
+            right_shift: value.right.shift,
+            right_op: value.right.op.into(),
+        }
+    }
+}
+
+impl Emitable for TcEmatchMetaHdr {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchMetaHdrBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchMetaHdrBuffer::from(self);
+        buffer[..size_of::<TcEmatchMetaHdrBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCF_META_TYPE_SHIFT: u16 = 12;
+const TCF_META_TYPE_MASK: u16 = 0xf << TCF_META_TYPE_SHIFT;
+const TCF_META_ID_MASK: u16 = 0x7ff;
+
+/// One side of the meta ematch comparison
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcEmatchMetaValue {
+    pub kind: TcEmatchMetaType,
+    pub id: TcEmatchMetaId,
+    /// Right shift applied to integer values, left side only
+    pub shift: u8,
+    /// Comparison operator, left side only
+    pub op: TcEmatchOperand,
+}
+
+impl TcEmatchMetaValue {
+    fn new(kind: u16, shift: u8, op: u8) -> Self {
+        Self {
+            kind: ((kind & TCF_META_TYPE_MASK) >> TCF_META_TYPE_SHIFT).into(),
+            id: (kind & TCF_META_ID_MASK).into(),
+            shift,
+            op: op.into(),
+        }
+    }
+
+    fn raw_kind(&self) -> u16 {
+        (u16::from(self.kind) << TCF_META_TYPE_SHIFT) & TCF_META_TYPE_MASK
+            | u16::from(self.id) & TCF_META_ID_MASK
+    }
+}
+
+const TCF_META_TYPE_VAR: u16 = 0;
+const TCF_META_TYPE_INT: u16 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchMetaType {
+    /// Variable length value, e.g. interface name
+    #[default]
+    Var,
+    /// Integer value
+    Int,
+    Other(u16),
+}
+
+impl From<u16> for TcEmatchMetaType {
+    fn from(d: u16) -> Self {
+        match d {
+            TCF_META_TYPE_VAR => Self::Var,
+            TCF_META_TYPE_INT => Self::Int,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchMetaType> for u16 {
+    fn from(v: TcEmatchMetaType) -> u16 {
+        match v {
+            TcEmatchMetaType::Var => TCF_META_TYPE_VAR,
+            TcEmatchMetaType::Int => TCF_META_TYPE_INT,
+            TcEmatchMetaType::Other(d) => d,
+        }
+    }
+}
+
+const TCF_META_ID_VALUE: u16 = 0;
+const TCF_META_ID_RANDOM: u16 = 1;
+const TCF_META_ID_LOADAVG_0: u16 = 2;
+const TCF_META_ID_LOADAVG_1: u16 = 3;
+const TCF_META_ID_LOADAVG_2: u16 = 4;
+const TCF_META_ID_DEV: u16 = 5;
+const TCF_META_ID_PRIORITY: u16 = 6;
+const TCF_META_ID_PROTOCOL: u16 = 7;
+const TCF_META_ID_PKTTYPE: u16 = 8;
+const TCF_META_ID_PKTLEN: u16 = 9;
+const TCF_META_ID_DATALEN: u16 = 10;
+const TCF_META_ID_MACLEN: u16 = 11;
+const TCF_META_ID_NFMARK: u16 = 12;
+const TCF_META_ID_TCINDEX: u16 = 13;
+const TCF_META_ID_RTCLASSID: u16 = 14;
+const TCF_META_ID_RTIIF: u16 = 15;
+const TCF_META_ID_SK_FAMILY: u16 = 16;
+const TCF_META_ID_SK_STATE: u16 = 17;
+const TCF_META_ID_SK_REUSE: u16 = 18;
+const TCF_META_ID_SK_BOUND_IF: u16 = 19;
+const TCF_META_ID_SK_REFCNT: u16 = 20;
+const TCF_META_ID_SK_SHUTDOWN: u16 = 21;
+const TCF_META_ID_SK_PROTO: u16 = 22;
+const TCF_META_ID_SK_TYPE: u16 = 23;
+const TCF_META_ID_SK_RCVBUF: u16 = 24;
+const TCF_META_ID_SK_RMEM_ALLOC: u16 = 25;
+const TCF_META_ID_SK_WMEM_ALLOC: u16 = 26;
+const TCF_META_ID_SK_OMEM_ALLOC: u16 = 27;
+const TCF_META_ID_SK_WMEM_QUEUED: u16 = 28;
+const TCF_META_ID_SK_RCV_QLEN: u16 = 29;
+const TCF_META_ID_SK_SND_QLEN: u16 = 30;
+const TCF_META_ID_SK_ERR_QLEN: u16 = 31;
+const TCF_META_ID_SK_FORWARD_ALLOCS: u16 = 32;
+const TCF_META_ID_SK_SNDBUF: u16 = 33;
+const TCF_META_ID_SK_ALLOCS: u16 = 34;
+const TCF_META_ID_SK_ROUTE_CAPS: u16 = 35;
+const TCF_META_ID_SK_HASH: u16 = 36;
+const TCF_META_ID_SK_LINGERTIME: u16 = 37;
+const TCF_META_ID_SK_ACK_BACKLOG: u16 = 38;
+const TCF_META_ID_SK_MAX_ACK_BACKLOG: u16 = 39;
+const TCF_META_ID_SK_PRIO: u16 = 40;
+const TCF_META_ID_SK_RCVLOWAT: u16 = 41;
+const TCF_META_ID_SK_RCVTIMEO: u16 = 42;
+const TCF_META_ID_SK_SNDTIMEO: u16 = 43;
+const TCF_META_ID_SK_SENDMSG_OFF: u16 = 44;
+const TCF_META_ID_SK_WRITE_PENDING: u16 = 45;
+const TCF_META_ID_VLAN_TAG: u16 = 46;
+const TCF_META_ID_RXHASH: u16 = 47;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcEmatchMetaId {
+    #[default]
+    /// Constant value from TCA_EM_META_LVALUE or TCA_EM_META_RVALUE
+    Value,
+    Random,
+    Loadavg0,
+    Loadavg1,
+    Loadavg2,
+    Dev,
+    Priority,
+    Protocol,
+    Pkttype,
+    Pktlen,
+    Datalen,
+    Maclen,
+    Nfmark,
+    Tcindex,
+    Rtclassid,
+    Rtiif,
+    SkFamily,
+    SkState,
+    SkReuse,
+    SkBoundIf,
+    SkRefcnt,
+    SkShutdown,
+    SkProto,
+    SkType,
+    SkRcvbuf,
+    SkRmemAlloc,
+    SkWmemAlloc,
+    SkOmemAlloc,
+    SkWmemQueued,
+    SkRcvQlen,
+    SkSndQlen,
+    SkErrQlen,
+    SkForwardAllocs,
+    SkSndbuf,
+    SkAllocs,
+    /// Deprecated, always fails to match
+    SkRouteCaps,
+    SkHash,
+    SkLingertime,
+    SkAckBacklog,
+    SkMaxAckBacklog,
+    SkPrio,
+    SkRcvlowat,
+    SkRcvtimeo,
+    SkSndtimeo,
+    SkSendmsgOff,
+    SkWritePending,
+    VlanTag,
+    Rxhash,
+    Other(u16),
+}
+
+impl From<u16> for TcEmatchMetaId {
+    fn from(d: u16) -> Self {
+        match d {
+            TCF_META_ID_VALUE => Self::Value,
+            TCF_META_ID_RANDOM => Self::Random,
+            TCF_META_ID_LOADAVG_0 => Self::Loadavg0,
+            TCF_META_ID_LOADAVG_1 => Self::Loadavg1,
+            TCF_META_ID_LOADAVG_2 => Self::Loadavg2,
+            TCF_META_ID_DEV => Self::Dev,
+            TCF_META_ID_PRIORITY => Self::Priority,
+            TCF_META_ID_PROTOCOL => Self::Protocol,
+            TCF_META_ID_PKTTYPE => Self::Pkttype,
+            TCF_META_ID_PKTLEN => Self::Pktlen,
+            TCF_META_ID_DATALEN => Self::Datalen,
+            TCF_META_ID_MACLEN => Self::Maclen,
+            TCF_META_ID_NFMARK => Self::Nfmark,
+            TCF_META_ID_TCINDEX => Self::Tcindex,
+            TCF_META_ID_RTCLASSID => Self::Rtclassid,
+            TCF_META_ID_RTIIF => Self::Rtiif,
+            TCF_META_ID_SK_FAMILY => Self::SkFamily,
+            TCF_META_ID_SK_STATE => Self::SkState,
+            TCF_META_ID_SK_REUSE => Self::SkReuse,
+            TCF_META_ID_SK_BOUND_IF => Self::SkBoundIf,
+            TCF_META_ID_SK_REFCNT => Self::SkRefcnt,
+            TCF_META_ID_SK_SHUTDOWN => Self::SkShutdown,
+            TCF_META_ID_SK_PROTO => Self::SkProto,
+            TCF_META_ID_SK_TYPE => Self::SkType,
+            TCF_META_ID_SK_RCVBUF => Self::SkRcvbuf,
+            TCF_META_ID_SK_RMEM_ALLOC => Self::SkRmemAlloc,
+            TCF_META_ID_SK_WMEM_ALLOC => Self::SkWmemAlloc,
+            TCF_META_ID_SK_OMEM_ALLOC => Self::SkOmemAlloc,
+            TCF_META_ID_SK_WMEM_QUEUED => Self::SkWmemQueued,
+            TCF_META_ID_SK_RCV_QLEN => Self::SkRcvQlen,
+            TCF_META_ID_SK_SND_QLEN => Self::SkSndQlen,
+            TCF_META_ID_SK_ERR_QLEN => Self::SkErrQlen,
+            TCF_META_ID_SK_FORWARD_ALLOCS => Self::SkForwardAllocs,
+            TCF_META_ID_SK_SNDBUF => Self::SkSndbuf,
+            TCF_META_ID_SK_ALLOCS => Self::SkAllocs,
+            TCF_META_ID_SK_ROUTE_CAPS => Self::SkRouteCaps,
+            TCF_META_ID_SK_HASH => Self::SkHash,
+            TCF_META_ID_SK_LINGERTIME => Self::SkLingertime,
+            TCF_META_ID_SK_ACK_BACKLOG => Self::SkAckBacklog,
+            TCF_META_ID_SK_MAX_ACK_BACKLOG => Self::SkMaxAckBacklog,
+            TCF_META_ID_SK_PRIO => Self::SkPrio,
+            TCF_META_ID_SK_RCVLOWAT => Self::SkRcvlowat,
+            TCF_META_ID_SK_RCVTIMEO => Self::SkRcvtimeo,
+            TCF_META_ID_SK_SNDTIMEO => Self::SkSndtimeo,
+            TCF_META_ID_SK_SENDMSG_OFF => Self::SkSendmsgOff,
+            TCF_META_ID_SK_WRITE_PENDING => Self::SkWritePending,
+            TCF_META_ID_VLAN_TAG => Self::VlanTag,
+            TCF_META_ID_RXHASH => Self::Rxhash,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcEmatchMetaId> for u16 {
+    fn from(v: TcEmatchMetaId) -> u16 {
+        match v {
+            TcEmatchMetaId::Value => TCF_META_ID_VALUE,
+            TcEmatchMetaId::Random => TCF_META_ID_RANDOM,
+            TcEmatchMetaId::Loadavg0 => TCF_META_ID_LOADAVG_0,
+            TcEmatchMetaId::Loadavg1 => TCF_META_ID_LOADAVG_1,
+            TcEmatchMetaId::Loadavg2 => TCF_META_ID_LOADAVG_2,
+            TcEmatchMetaId::Dev => TCF_META_ID_DEV,
+            TcEmatchMetaId::Priority => TCF_META_ID_PRIORITY,
+            TcEmatchMetaId::Protocol => TCF_META_ID_PROTOCOL,
+            TcEmatchMetaId::Pkttype => TCF_META_ID_PKTTYPE,
+            TcEmatchMetaId::Pktlen => TCF_META_ID_PKTLEN,
+            TcEmatchMetaId::Datalen => TCF_META_ID_DATALEN,
+            TcEmatchMetaId::Maclen => TCF_META_ID_MACLEN,
+            TcEmatchMetaId::Nfmark => TCF_META_ID_NFMARK,
+            TcEmatchMetaId::Tcindex => TCF_META_ID_TCINDEX,
+            TcEmatchMetaId::Rtclassid => TCF_META_ID_RTCLASSID,
+            TcEmatchMetaId::Rtiif => TCF_META_ID_RTIIF,
+            TcEmatchMetaId::SkFamily => TCF_META_ID_SK_FAMILY,
+            TcEmatchMetaId::SkState => TCF_META_ID_SK_STATE,
+            TcEmatchMetaId::SkReuse => TCF_META_ID_SK_REUSE,
+            TcEmatchMetaId::SkBoundIf => TCF_META_ID_SK_BOUND_IF,
+            TcEmatchMetaId::SkRefcnt => TCF_META_ID_SK_REFCNT,
+            TcEmatchMetaId::SkShutdown => TCF_META_ID_SK_SHUTDOWN,
+            TcEmatchMetaId::SkProto => TCF_META_ID_SK_PROTO,
+            TcEmatchMetaId::SkType => TCF_META_ID_SK_TYPE,
+            TcEmatchMetaId::SkRcvbuf => TCF_META_ID_SK_RCVBUF,
+            TcEmatchMetaId::SkRmemAlloc => TCF_META_ID_SK_RMEM_ALLOC,
+            TcEmatchMetaId::SkWmemAlloc => TCF_META_ID_SK_WMEM_ALLOC,
+            TcEmatchMetaId::SkOmemAlloc => TCF_META_ID_SK_OMEM_ALLOC,
+            TcEmatchMetaId::SkWmemQueued => TCF_META_ID_SK_WMEM_QUEUED,
+            TcEmatchMetaId::SkRcvQlen => TCF_META_ID_SK_RCV_QLEN,
+            TcEmatchMetaId::SkSndQlen => TCF_META_ID_SK_SND_QLEN,
+            TcEmatchMetaId::SkErrQlen => TCF_META_ID_SK_ERR_QLEN,
+            TcEmatchMetaId::SkForwardAllocs => TCF_META_ID_SK_FORWARD_ALLOCS,
+            TcEmatchMetaId::SkSndbuf => TCF_META_ID_SK_SNDBUF,
+            TcEmatchMetaId::SkAllocs => TCF_META_ID_SK_ALLOCS,
+            TcEmatchMetaId::SkRouteCaps => TCF_META_ID_SK_ROUTE_CAPS,
+            TcEmatchMetaId::SkHash => TCF_META_ID_SK_HASH,
+            TcEmatchMetaId::SkLingertime => TCF_META_ID_SK_LINGERTIME,
+            TcEmatchMetaId::SkAckBacklog => TCF_META_ID_SK_ACK_BACKLOG,
+            TcEmatchMetaId::SkMaxAckBacklog => TCF_META_ID_SK_MAX_ACK_BACKLOG,
+            TcEmatchMetaId::SkPrio => TCF_META_ID_SK_PRIO,
+            TcEmatchMetaId::SkRcvlowat => TCF_META_ID_SK_RCVLOWAT,
+            TcEmatchMetaId::SkRcvtimeo => TCF_META_ID_SK_RCVTIMEO,
+            TcEmatchMetaId::SkSndtimeo => TCF_META_ID_SK_SNDTIMEO,
+            TcEmatchMetaId::SkSendmsgOff => TCF_META_ID_SK_SENDMSG_OFF,
+            TcEmatchMetaId::SkWritePending => TCF_META_ID_SK_WRITE_PENDING,
+            TcEmatchMetaId::VlanTag => TCF_META_ID_VLAN_TAG,
+            TcEmatchMetaId::Rxhash => TCF_META_ID_RXHASH,
+            TcEmatchMetaId::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+
+mod canid;
+mod cmp;
+mod core;
+mod ipset;
+mod meta;
+mod nbyte;
+
+pub use self::{
+    canid::{TcEmatchCanFilter, TcEmatchCanFilterBuffer},
+    cmp::{TcEmatchCmp, TcEmatchCmpAlign, TcEmatchCmpBuffer},
+    core::{
+        TcEmatch, TcEmatchHdrBuffer, TcEmatchLayer, TcEmatchMatcher,
+        TcEmatchOperand, TcEmatchRelation, TcEmatchTree, TcEmatchTreeHdr,
+        TcEmatchTreeHdrBuffer,
+    },
+    ipset::{TcEmatchIpset, TcEmatchIpsetBuffer},
+    meta::{
+        TcEmatchMetaHdr, TcEmatchMetaHdrBuffer, TcEmatchMetaId,
+        TcEmatchMetaOption, TcEmatchMetaType, TcEmatchMetaValue,
+    },
+    nbyte::{TcEmatchNbyte, TcEmatchNbyteBuffer},
+};
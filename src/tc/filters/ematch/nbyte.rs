@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+/// Multi byte comparison ematch
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::TcEmatchLayer;
+
+const NBYTE_LEN_MASK: u16 = 0xfff;
+
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcEmatchNbyte {
+    /// Offset of the packet data from the start of `layer`
+    pub off: u16,
+    pub layer: TcEmatchLayer,
+    /// Bytes to compare with the packet data, at most 4095 bytes
+    pub needle: Vec<u8>,
+}
+
+// kernel struct `tcf_em_nbyte`, `len` and `layer` share `len_layer` with 12
+// and 4 bits each. It is followed by `len` bytes of needle.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcEmatchNbyteBuffer {
+    off: u16,
+    len_layer: u16,
+}
+
+impl TcEmatchNbyte {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, needle) = TcEmatchNbyteBuffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcEmatchNbyteBuffer>(),
+                )
+            })?;
+        let len = (raw.len_layer & NBYTE_LEN_MASK) as usize;
+        if needle.len() < len {
+            return Err(DecodeError::buffer_too_small(
+                payload.len(),
+                size_of::<TcEmatchNbyteBuffer>() + len,
+            ));
+        }
+        Ok(Self {
+            off: raw.off,
+            layer: ((raw.len_layer >> 12) as u8).into(),
+            needle: needle[..len].to_vec(),
+        })
+    }
+}
+
+impl From<&TcEmatchNbyte> for TcEmatchNbyteBuffer {
+    fn from(value: &TcEmatchNbyte) -> Self {
+        Self {
+            off: value.off,
+            len_layer: (value.needle.len() as u16 & NBYTE_LEN_MASK)
+                | (u16::from(u8::from(value.layer)) << 12),
+        }
+    }
+}
+
+impl Emitable for TcEmatchNbyte {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcEmatchNbyteBuffer>() + self.needle.len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcEmatchNbyteBuffer::from(self);
+        let hdr_len = size_of::<TcEmatchNbyteBuffer>();
+        buffer[..hdr_len].copy_from_slice(raw.as_bytes());
+        buffer[hdr_len..hdr_len + self.needle.len()]
+            .copy_from_slice(&self.needle);
+    }
+}
@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: MIT
+
+/// Flow filter
+///
+/// Classifies packets by mapping or hashing the selected flow keys into a
+/// class identifier, e.g. to get per-host fairness with SFQ or DRR.
+use netlink_packet_core::{
+    emit_u32, parse_u32, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, NlasIterator, Parseable,
+};
+
+use crate::tc::{TcAction, TcActionPoliceOption, TcEmatchTree, TcHandle};
+
+const TCA_FLOW_KEYS: u16 = 1;
+const TCA_FLOW_MODE: u16 = 2;
+const TCA_FLOW_BASECLASS: u16 = 3;
+const TCA_FLOW_RSHIFT: u16 = 4;
+const TCA_FLOW_ADDEND: u16 = 5;
+const TCA_FLOW_MASK: u16 = 6;
+const TCA_FLOW_XOR: u16 = 7;
+const TCA_FLOW_DIVISOR: u16 = 8;
+const TCA_FLOW_ACT: u16 = 9;
+const TCA_FLOW_POLICE: u16 = 10;
+const TCA_FLOW_EMATCHES: u16 = 11;
+const TCA_FLOW_PERTURB: u16 = 12;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcFilterFlow {}
+impl TcFilterFlow {
+    pub const KIND: &'static str = "flow";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowOption {
+    Keys(TcFlowKeys),
+    Mode(TcFlowMode),
+    /// Class identifier the computed value is added to
+    BaseClass(TcHandle),
+    Rshift(u32),
+    Addend(u32),
+    Mask(u32),
+    Xor(u32),
+    Divisor(u32),
+    Action(Vec<TcAction>),
+    Police(Vec<TcActionPoliceOption>),
+    /// Extended match expression
+    Ematches(Vec<TcEmatchTree>),
+    /// Hash perturbation period in seconds
+    Perturb(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Keys(_)
+            | Self::Mode(_)
+            | Self::BaseClass(_)
+            | Self::Rshift(_)
+            | Self::Addend(_)
+            | Self::Mask(_)
+            | Self::Xor(_)
+            | Self::Divisor(_)
+            | Self::Perturb(_) => 4,
+            Self::Action(acts) => acts.as_slice().buffer_len(),
+            Self::Police(p) => p.as_slice().buffer_len(),
+            Self::Ematches(v) => v.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Keys(v) => emit_u32(buffer, v.bits()).unwrap(),
+            Self::Mode(v) => emit_u32(buffer, (*v).into()).unwrap(),
+            Self::BaseClass(v) => emit_u32(buffer, (*v).into()).unwrap(),
+            Self::Rshift(v)
+            | Self::Addend(v)
+            | Self::Mask(v)
+            | Self::Xor(v)
+            | Self::Divisor(v)
+            | Self::Perturb(v) => emit_u32(buffer, *v).unwrap(),
+            Self::Action(acts) => acts.as_slice().emit(buffer),
+            Self::Police(p) => p.as_slice().emit(buffer),
+            Self::Ematches(v) => v.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Keys(_) => TCA_FLOW_KEYS,
+            Self::Mode(_) => TCA_FLOW_MODE,
+            Self::BaseClass(_) => TCA_FLOW_BASECLASS,
+            Self::Rshift(_) => TCA_FLOW_RSHIFT,
+            Self::Addend(_) => TCA_FLOW_ADDEND,
+            Self::Mask(_) => TCA_FLOW_MASK,
+            Self::Xor(_) => TCA_FLOW_XOR,
+            Self::Divisor(_) => TCA_FLOW_DIVISOR,
+            Self::Action(_) => TCA_FLOW_ACT,
+            Self::Police(_) => TCA_FLOW_POLICE,
+            Self::Ematches(_) => TCA_FLOW_EMATCHES,
+            Self::Perturb(_) => TCA_FLOW_PERTURB,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOW_KEYS => Self::Keys(TcFlowKeys::from_bits_retain(
+                parse_u32(payload).context("failed to parse TCA_FLOW_KEYS")?,
+            )),
+            TCA_FLOW_MODE => Self::Mode(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_MODE")?
+                    .into(),
+            ),
+            TCA_FLOW_BASECLASS => Self::BaseClass(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_BASECLASS")?
+                    .into(),
+            ),
+            TCA_FLOW_RSHIFT => Self::Rshift(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_RSHIFT")?,
+            ),
+            TCA_FLOW_ADDEND => Self::Addend(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_ADDEND")?,
+            ),
+            TCA_FLOW_MASK => Self::Mask(
+                parse_u32(payload).context("failed to parse TCA_FLOW_MASK")?,
+            ),
+            TCA_FLOW_XOR => Self::Xor(
+                parse_u32(payload).context("failed to parse TCA_FLOW_XOR")?,
+            ),
+            TCA_FLOW_DIVISOR => Self::Divisor(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_DIVISOR")?,
+            ),
+            TCA_FLOW_ACT => {
+                let mut acts = vec![];
+                for act in NlasIterator::new(payload) {
+                    let act = act.context("invalid TCA_FLOW_ACT")?;
+                    acts.push(
+                        TcAction::parse(&act)
+                            .context("failed to parse TCA_FLOW_ACT")?,
+                    );
+                }
+                Self::Action(acts)
+            }
+            TCA_FLOW_POLICE => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_FLOW_POLICE")?;
+                    nlas.push(
+                        TcActionPoliceOption::parse(&nla)
+                            .context("failed to parse TCA_FLOW_POLICE")?,
+                    );
+                }
+                Self::Police(nlas)
+            }
+            TCA_FLOW_EMATCHES => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_FLOW_EMATCHES")?;
+                    nlas.push(
+                        TcEmatchTree::parse(&nla)
+                            .context("failed to parse TCA_FLOW_EMATCHES")?,
+                    );
+                }
+                Self::Ematches(nlas)
+            }
+            TCA_FLOW_PERTURB => Self::Perturb(
+                parse_u32(payload)
+                    .context("failed to parse TCA_FLOW_PERTURB")?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse flow nla")?,
+            ),
+        })
+    }
+}
+
+const FLOW_KEY_SRC: u32 = 1 << 0;
+const FLOW_KEY_DST: u32 = 1 << 1;
+const FLOW_KEY_PROTO: u32 = 1 << 2;
+const FLOW_KEY_PROTO_SRC: u32 = 1 << 3;
+const FLOW_KEY_PROTO_DST: u32 = 1 << 4;
+const FLOW_KEY_IIF: u32 = 1 << 5;
+const FLOW_KEY_PRIORITY: u32 = 1 << 6;
+const FLOW_KEY_MARK: u32 = 1 << 7;
+const FLOW_KEY_NFCT: u32 = 1 << 8;
+const FLOW_KEY_NFCT_SRC: u32 = 1 << 9;
+const FLOW_KEY_NFCT_DST: u32 = 1 << 10;
+const FLOW_KEY_NFCT_PROTO_SRC: u32 = 1 << 11;
+const FLOW_KEY_NFCT_PROTO_DST: u32 = 1 << 12;
+const FLOW_KEY_RTCLASSID: u32 = 1 << 13;
+const FLOW_KEY_SKUID: u32 = 1 << 14;
+const FLOW_KEY_SKGID: u32 = 1 << 15;
+const FLOW_KEY_VLAN_TAG: u32 = 1 << 16;
+const FLOW_KEY_RXHASH: u32 = 1 << 17;
+
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcFlowKeys: u32 {
+        const Src = FLOW_KEY_SRC;
+        const Dst = FLOW_KEY_DST;
+        const Proto = FLOW_KEY_PROTO;
+        const ProtoSrc = FLOW_KEY_PROTO_SRC;
+        const ProtoDst = FLOW_KEY_PROTO_DST;
+        const Iif = FLOW_KEY_IIF;
+        const Priority = FLOW_KEY_PRIORITY;
+        const Mark = FLOW_KEY_MARK;
+        const Nfct = FLOW_KEY_NFCT;
+        const NfctSrc = FLOW_KEY_NFCT_SRC;
+        const NfctDst = FLOW_KEY_NFCT_DST;
+        const NfctProtoSrc = FLOW_KEY_NFCT_PROTO_SRC;
+        const NfctProtoDst = FLOW_KEY_NFCT_PROTO_DST;
+        const RtClassId = FLOW_KEY_RTCLASSID;
+        const SkUid = FLOW_KEY_SKUID;
+        const SkGid = FLOW_KEY_SKGID;
+        const VlanTag = FLOW_KEY_VLAN_TAG;
+        const RxHash = FLOW_KEY_RXHASH;
+        const _ = !0;
+    }
+}
+
+const FLOW_MODE_MAP: u32 = 0;
+const FLOW_MODE_HASH: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcFlowMode {
+    /// Map a single key to a class using `mask`, `xor`, `rshift` and
+    /// `addend`
+    #[default]
+    Map,
+    /// Hash the keys into `divisor` buckets
+    Hash,
+    Other(u32),
+}
+
+impl From<u32> for TcFlowMode {
+    fn from(d: u32) -> Self {
+        match d {
+            FLOW_MODE_MAP => Self::Map,
+            FLOW_MODE_HASH => Self::Hash,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcFlowMode> for u32 {
+    fn from(v: TcFlowMode) -> u32 {
+        match v {
+            TcFlowMode::Map => FLOW_MODE_MAP,
+            TcFlowMode::Hash => FLOW_MODE_HASH,
+            TcFlowMode::Other(d) => d,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{
+    parse_u8, DecodeError, DefaultNla, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+
+macro_rules! nla_err {
+    // Match rule that takes an argument expression
+    ($message:expr) => {
+        &format!("failed to parse {} value", stringify!($message))
+    };
+}
+
+/*
+ * NLA layout:
+ * TCA_FLOWER_KEY_CFM
+ *   TCA_FLOWER_KEY_CFM_*
+ *   ..
+ *   TCA_FLOWER_KEY_CFM_*
+ */
+
+const TCA_FLOWER_KEY_CFM_MD_LEVEL: u16 = 1;
+const TCA_FLOWER_KEY_CFM_OPCODE: u16 = 2;
+
+/// Connectivity Fault Management (IEEE 802.1Q CFM) header matches
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerCfmOption {
+    /// Maintenance domain level, 0 to 7
+    MdLevel(u8),
+    Opcode(u8),
+
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerCfmOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::MdLevel(_) | Self::Opcode(_) => 1,
+
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::MdLevel(i) | Self::Opcode(i) => buffer[0] = *i,
+
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::MdLevel(_) => TCA_FLOWER_KEY_CFM_MD_LEVEL,
+            Self::Opcode(_) => TCA_FLOWER_KEY_CFM_OPCODE,
+
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerCfmOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_CFM_MD_LEVEL => Self::MdLevel(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_CFM_MD_LEVEL))?,
+            ),
+            TCA_FLOWER_KEY_CFM_OPCODE => Self::Opcode(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_CFM_OPCODE))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf)
+                    .context("failed to parse cfm option nla")?,
+            ),
+        })
+    }
+}
@@ -9,10 +9,13 @@ use netlink_packet_core::{
     NLA_F_NESTED,
 };
 
-use super::TcFilterFlowerMplsOption;
+use super::{
+    TcFilterFlowerCfmOption, TcFilterFlowerCtState, TcFilterFlowerEncOption,
+    TcFilterFlowerKeyFlags, TcFilterFlowerMplsOption,
+};
 use crate::{
-    ip::{parse_ipv4_addr, parse_ipv6_addr},
-    tc::TcAction,
+    ip::{parse_ipv4_addr, parse_ipv6_addr, IpProtocol},
+    tc::{TcAction, TcClsFlags},
 };
 
 const TCA_FLOWER_CLASSID: u16 = 1;
@@ -98,8 +101,8 @@ const TCA_FLOWER_KEY_ENC_IP_TOS: u16 = 80;
 const TCA_FLOWER_KEY_ENC_IP_TOS_MASK: u16 = 81;
 const TCA_FLOWER_KEY_ENC_IP_TTL: u16 = 82;
 const TCA_FLOWER_KEY_ENC_IP_TTL_MASK: u16 = 83;
-// const TCA_FLOWER_KEY_ENC_OPTS: u16 = 84;
-// const TCA_FLOWER_KEY_ENC_OPTS_MASK: u16 = 85;
+const TCA_FLOWER_KEY_ENC_OPTS: u16 = 84;
+const TCA_FLOWER_KEY_ENC_OPTS_MASK: u16 = 85;
 const TCA_FLOWER_IN_HW_COUNT: u16 = 86;
 const TCA_FLOWER_KEY_PORT_SRC_MIN: u16 = 87;
 const TCA_FLOWER_KEY_PORT_SRC_MAX: u16 = 88;
@@ -116,6 +119,16 @@ const TCA_FLOWER_KEY_CT_LABELS_MASK: u16 = 98;
 const TCA_FLOWER_KEY_MPLS_OPTS: u16 = 99;
 const TCA_FLOWER_KEY_HASH: u16 = 100;
 const TCA_FLOWER_KEY_HASH_MASK: u16 = 101;
+const TCA_FLOWER_KEY_NUM_OF_VLANS: u16 = 102;
+const TCA_FLOWER_KEY_PPPOE_SID: u16 = 103;
+const TCA_FLOWER_KEY_PPP_PROTO: u16 = 104;
+const TCA_FLOWER_KEY_L2TPV3_SID: u16 = 105;
+const TCA_FLOWER_L2_MISS: u16 = 106;
+const TCA_FLOWER_KEY_CFM: u16 = 107;
+const TCA_FLOWER_KEY_SPI: u16 = 108;
+const TCA_FLOWER_KEY_SPI_MASK: u16 = 109;
+const TCA_FLOWER_KEY_ENC_FLAGS: u16 = 110;
+const TCA_FLOWER_KEY_ENC_FLAGS_MASK: u16 = 111;
 
 fn parse_bytes_16(payload: &[u8]) -> Result<[u8; 16], DecodeError> {
     if payload.len() != 16 {
@@ -150,7 +163,10 @@ pub enum TcFilterFlowerOption {
     EthSrc([u8; 6]),
     EthSrcMask([u8; 6]),
     EthType(u16),
-    IpProto(u8),
+    /// Layer 4 protocol of IPv4 or IPv6 packets, kernel ignores it for
+    /// other [TcFilterFlowerOption::EthType]. Use
+    /// [TcFilterFlowerOption::ArpOp] to match ARP opcode.
+    IpProto(IpProtocol),
     IpTtl(u8),
     IpTtlMask(u8),
     IpTos(u8),
@@ -199,10 +215,10 @@ pub enum TcFilterFlowerOption {
     MplsLabel(u32),
     TcpFlags(u16),
     TcpFlagsMask(u16),
-    KeyFlags(u32),
-    KeyFlagsMask(u32),
+    KeyFlags(TcFilterFlowerKeyFlags),
+    KeyFlagsMask(TcFilterFlowerKeyFlags),
 
-    Flags(u32),
+    Flags(TcClsFlags),
     VlanId(u16),
     VlanPrio(u8),
     VlanEthType(u16),
@@ -227,13 +243,15 @@ pub enum TcFilterFlowerOption {
     EncKeyIpv6SrcMask(Ipv6Addr),
     EncKeyIpv6Dst(Ipv6Addr),
     EncKeyIpv6DstMask(Ipv6Addr),
+    EncOpts(Vec<TcFilterFlowerEncOption>),
+    EncOptsMask(Vec<TcFilterFlowerEncOption>),
     InHwCount(u32),
     PortSrcMin(u16),
     PortSrcMax(u16),
     PortDstMin(u16),
     PortDstMax(u16),
-    CtState(u16),
-    CtStateMask(u16),
+    CtState(TcFilterFlowerCtState),
+    CtStateMask(TcFilterFlowerCtState),
     CtZone(u16),
     CtZoneMask(u16),
     CtMark(u32),
@@ -243,6 +261,19 @@ pub enum TcFilterFlowerOption {
     MplsOpts(Vec<TcFilterFlowerMplsOption>),
     KeyHash(u32),
     KeyHashMask(u32),
+    EncFlags(TcFilterFlowerKeyFlags),
+    EncFlagsMask(TcFilterFlowerKeyFlags),
+    /// Number of VLAN tags
+    NumOfVlans(u8),
+    PppoeSid(u16),
+    PppProto(u16),
+    L2tpv3Sid(u32),
+    /// Match packets whose destination MAC address missed in bridge FDB
+    L2Miss(bool),
+    Cfm(Vec<TcFilterFlowerCfmOption>),
+    /// IPsec Security Parameter Index of ESP or AH
+    Spi(u32),
+    SpiMask(u32),
 
     Other(DefaultNla),
 }
@@ -346,6 +377,14 @@ impl Nla for TcFilterFlowerOption {
             Self::CtLabels(_) | Self::CtLabelsMask(_) => 16,
             Self::MplsOpts(attr) => attr.as_slice().buffer_len(),
             Self::KeyHash(_) | Self::KeyHashMask(_) => 4,
+            Self::EncOpts(attr) | Self::EncOptsMask(attr) => {
+                attr.as_slice().buffer_len()
+            }
+            Self::EncFlags(_) | Self::EncFlagsMask(_) => 4,
+            Self::NumOfVlans(_) | Self::L2Miss(_) => 1,
+            Self::PppoeSid(_) | Self::PppProto(_) => 2,
+            Self::L2tpv3Sid(_) | Self::Spi(_) | Self::SpiMask(_) => 4,
+            Self::Cfm(attr) => attr.as_slice().buffer_len(),
 
             Self::Other(attr) => attr.value_len(),
         }
@@ -361,7 +400,7 @@ impl Nla for TcFilterFlowerOption {
             | Self::EthSrc(b)
             | Self::EthSrcMask(b) => buffer.copy_from_slice(b.as_slice()),
             Self::EthType(i) => emit_u16_be(buffer, *i).unwrap(),
-            Self::IpProto(i) => buffer[0] = *i,
+            Self::IpProto(i) => buffer[0] = u8::from(*i),
             Self::IpTtl(i)
             | Self::IpTtlMask(i)
             | Self::IpTos(i)
@@ -410,10 +449,11 @@ impl Nla for TcFilterFlowerOption {
             Self::TcpFlags(i) | Self::TcpFlagsMask(i) => {
                 emit_u16_be(buffer, *i).unwrap()
             }
-            Self::KeyFlags(i) | Self::KeyFlagsMask(i) => {
-                emit_u32_be(buffer, *i).unwrap()
-            }
-            Self::Flags(i) => emit_u32(buffer, *i).unwrap(),
+            Self::KeyFlags(f)
+            | Self::KeyFlagsMask(f)
+            | Self::EncFlags(f)
+            | Self::EncFlagsMask(f) => emit_u32_be(buffer, f.bits()).unwrap(),
+            Self::Flags(f) => emit_u32(buffer, f.bits()).unwrap(),
             Self::VlanId(i) => emit_u16(buffer, *i).unwrap(),
             Self::VlanPrio(i) => buffer[0] = *i,
             Self::VlanEthType(i) => emit_u16_be(buffer, *i).unwrap(),
@@ -446,10 +486,12 @@ impl Nla for TcFilterFlowerOption {
             | Self::PortSrcMax(i)
             | Self::PortDstMin(i)
             | Self::PortDstMax(i) => emit_u16_be(buffer, *i).unwrap(),
-            Self::CtState(i)
-            | Self::CtStateMask(i)
-            | Self::CtZone(i)
-            | Self::CtZoneMask(i) => emit_u16(buffer, *i).unwrap(),
+            Self::CtState(f) | Self::CtStateMask(f) => {
+                emit_u16(buffer, f.bits()).unwrap()
+            }
+            Self::CtZone(i) | Self::CtZoneMask(i) => {
+                emit_u16(buffer, *i).unwrap()
+            }
             Self::CtMark(i) | Self::CtMarkMask(i) => {
                 emit_u32(buffer, *i).unwrap()
             }
@@ -460,6 +502,18 @@ impl Nla for TcFilterFlowerOption {
             Self::KeyHash(i) | Self::KeyHashMask(i) => {
                 emit_u32(buffer, *i).unwrap()
             }
+            Self::EncOpts(attr) | Self::EncOptsMask(attr) => {
+                attr.as_slice().emit(buffer)
+            }
+            Self::NumOfVlans(i) => buffer[0] = *i,
+            Self::L2Miss(v) => buffer[0] = (*v).into(),
+            Self::PppoeSid(i) | Self::PppProto(i) => {
+                emit_u16_be(buffer, *i).unwrap()
+            }
+            Self::L2tpv3Sid(i) | Self::Spi(i) | Self::SpiMask(i) => {
+                emit_u32_be(buffer, *i).unwrap()
+            }
+            Self::Cfm(attr) => attr.as_slice().emit(buffer),
             Self::Other(attr) => attr.emit_value(buffer),
         }
     }
@@ -569,6 +623,18 @@ impl Nla for TcFilterFlowerOption {
             Self::MplsOpts(_) => TCA_FLOWER_KEY_MPLS_OPTS | NLA_F_NESTED,
             Self::KeyHash(_) => TCA_FLOWER_KEY_HASH,
             Self::KeyHashMask(_) => TCA_FLOWER_KEY_HASH_MASK,
+            Self::EncOpts(_) => TCA_FLOWER_KEY_ENC_OPTS,
+            Self::EncOptsMask(_) => TCA_FLOWER_KEY_ENC_OPTS_MASK,
+            Self::EncFlags(_) => TCA_FLOWER_KEY_ENC_FLAGS,
+            Self::EncFlagsMask(_) => TCA_FLOWER_KEY_ENC_FLAGS_MASK,
+            Self::NumOfVlans(_) => TCA_FLOWER_KEY_NUM_OF_VLANS,
+            Self::PppoeSid(_) => TCA_FLOWER_KEY_PPPOE_SID,
+            Self::PppProto(_) => TCA_FLOWER_KEY_PPP_PROTO,
+            Self::L2tpv3Sid(_) => TCA_FLOWER_KEY_L2TPV3_SID,
+            Self::L2Miss(_) => TCA_FLOWER_L2_MISS,
+            Self::Cfm(_) => TCA_FLOWER_KEY_CFM | NLA_F_NESTED,
+            Self::Spi(_) => TCA_FLOWER_KEY_SPI,
+            Self::SpiMask(_) => TCA_FLOWER_KEY_SPI_MASK,
             Self::Other(attr) => attr.kind(),
         }
     }
@@ -615,9 +681,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 parse_u16_be(payload)
                     .context(nla_err!(TCA_FLOWER_KEY_ETH_TYPE))?,
             ),
-            TCA_FLOWER_KEY_IP_PROTO => Self::IpProto(
+            TCA_FLOWER_KEY_IP_PROTO => Self::IpProto(IpProtocol::from(
                 parse_u8(payload).context(nla_err!(TCA_FLOWER_KEY_IP_PROTO))?,
-            ),
+            )),
             TCA_FLOWER_KEY_IP_TTL => Self::IpTtl(
                 parse_u8(payload).context(nla_err!(TCA_FLOWER_KEY_IP_TTL))?,
             ),
@@ -713,9 +779,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                     .context(nla_err!(TCA_FLOWER_KEY_SCTP_DST_MASK))?,
             ),
 
-            TCA_FLOWER_FLAGS => Self::Flags(
+            TCA_FLOWER_FLAGS => Self::Flags(TcClsFlags::from_bits_retain(
                 parse_u32(payload).context(nla_err!(TCA_FLOWER_FLAGS))?,
-            ),
+            )),
 
             TCA_FLOWER_KEY_VLAN_ID => Self::VlanId(
                 parse_u16(payload).context(nla_err!(TCA_FLOWER_KEY_VLAN_ID))?,
@@ -850,14 +916,18 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 parse_u16_be(payload)
                     .context(nla_err!(TCA_FLOWER_KEY_TCP_FLAGS_MASK))?,
             ),
-            TCA_FLOWER_KEY_FLAGS => Self::KeyFlags(
-                parse_u32_be(payload)
-                    .context(nla_err!(TCA_FLOWER_KEY_FLAGS))?,
-            ),
-            TCA_FLOWER_KEY_FLAGS_MASK => Self::KeyFlagsMask(
-                parse_u32_be(payload)
-                    .context(nla_err!(TCA_FLOWER_KEY_FLAGS_MASK))?,
-            ),
+            TCA_FLOWER_KEY_FLAGS => {
+                Self::KeyFlags(TcFilterFlowerKeyFlags::from_bits_retain(
+                    parse_u32_be(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_FLAGS))?,
+                ))
+            }
+            TCA_FLOWER_KEY_FLAGS_MASK => {
+                Self::KeyFlagsMask(TcFilterFlowerKeyFlags::from_bits_retain(
+                    parse_u32_be(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_FLAGS_MASK))?,
+                ))
+            }
 
             TCA_FLOWER_KEY_ENC_IP_TTL => Self::EncKeyIpTtl(
                 parse_u8(payload)
@@ -926,14 +996,18 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 parse_u16_be(payload)
                     .context(nla_err!(TCA_FLOWER_KEY_PORT_DST_MAX))?,
             ),
-            TCA_FLOWER_KEY_CT_STATE => Self::CtState(
-                parse_u16(payload)
-                    .context(nla_err!(TCA_FLOWER_KEY_CT_STATE))?,
-            ),
-            TCA_FLOWER_KEY_CT_STATE_MASK => Self::CtStateMask(
-                parse_u16(payload)
-                    .context(nla_err!(TCA_FLOWER_KEY_CT_STATE_MASK))?,
-            ),
+            TCA_FLOWER_KEY_CT_STATE => {
+                Self::CtState(TcFilterFlowerCtState::from_bits_retain(
+                    parse_u16(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_CT_STATE))?,
+                ))
+            }
+            TCA_FLOWER_KEY_CT_STATE_MASK => {
+                Self::CtStateMask(TcFilterFlowerCtState::from_bits_retain(
+                    parse_u16(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_CT_STATE_MASK))?,
+                ))
+            }
             TCA_FLOWER_KEY_CT_ZONE => Self::CtZone(
                 parse_u16(payload).context(nla_err!(TCA_FLOWER_KEY_CT_ZONE))?,
             ),
@@ -975,6 +1049,79 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 parse_u32(payload)
                     .context(nla_err!(TCA_FLOWER_KEY_HASH_MASK))?,
             ),
+            TCA_FLOWER_KEY_ENC_OPTS => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid TCA_FLOWER_KEY_ENC_OPTS nla")?;
+                    nlas.push(
+                        TcFilterFlowerEncOption::parse(&nla)
+                            .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS))?,
+                    )
+                }
+                Self::EncOpts(nlas)
+            }
+            TCA_FLOWER_KEY_ENC_OPTS_MASK => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla
+                        .context("invalid TCA_FLOWER_KEY_ENC_OPTS_MASK nla")?;
+                    nlas.push(
+                        TcFilterFlowerEncOption::parse(&nla)
+                            .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_MASK))?,
+                    )
+                }
+                Self::EncOptsMask(nlas)
+            }
+            TCA_FLOWER_KEY_ENC_FLAGS => {
+                Self::EncFlags(TcFilterFlowerKeyFlags::from_bits_retain(
+                    parse_u32_be(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_ENC_FLAGS))?,
+                ))
+            }
+            TCA_FLOWER_KEY_ENC_FLAGS_MASK => {
+                Self::EncFlagsMask(TcFilterFlowerKeyFlags::from_bits_retain(
+                    parse_u32_be(payload)
+                        .context(nla_err!(TCA_FLOWER_KEY_ENC_FLAGS_MASK))?,
+                ))
+            }
+            TCA_FLOWER_KEY_NUM_OF_VLANS => Self::NumOfVlans(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_NUM_OF_VLANS))?,
+            ),
+            TCA_FLOWER_KEY_PPPOE_SID => Self::PppoeSid(
+                parse_u16_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_PPPOE_SID))?,
+            ),
+            TCA_FLOWER_KEY_PPP_PROTO => Self::PppProto(
+                parse_u16_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_PPP_PROTO))?,
+            ),
+            TCA_FLOWER_KEY_L2TPV3_SID => Self::L2tpv3Sid(
+                parse_u32_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_L2TPV3_SID))?,
+            ),
+            TCA_FLOWER_L2_MISS => Self::L2Miss(
+                parse_u8(payload).context(nla_err!(TCA_FLOWER_L2_MISS))? != 0,
+            ),
+            TCA_FLOWER_KEY_CFM => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_FLOWER_KEY_CFM nla")?;
+                    nlas.push(
+                        TcFilterFlowerCfmOption::parse(&nla)
+                            .context(nla_err!(TCA_FLOWER_KEY_CFM))?,
+                    )
+                }
+                Self::Cfm(nlas)
+            }
+            TCA_FLOWER_KEY_SPI => Self::Spi(
+                parse_u32_be(payload).context(nla_err!(TCA_FLOWER_KEY_SPI))?,
+            ),
+            TCA_FLOWER_KEY_SPI_MASK => Self::SpiMask(
+                parse_u32_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_SPI_MASK))?,
+            ),
 
             _ => Self::Other(
                 DefaultNla::parse(buf).context("failed to parse flower nla")?,
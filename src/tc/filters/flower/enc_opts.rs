@@ -0,0 +1,433 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{
+    emit_u16_be, emit_u32, emit_u32_be, emit_u64_be, parse_u16_be, parse_u32,
+    parse_u32_be, parse_u64_be, parse_u8, DecodeError, DefaultNla, Emitable,
+    ErrorContext, Nla, NlaBuffer, NlasIterator, Parseable,
+};
+
+macro_rules! nla_err {
+    // Match rule that takes an argument expression
+    ($message:expr) => {
+        &format!("failed to parse {} value", stringify!($message))
+    };
+}
+
+/*
+ * NLA layout:
+ * TCA_FLOWER_KEY_ENC_OPTS or TCA_FLOWER_KEY_ENC_OPTS_MASK
+ *   TCA_FLOWER_KEY_ENC_OPTS_GENEVE
+ *     TCA_FLOWER_KEY_ENC_OPT_GENEVE_*
+ *   ..
+ *   TCA_FLOWER_KEY_ENC_OPTS_GENEVE
+ *     TCA_FLOWER_KEY_ENC_OPT_GENEVE_*
+ *
+ * Kernel dumps each Geneve option TLV in its own
+ * TCA_FLOWER_KEY_ENC_OPTS_GENEVE while other tunnel types have at most one.
+ * Unlike TCA_FLOWER_KEY_MPLS_OPTS, kernel dumps these nests without the
+ * NLA_F_NESTED flag.
+ */
+
+const TCA_FLOWER_KEY_ENC_OPTS_GENEVE: u16 = 1;
+const TCA_FLOWER_KEY_ENC_OPTS_VXLAN: u16 = 2;
+const TCA_FLOWER_KEY_ENC_OPTS_ERSPAN: u16 = 3;
+const TCA_FLOWER_KEY_ENC_OPTS_GTP: u16 = 4;
+const TCA_FLOWER_KEY_ENC_OPTS_PFCP: u16 = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOption {
+    Geneve(Vec<TcFilterFlowerEncOptGeneve>),
+    Vxlan(Vec<TcFilterFlowerEncOptVxlan>),
+    Erspan(Vec<TcFilterFlowerEncOptErspan>),
+    Gtp(Vec<TcFilterFlowerEncOptGtp>),
+    Pfcp(Vec<TcFilterFlowerEncOptPfcp>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Geneve(attr) => attr.as_slice().buffer_len(),
+            Self::Vxlan(attr) => attr.as_slice().buffer_len(),
+            Self::Erspan(attr) => attr.as_slice().buffer_len(),
+            Self::Gtp(attr) => attr.as_slice().buffer_len(),
+            Self::Pfcp(attr) => attr.as_slice().buffer_len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Geneve(attr) => attr.as_slice().emit(buffer),
+            Self::Vxlan(attr) => attr.as_slice().emit(buffer),
+            Self::Erspan(attr) => attr.as_slice().emit(buffer),
+            Self::Gtp(attr) => attr.as_slice().emit(buffer),
+            Self::Pfcp(attr) => attr.as_slice().emit(buffer),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Geneve(_) => TCA_FLOWER_KEY_ENC_OPTS_GENEVE,
+            Self::Vxlan(_) => TCA_FLOWER_KEY_ENC_OPTS_VXLAN,
+            Self::Erspan(_) => TCA_FLOWER_KEY_ENC_OPTS_ERSPAN,
+            Self::Gtp(_) => TCA_FLOWER_KEY_ENC_OPTS_GTP,
+            Self::Pfcp(_) => TCA_FLOWER_KEY_ENC_OPTS_PFCP,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+fn parse_nested<T>(payload: &[u8]) -> Result<Vec<T>, DecodeError>
+where
+    T: for<'b> Parseable<NlaBuffer<&'b [u8]>>,
+{
+    let mut nlas = vec![];
+    for nla in NlasIterator::new(payload) {
+        nlas.push(T::parse(&nla?)?);
+    }
+    Ok(nlas)
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPTS_GENEVE => Self::Geneve(
+                parse_nested(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_GENEVE))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPTS_VXLAN => Self::Vxlan(
+                parse_nested(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_VXLAN))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPTS_ERSPAN => Self::Erspan(
+                parse_nested(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_ERSPAN))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPTS_GTP => Self::Gtp(
+                parse_nested(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_GTP))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPTS_PFCP => Self::Pfcp(
+                parse_nested(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPTS_PFCP))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf)
+                    .context("failed to parse enc option nla")?,
+            ),
+        })
+    }
+}
+
+const TCA_FLOWER_KEY_ENC_OPT_GENEVE_CLASS: u16 = 1;
+const TCA_FLOWER_KEY_ENC_OPT_GENEVE_TYPE: u16 = 2;
+const TCA_FLOWER_KEY_ENC_OPT_GENEVE_DATA: u16 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOptGeneve {
+    Class(u16),
+    Type(u8),
+    /// Option data, length must be a multiple of 4 bytes
+    Data(Vec<u8>),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOptGeneve {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Class(_) => 2,
+            Self::Type(_) => 1,
+            Self::Data(b) => b.len(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Class(i) => emit_u16_be(buffer, *i).unwrap(),
+            Self::Type(i) => buffer[0] = *i,
+            Self::Data(b) => buffer.copy_from_slice(b.as_slice()),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Class(_) => TCA_FLOWER_KEY_ENC_OPT_GENEVE_CLASS,
+            Self::Type(_) => TCA_FLOWER_KEY_ENC_OPT_GENEVE_TYPE,
+            Self::Data(_) => TCA_FLOWER_KEY_ENC_OPT_GENEVE_DATA,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOptGeneve
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPT_GENEVE_CLASS => Self::Class(
+                parse_u16_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_GENEVE_CLASS))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_GENEVE_TYPE => Self::Type(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_GENEVE_TYPE))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_GENEVE_DATA => Self::Data(payload.to_vec()),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse geneve nla")?,
+            ),
+        })
+    }
+}
+
+const TCA_FLOWER_KEY_ENC_OPT_VXLAN_GBP: u16 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOptVxlan {
+    /// Group Based Policy
+    Gbp(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOptVxlan {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Gbp(_) => 4,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Gbp(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Gbp(_) => TCA_FLOWER_KEY_ENC_OPT_VXLAN_GBP,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOptVxlan
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPT_VXLAN_GBP => Self::Gbp(
+                parse_u32(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_VXLAN_GBP))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse vxlan nla")?,
+            ),
+        })
+    }
+}
+
+const TCA_FLOWER_KEY_ENC_OPT_ERSPAN_VER: u16 = 1;
+const TCA_FLOWER_KEY_ENC_OPT_ERSPAN_INDEX: u16 = 2;
+const TCA_FLOWER_KEY_ENC_OPT_ERSPAN_DIR: u16 = 3;
+const TCA_FLOWER_KEY_ENC_OPT_ERSPAN_HWID: u16 = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOptErspan {
+    Ver(u8),
+    /// Session index, ERSPAN version 1 only
+    Index(u32),
+    /// Mirrored traffic direction, ERSPAN version 2 only
+    Dir(u8),
+    /// Hardware identifier, ERSPAN version 2 only
+    Hwid(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOptErspan {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Ver(_) | Self::Dir(_) | Self::Hwid(_) => 1,
+            Self::Index(_) => 4,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Ver(i) | Self::Dir(i) | Self::Hwid(i) => buffer[0] = *i,
+            Self::Index(i) => emit_u32_be(buffer, *i).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Ver(_) => TCA_FLOWER_KEY_ENC_OPT_ERSPAN_VER,
+            Self::Index(_) => TCA_FLOWER_KEY_ENC_OPT_ERSPAN_INDEX,
+            Self::Dir(_) => TCA_FLOWER_KEY_ENC_OPT_ERSPAN_DIR,
+            Self::Hwid(_) => TCA_FLOWER_KEY_ENC_OPT_ERSPAN_HWID,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOptErspan
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPT_ERSPAN_VER => Self::Ver(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_ERSPAN_VER))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_ERSPAN_INDEX => Self::Index(
+                parse_u32_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_ERSPAN_INDEX))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_ERSPAN_DIR => Self::Dir(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_ERSPAN_DIR))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_ERSPAN_HWID => Self::Hwid(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_ERSPAN_HWID))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse erspan nla")?,
+            ),
+        })
+    }
+}
+
+const TCA_FLOWER_KEY_ENC_OPT_GTP_PDU_TYPE: u16 = 1;
+const TCA_FLOWER_KEY_ENC_OPT_GTP_QFI: u16 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOptGtp {
+    PduType(u8),
+    /// QoS Flow Identifier
+    Qfi(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOptGtp {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::PduType(_) | Self::Qfi(_) => 1,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::PduType(i) | Self::Qfi(i) => buffer[0] = *i,
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::PduType(_) => TCA_FLOWER_KEY_ENC_OPT_GTP_PDU_TYPE,
+            Self::Qfi(_) => TCA_FLOWER_KEY_ENC_OPT_GTP_QFI,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOptGtp
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPT_GTP_PDU_TYPE => Self::PduType(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_GTP_PDU_TYPE))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_GTP_QFI => Self::Qfi(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_GTP_QFI))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse gtp nla")?,
+            ),
+        })
+    }
+}
+
+const TCA_FLOWER_KEY_ENC_OPT_PFCP_TYPE: u16 = 1;
+const TCA_FLOWER_KEY_ENC_OPT_PFCP_SEID: u16 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcFilterFlowerEncOptPfcp {
+    /// PFCP message type
+    Type(u8),
+    /// Session Endpoint Identifier
+    Seid(u64),
+    Other(DefaultNla),
+}
+
+impl Nla for TcFilterFlowerEncOptPfcp {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Type(_) => 1,
+            Self::Seid(_) => 8,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Type(i) => buffer[0] = *i,
+            Self::Seid(i) => emit_u64_be(buffer, *i).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Type(_) => TCA_FLOWER_KEY_ENC_OPT_PFCP_TYPE,
+            Self::Seid(_) => TCA_FLOWER_KEY_ENC_OPT_PFCP_SEID,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcFilterFlowerEncOptPfcp
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_ENC_OPT_PFCP_TYPE => Self::Type(
+                parse_u8(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_PFCP_TYPE))?,
+            ),
+            TCA_FLOWER_KEY_ENC_OPT_PFCP_SEID => Self::Seid(
+                parse_u64_be(payload)
+                    .context(nla_err!(TCA_FLOWER_KEY_ENC_OPT_PFCP_SEID))?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse pfcp nla")?,
+            ),
+        })
+    }
+}
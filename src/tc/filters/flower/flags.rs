@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+
+const TCA_FLOWER_KEY_FLAGS_IS_FRAGMENT: u32 = 1 << 0;
+const TCA_FLOWER_KEY_FLAGS_FRAG_IS_FIRST: u32 = 1 << 1;
+const TCA_FLOWER_KEY_FLAGS_TUNNEL_CSUM: u32 = 1 << 2;
+const TCA_FLOWER_KEY_FLAGS_TUNNEL_DONT_FRAGMENT: u32 = 1 << 3;
+const TCA_FLOWER_KEY_FLAGS_TUNNEL_OAM: u32 = 1 << 4;
+const TCA_FLOWER_KEY_FLAGS_TUNNEL_CRIT_OPT: u32 = 1 << 5;
+
+bitflags! {
+    /// Flags of `TCA_FLOWER_KEY_FLAGS` and `TCA_FLOWER_KEY_ENC_FLAGS`.
+    /// The `Tunnel*` flags are only valid for the latter.
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcFilterFlowerKeyFlags: u32 {
+        const IsFragment = TCA_FLOWER_KEY_FLAGS_IS_FRAGMENT;
+        const FragIsFirst = TCA_FLOWER_KEY_FLAGS_FRAG_IS_FIRST;
+        const TunnelCsum = TCA_FLOWER_KEY_FLAGS_TUNNEL_CSUM;
+        const TunnelDontFragment = TCA_FLOWER_KEY_FLAGS_TUNNEL_DONT_FRAGMENT;
+        const TunnelOam = TCA_FLOWER_KEY_FLAGS_TUNNEL_OAM;
+        const TunnelCritOpt = TCA_FLOWER_KEY_FLAGS_TUNNEL_CRIT_OPT;
+        const _ = !0;
+    }
+}
+
+const TCA_FLOWER_KEY_CT_FLAGS_NEW: u16 = 1 << 0;
+const TCA_FLOWER_KEY_CT_FLAGS_ESTABLISHED: u16 = 1 << 1;
+const TCA_FLOWER_KEY_CT_FLAGS_RELATED: u16 = 1 << 2;
+const TCA_FLOWER_KEY_CT_FLAGS_TRACKED: u16 = 1 << 3;
+const TCA_FLOWER_KEY_CT_FLAGS_INVALID: u16 = 1 << 4;
+const TCA_FLOWER_KEY_CT_FLAGS_REPLY: u16 = 1 << 5;
+
+bitflags! {
+    /// Conntrack state, e.g. `ct_state +trk+est` of tc-flower(8) is
+    /// `Tracked | Established` in both `CtState` and `CtStateMask`.
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct TcFilterFlowerCtState: u16 {
+        const New = TCA_FLOWER_KEY_CT_FLAGS_NEW;
+        const Established = TCA_FLOWER_KEY_CT_FLAGS_ESTABLISHED;
+        const Related = TCA_FLOWER_KEY_CT_FLAGS_RELATED;
+        const Tracked = TCA_FLOWER_KEY_CT_FLAGS_TRACKED;
+        const Invalid = TCA_FLOWER_KEY_CT_FLAGS_INVALID;
+        const Reply = TCA_FLOWER_KEY_CT_FLAGS_REPLY;
+        const _ = !0;
+    }
+}
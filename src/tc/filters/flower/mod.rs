@@ -1,9 +1,19 @@
 // SPDX-License-Identifier: MIT
 
+mod cfm;
 mod core;
+mod enc_opts;
+mod flags;
 mod mpls;
 
 pub use self::{
+    cfm::TcFilterFlowerCfmOption,
     core::{TcFilterFlower, TcFilterFlowerOption},
+    enc_opts::{
+        TcFilterFlowerEncOptErspan, TcFilterFlowerEncOptGeneve,
+        TcFilterFlowerEncOptGtp, TcFilterFlowerEncOptPfcp,
+        TcFilterFlowerEncOptVxlan, TcFilterFlowerEncOption,
+    },
+    flags::{TcFilterFlowerCtState, TcFilterFlowerKeyFlags},
     mpls::{TcFilterFlowerMplsLseOption, TcFilterFlowerMplsOption},
 };
@@ -9,7 +9,7 @@ use netlink_packet_core::{
     NlasIterator, Parseable,
 };
 
-use crate::tc::{TcAction, TcHandle};
+use crate::tc::{TcAction, TcClsFlags, TcHandle};
 
 const TCA_MATCHALL_CLASSID: u16 = 1;
 const TCA_MATCHALL_ACT: u16 = 2;
@@ -29,7 +29,7 @@ pub enum TcFilterMatchAllOption {
     ClassId(TcHandle),
     Action(Vec<TcAction>),
     Pnct(Vec<u8>),
-    Flags(u32),
+    Flags(TcClsFlags),
     Other(DefaultNla),
 }
 
@@ -48,7 +48,7 @@ impl Nla for TcFilterMatchAllOption {
         match self {
             Self::Pnct(b) => buffer.copy_from_slice(b.as_slice()),
             Self::ClassId(i) => emit_u32(buffer, (*i).into()).unwrap(),
-            Self::Flags(i) => emit_u32(buffer, *i).unwrap(),
+            Self::Flags(f) => emit_u32(buffer, f.bits()).unwrap(),
             Self::Action(acts) => acts.as_slice().emit(buffer),
             Self::Other(attr) => attr.emit_value(buffer),
         }
@@ -88,10 +88,10 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
                 Self::Action(acts)
             }
             TCA_MATCHALL_PCNT => Self::Pnct(payload.to_vec()),
-            TCA_MATCHALL_FLAGS => Self::Flags(
+            TCA_MATCHALL_FLAGS => Self::Flags(TcClsFlags::from_bits_retain(
                 parse_u32(payload)
                     .context("failed to parse TCA_MATCHALL_FLAGS")?,
-            ),
+            )),
             _ => Self::Other(
                 DefaultNla::parse(buf)
                     .context("failed to parse matchall nla")?,
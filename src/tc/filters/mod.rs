@@ -1,22 +1,52 @@
 // SPDX-License-Identifier: MIT
 
+mod basic;
 mod bpf;
+mod cgroup;
+mod cls_flags;
 mod cls_u32;
+mod ematch;
+mod flow;
 mod flower;
+mod fw;
 mod matchall;
+mod route;
 mod u32_flags;
+mod u32_hash;
 
+pub use cls_flags::TcClsFlags;
 pub use u32_flags::{TcU32OptionFlags, TcU32SelectorFlags};
+pub use u32_hash::{TcU32Handle, TcU32HashTable, TcU32Node};
 
 pub use self::{
+    basic::{
+        TcBasicPcnt, TcBasicPcntBuffer, TcFilterBasic, TcFilterBasicOption,
+    },
     bpf::{TcBpfFlags, TcFilterBpf, TcFilterBpfOption},
+    cgroup::{TcFilterCgroup, TcFilterCgroupOption},
     cls_u32::{
-        TcFilterU32, TcFilterU32Option, TcU32Key, TcU32Selector,
-        TcU32SelectorBuffer,
+        TcFilterU32, TcFilterU32Option, TcU32Key, TcU32Mark, TcU32MarkBuffer,
+        TcU32Pcnt, TcU32PcntBuffer, TcU32Selector, TcU32SelectorBuffer,
+    },
+    ematch::{
+        TcEmatch, TcEmatchCanFilter, TcEmatchCanFilterBuffer, TcEmatchCmp,
+        TcEmatchCmpAlign, TcEmatchCmpBuffer, TcEmatchHdrBuffer, TcEmatchIpset,
+        TcEmatchIpsetBuffer, TcEmatchLayer, TcEmatchMatcher, TcEmatchMetaHdr,
+        TcEmatchMetaHdrBuffer, TcEmatchMetaId, TcEmatchMetaOption,
+        TcEmatchMetaType, TcEmatchMetaValue, TcEmatchNbyte,
+        TcEmatchNbyteBuffer, TcEmatchOperand, TcEmatchRelation, TcEmatchTree,
+        TcEmatchTreeHdr, TcEmatchTreeHdrBuffer,
     },
+    flow::{TcFilterFlow, TcFilterFlowOption, TcFlowKeys, TcFlowMode},
     flower::{
-        TcFilterFlower, TcFilterFlowerMplsLseOption, TcFilterFlowerMplsOption,
-        TcFilterFlowerOption,
+        TcFilterFlower, TcFilterFlowerCfmOption, TcFilterFlowerCtState,
+        TcFilterFlowerEncOptErspan, TcFilterFlowerEncOptGeneve,
+        TcFilterFlowerEncOptGtp, TcFilterFlowerEncOptPfcp,
+        TcFilterFlowerEncOptVxlan, TcFilterFlowerEncOption,
+        TcFilterFlowerKeyFlags, TcFilterFlowerMplsLseOption,
+        TcFilterFlowerMplsOption, TcFilterFlowerOption,
     },
+    fw::{TcFilterFw, TcFilterFwOption},
     matchall::{TcFilterMatchAll, TcFilterMatchAllOption},
+    route::{TcFilterRoute, TcFilterRouteOption},
 };
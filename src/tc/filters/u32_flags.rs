@@ -17,21 +17,7 @@ bitflags! {
     }
 }
 
-const TCA_CLS_FLAGS_SKIP_HW: u32 = 1 << 0;
-const TCA_CLS_FLAGS_SKIP_SW: u32 = 1 << 1;
-const TCA_CLS_FLAGS_IN_HW: u32 = 1 << 2;
-const TCA_CLS_FLAGS_NOT_IN_HW: u32 = 1 << 3;
-const TCA_CLS_FLAGS_VERBOSE: u32 = 1 << 4;
-
-bitflags! {
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    #[non_exhaustive]
-    pub struct TcU32OptionFlags: u32 {
-        const SkipHw = TCA_CLS_FLAGS_SKIP_HW;
-        const SkipSw = TCA_CLS_FLAGS_SKIP_SW;
-        const InHw = TCA_CLS_FLAGS_IN_HW;
-        const NotInHw = TCA_CLS_FLAGS_NOT_IN_HW;
-        const Verbose = TCA_CLS_FLAGS_VERBOSE;
-        const _ = !0;
-    }
-}
+/// Alias kept for the u32 and bpf options, see [`TcClsFlags`].
+///
+/// [`TcClsFlags`]: super::TcClsFlags
+pub type TcU32OptionFlags = super::TcClsFlags;
@@ -0,0 +1,363 @@
+// SPDX-License-Identifier: MIT
+
+/// U32 hash tables
+///
+/// A u32 filter handle is made of a hash table id (12 bits), a bucket
+/// (8 bits) and a node id (12 bits), displayed as `htid:hash:node` by
+/// tc(8). Each priority has a root hash table created by the kernel; other
+/// tables are created with a divisor (number of buckets) and reached from
+/// a filter of their parent table through a link, the bucket being
+/// computed from the packet with the hash key of that filter's selector.
+use std::fmt::{self, Display};
+
+use netlink_packet_core::DecodeError;
+
+use super::{TcFilterU32, TcFilterU32Option, TcU32Selector};
+use crate::tc::{TcAttribute, TcHandle, TcHeader, TcMessage, TcOption};
+
+const TC_U32_HTID_SHIFT: u32 = 20;
+const TC_U32_HASH_SHIFT: u32 = 12;
+const TC_U32_HTID_MASK: u32 = 0xFFF;
+const TC_U32_HASH_MASK: u32 = 0xFF;
+const TC_U32_NODE_MASK: u32 = 0xFFF;
+const TC_U32_ROOT: u16 = 0xFFF;
+const TC_U32_MAXDEPTH: usize = 8;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct TcU32Handle {
+    /// Hash table id, 12 bits
+    pub htid: u16,
+    /// Bucket in the hash table
+    pub hash: u8,
+    /// Node id, 12 bits
+    pub node: u16,
+}
+
+impl TcU32Handle {
+    /// Hash table id referring to the root hash table of the priority,
+    /// whatever its actual id.
+    pub const ROOT_HTID: u16 = TC_U32_ROOT;
+
+    pub fn new(htid: u16, hash: u8, node: u16) -> Self {
+        Self {
+            htid: htid & TC_U32_HTID_MASK as u16,
+            hash,
+            node: node & TC_U32_NODE_MASK as u16,
+        }
+    }
+
+    /// Handle of the hash table `htid`, as used to create it and to link
+    /// to it.
+    pub fn table(htid: u16) -> Self {
+        Self::new(htid, 0, 0)
+    }
+
+    /// Return the handle of the hash table holding this node.
+    pub fn to_table(self) -> Self {
+        Self::table(self.htid)
+    }
+
+    /// Return the handle of the bucket holding this node.
+    pub fn to_bucket(self) -> Self {
+        Self::new(self.htid, self.hash, 0)
+    }
+
+    /// Whether this handle is a hash table rather than a filter.
+    pub fn is_table(&self) -> bool {
+        self.hash == 0 && self.node == 0
+    }
+}
+
+impl From<u32> for TcU32Handle {
+    fn from(d: u32) -> Self {
+        Self {
+            htid: ((d >> TC_U32_HTID_SHIFT) & TC_U32_HTID_MASK) as u16,
+            hash: ((d >> TC_U32_HASH_SHIFT) & TC_U32_HASH_MASK) as u8,
+            node: (d & TC_U32_NODE_MASK) as u16,
+        }
+    }
+}
+
+impl From<TcU32Handle> for u32 {
+    fn from(v: TcU32Handle) -> u32 {
+        ((v.htid as u32) << TC_U32_HTID_SHIFT)
+            | ((v.hash as u32) << TC_U32_HASH_SHIFT)
+            | v.node as u32
+    }
+}
+
+impl From<TcHandle> for TcU32Handle {
+    fn from(v: TcHandle) -> Self {
+        Self::from(u32::from(v))
+    }
+}
+
+impl From<TcU32Handle> for TcHandle {
+    fn from(v: TcU32Handle) -> Self {
+        Self::from(u32::from(v))
+    }
+}
+
+impl Display for TcU32Handle {
+    // Same format as iproute2, e.g. `800::800` or `1:2:`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if u32::from(*self) == 0 {
+            return write!(f, "none");
+        }
+        if self.htid != 0 {
+            write!(f, "{:x}:", self.htid)?;
+        }
+        if self.hash != 0 {
+            write!(f, "{:x}", self.hash)?;
+        }
+        if self.node != 0 {
+            write!(f, ":{:x}", self.node)?;
+        }
+        Ok(())
+    }
+}
+
+/// A u32 hash table and its filters
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcU32HashTable {
+    /// Hash table id, [`TcU32Handle::ROOT_HTID`] for the root table
+    pub htid: u16,
+    /// Number of buckets, from 1 to 256
+    pub divisor: u32,
+    pub nodes: Vec<TcU32Node>,
+}
+
+/// A u32 filter in a hash table bucket
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct TcU32Node {
+    /// Bucket of the hash table holding this filter
+    pub hash: u8,
+    /// Node id, 0 to let the kernel allocate one
+    pub node: u16,
+    pub selector: TcU32Selector,
+    /// Class of the packets matched by this filter
+    pub class_id: Option<TcHandle>,
+    /// Hash table looked up when this filter matches. The bucket is
+    /// computed from the hash key of the selector.
+    pub link: Option<TcU32HashTable>,
+    /// Other options of the filter, e.g. actions or flags
+    pub options: Vec<TcFilterU32Option>,
+}
+
+impl TcU32HashTable {
+    /// The root hash table of the priority, which the kernel creates
+    /// along with the first u32 filter.
+    pub fn root(nodes: Vec<TcU32Node>) -> Self {
+        Self::new(TcU32Handle::ROOT_HTID, 1, nodes)
+    }
+
+    pub fn new(htid: u16, divisor: u32, nodes: Vec<TcU32Node>) -> Self {
+        Self {
+            htid,
+            divisor,
+            nodes,
+        }
+    }
+
+    /// Build the RTM_NEWTFILTER messages creating this tree of hash
+    /// tables and filters, in an order the kernel accepts: a hash table
+    /// is created before its filters, and a linked hash table and its
+    /// filters before the filter linking to it.
+    ///
+    /// `self` is considered to be the root hash table of the priority, so
+    /// no message is emitted to create it. The `header` should hold the
+    /// interface index, the parent qdisc and the priority and protocol in
+    /// `info`.
+    pub fn to_messages(&self, header: &TcHeader) -> Vec<TcMessage> {
+        let mut msgs = Vec::new();
+        self.emit_nodes(header, &mut msgs);
+        msgs
+    }
+
+    fn emit_table(&self, header: &TcHeader, msgs: &mut Vec<TcMessage>) {
+        msgs.push(u32_message(
+            header,
+            TcU32Handle::table(self.htid),
+            vec![TcFilterU32Option::Divisor(self.divisor)],
+        ));
+        self.emit_nodes(header, msgs);
+    }
+
+    fn emit_nodes(&self, header: &TcHeader, msgs: &mut Vec<TcMessage>) {
+        for node in &self.nodes {
+            let mut options = vec![TcFilterU32Option::Hash(TcU32Handle::new(
+                self.htid, node.hash, 0,
+            ))];
+            if let Some(link) = &node.link {
+                link.emit_table(header, msgs);
+                options.push(TcFilterU32Option::Link(TcU32Handle::table(
+                    link.htid,
+                )));
+            }
+            if let Some(class_id) = node.class_id {
+                options.push(TcFilterU32Option::ClassId(class_id));
+            }
+            options.push(TcFilterU32Option::Selector(node.selector.clone()));
+            options.extend(node.options.iter().cloned());
+            // The kernel substitutes the real root hash table id to
+            // TcU32Handle::ROOT_HTID in TCA_U32_HASH only, a handle holding
+            // it would not match.
+            let handle = match node.node {
+                0 => TcU32Handle::default(),
+                n if self.htid == TcU32Handle::ROOT_HTID => {
+                    TcU32Handle::new(0, 0, n)
+                }
+                n => TcU32Handle::new(self.htid, node.hash, n),
+            };
+            msgs.push(u32_message(header, handle, options));
+        }
+    }
+
+    /// Rebuild the hash table trees from the RTM_NEWTFILTER messages of a
+    /// u32 filter dump of a single priority. Messages of other classifiers
+    /// are ignored.
+    ///
+    /// Return the hash tables not linked from any filter, in dump order:
+    /// the root hash table of the priority and the tables not linked yet.
+    pub fn from_messages(msgs: &[TcMessage]) -> Result<Vec<Self>, DecodeError> {
+        let mut tables: Vec<(u16, u32)> = Vec::new();
+        let mut nodes: Vec<(TcU32Handle, Vec<TcFilterU32Option>)> = Vec::new();
+        for msg in msgs {
+            let mut is_u32 = false;
+            let mut options = Vec::new();
+            for attr in &msg.attributes {
+                match attr {
+                    TcAttribute::Kind(v) => is_u32 = v == TcFilterU32::KIND,
+                    TcAttribute::Options(v) => {
+                        options.extend(v.iter().filter_map(|o| match o {
+                            TcOption::U32(o) => Some(o.clone()),
+                            _ => None,
+                        }))
+                    }
+                    _ => (),
+                }
+            }
+            if !is_u32 {
+                continue;
+            }
+            let handle = TcU32Handle::from(msg.header.handle);
+            if handle.is_table() {
+                let divisor = options
+                    .iter()
+                    .find_map(|o| match o {
+                        TcFilterU32Option::Divisor(d) => Some(*d),
+                        _ => None,
+                    })
+                    .unwrap_or(1);
+                tables.push((handle.htid, divisor));
+            } else {
+                nodes.push((handle, options));
+            }
+        }
+
+        let linked: Vec<u16> = nodes
+            .iter()
+            .flat_map(|(_, options)| options.iter())
+            .filter_map(|o| match o {
+                TcFilterU32Option::Link(h) => Some(h.htid),
+                _ => None,
+            })
+            .collect();
+        tables
+            .iter()
+            .filter(|(htid, _)| !linked.contains(htid))
+            .map(|(htid, _)| build_table(*htid, &tables, &nodes, 0))
+            .collect()
+    }
+}
+
+impl TcU32Node {
+    pub fn new(selector: TcU32Selector) -> Self {
+        Self {
+            selector,
+            ..Default::default()
+        }
+    }
+
+    /// Compute the bucket of the linked hash table a packet lands in,
+    /// given the 32 bit word at the hash key offset of the selector.
+    ///
+    /// Like the kernel, the masked word is shifted right by the number of
+    /// trailing zeros of the mask, then folded into the divisor.
+    pub fn bucket(&self, word: u32) -> u8 {
+        let divisor = self.link.as_ref().map_or(1, |l| l.divisor);
+        let hmask = u32::from_be(self.selector.hmask);
+        let fshift = if hmask == 0 {
+            0
+        } else {
+            hmask.trailing_zeros()
+        };
+        (((word & hmask) >> fshift) & divisor.saturating_sub(1)) as u8
+    }
+}
+
+fn u32_message(
+    header: &TcHeader,
+    handle: TcU32Handle,
+    options: Vec<TcFilterU32Option>,
+) -> TcMessage {
+    let mut header = header.clone();
+    header.handle = handle.into();
+    TcMessage::from_parts(
+        header,
+        vec![
+            TcAttribute::Kind(TcFilterU32::KIND.to_string()),
+            TcAttribute::Options(
+                options.into_iter().map(TcOption::U32).collect(),
+            ),
+        ],
+    )
+}
+
+fn build_table(
+    htid: u16,
+    tables: &[(u16, u32)],
+    nodes: &[(TcU32Handle, Vec<TcFilterU32Option>)],
+    depth: usize,
+) -> Result<TcU32HashTable, DecodeError> {
+    if depth > TC_U32_MAXDEPTH {
+        return Err(DecodeError::from(format!(
+            "u32 hash table {:x}: linked deeper than {TC_U32_MAXDEPTH} levels",
+            htid
+        )));
+    }
+    let divisor = tables
+        .iter()
+        .find(|(id, _)| *id == htid)
+        .map(|(_, divisor)| *divisor)
+        .ok_or_else(|| {
+            DecodeError::from(format!(
+                "u32 hash table {htid:x}: linked but not dumped"
+            ))
+        })?;
+    let mut table = TcU32HashTable::new(htid, divisor, Vec::new());
+    for (handle, options) in nodes.iter().filter(|(h, _)| h.htid == htid) {
+        let mut node = TcU32Node {
+            hash: handle.hash,
+            node: handle.node,
+            ..Default::default()
+        };
+        for option in options {
+            match option {
+                TcFilterU32Option::Selector(v) => node.selector = v.clone(),
+                TcFilterU32Option::ClassId(v) => node.class_id = Some(*v),
+                TcFilterU32Option::Link(v) => {
+                    node.link =
+                        Some(build_table(v.htid, tables, nodes, depth + 1)?)
+                }
+                TcFilterU32Option::Hash(_) => (),
+                _ => node.options.push(option.clone()),
+            }
+        }
+        table.nodes.push(node);
+    }
+    Ok(table)
+}
@@ -3,7 +3,7 @@
 use netlink_packet_core::{DecodeError, Emitable};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 
-use crate::AddressFamily;
+use crate::{AddressFamily, EthernetProtocol};
 
 pub(crate) const TC_HEADER_LEN: usize = 20;
 
@@ -44,6 +44,43 @@ pub struct TcHeader {
 impl TcHeader {
     pub const TCM_IFINDEX_MAGIC_BLOCK: u32 = 0xFFFFFFFF;
 
+    /// Header of a filter attached to the ingress hook of the clsact
+    /// qdisc of interface `index`, like `tc filter add dev X ingress`.
+    pub fn clsact_ingress(index: i32) -> Self {
+        Self {
+            index,
+            parent: TcHandle::CLSACT_INGRESS,
+            ..Default::default()
+        }
+    }
+
+    /// Header of a filter attached to the egress hook of the clsact
+    /// qdisc of interface `index`, like `tc filter add dev X egress`.
+    pub fn clsact_egress(index: i32) -> Self {
+        Self {
+            index,
+            parent: TcHandle::CLSACT_EGRESS,
+            ..Default::default()
+        }
+    }
+
+    /// Priority and protocol of a filter, stored in `info`.
+    pub fn filter_info(&self) -> TcFilterInfo {
+        TcFilterInfo::from(self.info)
+    }
+
+    pub fn set_filter_info(&mut self, info: TcFilterInfo) {
+        self.info = info.into();
+    }
+
+    /// Set the priority and protocol of a filter. For RTM_GETTFILTER
+    /// dumps, the kernel only returns the filters matching the non-zero
+    /// priority and protocol.
+    pub fn with_filter_info(mut self, info: TcFilterInfo) -> Self {
+        self.set_filter_info(info);
+        self
+    }
+
     pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
         let (raw, _) =
             TcMessageBuffer::ref_from_prefix(payload).map_err(|_| {
@@ -84,6 +121,51 @@ impl Emitable for TcHeader {
     }
 }
 
+/// Filter priority and protocol
+///
+/// For filters, `TcHeader::info` holds the priority in the upper 16 bits
+/// and the ethertype, in network byte order, in the lower 16 bits.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcFilterInfo {
+    pub priority: u16,
+    pub protocol: EthernetProtocol,
+}
+
+impl TcFilterInfo {
+    pub fn new(priority: u16, protocol: EthernetProtocol) -> Self {
+        Self { priority, protocol }
+    }
+}
+
+impl Default for TcFilterInfo {
+    fn default() -> Self {
+        Self::new(0, EthernetProtocol::Other(0))
+    }
+}
+
+impl From<u32> for TcFilterInfo {
+    fn from(d: u32) -> Self {
+        Self {
+            priority: (d >> 16) as u16,
+            protocol: EthernetProtocol::from(u16::from_be(d as u16)),
+        }
+    }
+}
+
+impl From<TcFilterInfo> for u32 {
+    fn from(v: TcFilterInfo) -> u32 {
+        ((v.priority as u32) << 16) | u16::from(v.protocol).to_be() as u32
+    }
+}
+
+// Same format as `tc filter show`, e.g. `prio 49152 protocol ip`
+impl std::fmt::Display for TcFilterInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "prio {} protocol {}", self.priority, self.protocol)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub struct TcHandle {
     pub major: u16,
@@ -106,6 +188,31 @@ impl TcHandle {
     pub const MIN_PRIORITY: u16 = 0xFFE0;
     pub const MIN_INGRESS: u16 = 0xFFF2;
     pub const MIN_EGRESS: u16 = 0xFFF3;
+
+    /// Parent of the filters on the ingress hook of a clsact qdisc
+    pub const CLSACT_INGRESS: Self = Self {
+        major: Self::CLSACT.major,
+        minor: Self::MIN_INGRESS,
+    };
+    /// Parent of the filters on the egress hook of a clsact qdisc
+    pub const CLSACT_EGRESS: Self = Self {
+        major: Self::CLSACT.major,
+        minor: Self::MIN_EGRESS,
+    };
+
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+
+    /// Handle `major:`, as used for qdiscs.
+    pub const fn from_major(major: u16) -> Self {
+        Self::new(major, 0)
+    }
+
+    /// Handle `:minor`, as used for classes of the parent qdisc.
+    pub const fn from_minor(minor: u16) -> Self {
+        Self::new(0, minor)
+    }
 }
 
 impl From<u32> for TcHandle {
@@ -131,8 +238,53 @@ impl From<TcHandle> for u32 {
     }
 }
 
+// Same format as `tc(8)`: `root`, `none`, `1:`, `:10` or `1:10` in
+// hexadecimal.
 impl std::fmt::Display for TcHandle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.major, self.minor)
+        match *self {
+            Self::ROOT => write!(f, "root"),
+            Self::UNSPEC => write!(f, "none"),
+            Self { major: 0, minor } => write!(f, ":{minor:x}"),
+            Self { major, minor: 0 } => write!(f, "{major:x}:"),
+            Self { major, minor } => write!(f, "{major:x}:{minor:x}"),
+        }
+    }
+}
+
+// Same syntax as `tc(8)`: `major:minor` in hexadecimal where both parts
+// may be omitted, a bare hexadecimal 32 bits handle, or one of the
+// `root`, `none`, `ingress` and `clsact` qdisc parent keywords. As for
+// `tc qdisc`, `ingress` is TC_H_INGRESS; use `TcHandle::CLSACT_INGRESS`
+// and `TcHandle::CLSACT_EGRESS` for the parents of clsact filters.
+impl std::str::FromStr for TcHandle {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_hex = |v: &str| {
+            if v.is_empty() {
+                Ok(0)
+            } else {
+                u16::from_str_radix(v, 16).map_err(|e| {
+                    DecodeError::from(format!("invalid tc handle {s}: {e}"))
+                })
+            }
+        };
+        match s {
+            "root" => Ok(Self::ROOT),
+            "none" => Ok(Self::UNSPEC),
+            "ingress" => Ok(Self::INGRESS),
+            "clsact" => Ok(Self::CLSACT),
+            _ => match s.split_once(':') {
+                Some((major, minor)) => {
+                    Ok(Self::new(parse_hex(major)?, parse_hex(minor)?))
+                }
+                None => {
+                    u32::from_str_radix(s, 16).map(Self::from).map_err(|e| {
+                        DecodeError::from(format!("invalid tc handle {s}: {e}"))
+                    })
+                }
+            },
+        }
     }
 }
@@ -22,6 +22,22 @@ impl TcMessage {
         TcMessage { header, attributes }
     }
 
+    /// Parse a RTM_NEWTCLASS, RTM_DELTCLASS or RTM_GETTCLASS message,
+    /// decoding the xstats as [`super::TcClassXstats`].
+    pub fn parse_class(buf: &[u8]) -> Result<Self, DecodeError> {
+        let header = TcHeader::parse(buf)
+            .context("failed to parse tc message header")?;
+        Ok(Self {
+            header,
+            attributes: VecTcAttribute::parse_with_class(
+                &buf[TC_HEADER_LEN..],
+                true,
+            )
+            .context("failed to parse tc message NLAs")?
+            .0,
+        })
+    }
+
     /// Create a new `TcMessage` with the given index
     pub fn with_index(index: i32) -> Self {
         Self {
@@ -2,43 +2,95 @@
 
 mod actions;
 mod attribute;
+mod chain;
+mod estimator;
 mod filters;
 mod header;
 mod message;
 mod options;
 mod qdiscs;
+mod ratespec;
+mod stab;
 mod stats;
 
 pub(crate) use self::options::VecTcOption;
 pub use self::{
     actions::{
-        TcAction, TcActionAttribute, TcActionGeneric, TcActionGenericBuffer,
+        TcAction, TcActionAttribute, TcActionBpf, TcActionBpfOption,
+        TcActionConnmark, TcActionConnmarkOption, TcActionCsum,
+        TcActionCsumOption, TcActionCt, TcActionCtInfo, TcActionCtInfoOption,
+        TcActionCtOption, TcActionFlags, TcActionGact, TcActionGactOption,
+        TcActionGate, TcActionGateOption, TcActionGeneric,
+        TcActionGenericBuffer, TcActionHwStats, TcActionIfe, TcActionIfeOption,
         TcActionMessage, TcActionMessageAttribute, TcActionMessageBuffer,
         TcActionMessageFlags, TcActionMessageFlagsWithSelector, TcActionMirror,
-        TcActionMirrorOption, TcActionNat, TcActionNatOption, TcActionOption,
-        TcActionTunnelKey, TcActionTunnelKeyOption, TcActionType, TcMirror,
-        TcMirrorActionType, TcMirrorBuffer, TcNat, TcNatBuffer, TcNatFlags,
-        TcTunnelKey, Tcf,
+        TcActionMirrorOption, TcActionMpls, TcActionMplsOption, TcActionNat,
+        TcActionNatOption, TcActionOption, TcActionPedit, TcActionPeditOption,
+        TcActionPolice, TcActionPoliceOption, TcActionSample,
+        TcActionSampleOption, TcActionSkbEdit, TcActionSkbEditOption,
+        TcActionSkbMod, TcActionSkbModOption, TcActionTunnelKey,
+        TcActionTunnelKeyOption, TcActionType, TcActionVlan,
+        TcActionVlanOption, TcBpfSockFilter, TcBpfSockFilterBuffer, TcConnmark,
+        TcConnmarkBuffer, TcCsum, TcCsumBuffer, TcCsumUpdateFlags,
+        TcCtActionFlags, TcGactProb, TcGactProbBuffer, TcGactProbType,
+        TcGateEntry, TcGateEntryAttribute, TcIfe, TcIfeBuffer, TcIfeFlags,
+        TcIfeMeta, TcMirror, TcMirrorActionType, TcMirrorBuffer, TcMpls,
+        TcMplsActionType, TcMplsBuffer, TcNat, TcNatBuffer, TcNatFlags,
+        TcPeditCmd, TcPeditEdit, TcPeditHeaderType, TcPeditKey,
+        TcPeditKeyBuffer, TcPeditKeyEx, TcPeditKeyExAttribute, TcPeditSel,
+        TcPeditSelBuffer, TcPolice, TcPoliceBuffer, TcSkbEditFlags,
+        TcSkbEditPtype, TcSkbMod, TcSkbModBuffer, TcSkbModFlags, TcTunnelKey,
+        TcTunnelKeyEncOption, TcVlan, TcVlanActionType, TcVlanBuffer, Tcf,
     },
     attribute::TcAttribute,
+    chain::TcChainTemplate,
+    estimator::{TcEstimator, TcEstimatorBuffer},
     filters::{
-        TcBpfFlags, TcFilterBpf, TcFilterBpfOption, TcFilterFlower,
-        TcFilterFlowerMplsLseOption, TcFilterFlowerMplsOption,
-        TcFilterFlowerOption, TcFilterMatchAll, TcFilterMatchAllOption,
-        TcFilterU32, TcFilterU32Option, TcU32Key, TcU32OptionFlags,
-        TcU32Selector, TcU32SelectorBuffer, TcU32SelectorFlags,
+        TcBasicPcnt, TcBasicPcntBuffer, TcBpfFlags, TcClsFlags, TcEmatch,
+        TcEmatchCanFilter, TcEmatchCanFilterBuffer, TcEmatchCmp,
+        TcEmatchCmpAlign, TcEmatchCmpBuffer, TcEmatchHdrBuffer, TcEmatchIpset,
+        TcEmatchIpsetBuffer, TcEmatchLayer, TcEmatchMatcher, TcEmatchMetaHdr,
+        TcEmatchMetaHdrBuffer, TcEmatchMetaId, TcEmatchMetaOption,
+        TcEmatchMetaType, TcEmatchMetaValue, TcEmatchNbyte,
+        TcEmatchNbyteBuffer, TcEmatchOperand, TcEmatchRelation, TcEmatchTree,
+        TcEmatchTreeHdr, TcEmatchTreeHdrBuffer, TcFilterBasic,
+        TcFilterBasicOption, TcFilterBpf, TcFilterBpfOption, TcFilterCgroup,
+        TcFilterCgroupOption, TcFilterFlow, TcFilterFlowOption, TcFilterFlower,
+        TcFilterFlowerCfmOption, TcFilterFlowerCtState,
+        TcFilterFlowerEncOptErspan, TcFilterFlowerEncOptGeneve,
+        TcFilterFlowerEncOptGtp, TcFilterFlowerEncOptPfcp,
+        TcFilterFlowerEncOptVxlan, TcFilterFlowerEncOption,
+        TcFilterFlowerKeyFlags, TcFilterFlowerMplsLseOption,
+        TcFilterFlowerMplsOption, TcFilterFlowerOption, TcFilterFw,
+        TcFilterFwOption, TcFilterMatchAll, TcFilterMatchAllOption,
+        TcFilterRoute, TcFilterRouteOption, TcFilterU32, TcFilterU32Option,
+        TcFlowKeys, TcFlowMode, TcU32Handle, TcU32HashTable, TcU32Key,
+        TcU32Mark, TcU32MarkBuffer, TcU32Node, TcU32OptionFlags, TcU32Pcnt,
+        TcU32PcntBuffer, TcU32Selector, TcU32SelectorBuffer,
+        TcU32SelectorFlags,
     },
-    header::{TcHandle, TcHeader, TcMessageBuffer},
+    header::{TcFilterInfo, TcHandle, TcHeader, TcMessageBuffer},
     message::TcMessage,
     options::TcOption,
     qdiscs::{
-        TcFqCodelClStats, TcFqCodelClStatsBuffer, TcFqCodelQdStats,
-        TcFqCodelQdStatsBuffer, TcFqCodelXstats, TcQdiscFqCodel,
-        TcQdiscFqCodelOption, TcQdiscIngress, TcQdiscIngressOption,
+        TcDrrXstats, TcDrrXstatsBuffer, TcFqCodelClStats,
+        TcFqCodelClStatsBuffer, TcFqCodelQdStats, TcFqCodelQdStatsBuffer,
+        TcFqCodelXstats, TcHfscXstats, TcHfscXstatsBuffer, TcHhfXstats,
+        TcHhfXstatsBuffer, TcHtbXstats, TcHtbXstatsBuffer, TcMultiqQopt,
+        TcMultiqQoptBuffer, TcPlugAction, TcPlugQopt, TcPlugQoptBuffer,
+        TcQdiscDrr, TcQdiscFqCodel, TcQdiscFqCodelOption, TcQdiscHfsc,
+        TcQdiscHhf, TcQdiscHhfOption, TcQdiscHtb, TcQdiscIngress,
+        TcQdiscIngressOption, TcQdiscMq, TcQdiscMqOption, TcQdiscMultiq,
+        TcQdiscPlug, TcQdiscSfq, TcQdiscSkbprio, TcSfqXstats,
+        TcSfqXstatsBuffer, TcSkbprioQopt, TcSkbprioQoptBuffer,
     },
+    ratespec::{TcRateSpec, TcRateSpecBuffer},
+    stab::{TcLinkLayer, TcSizeSpec, TcSizeSpecBuffer, TcStab},
     stats::{
-        TcStats, TcStats2, TcStatsBasic, TcStatsBasicBuffer, TcStatsBuffer,
-        TcStatsQueue, TcStatsQueueBuffer, TcXstats,
+        TcClassXstats, TcStats, TcStats2, TcStatsBasic, TcStatsBasic64,
+        TcStatsBasicBuffer, TcStatsBuffer, TcStatsQueue, TcStatsQueueBuffer,
+        TcStatsRateEst, TcStatsRateEst64, TcStatsRateEst64Buffer,
+        TcStatsRateEstBuffer, TcXstats,
     },
 };
 
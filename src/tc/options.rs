@@ -1,14 +1,19 @@
 // SPDX-License-Identifier: MIT
 
 use netlink_packet_core::{
-    DecodeError, DefaultNla, ErrorContext, Nla, NlaBuffer, NlasIterator,
-    Parseable, ParseableParametrized,
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized,
 };
 
 use super::{
-    TcFilterBpf, TcFilterBpfOption, TcFilterFlower, TcFilterFlowerOption,
-    TcFilterMatchAll, TcFilterMatchAllOption, TcFilterU32, TcFilterU32Option,
-    TcQdiscFqCodel, TcQdiscFqCodelOption, TcQdiscIngress, TcQdiscIngressOption,
+    attribute::TCA_OPTIONS, TcFilterBasic, TcFilterBasicOption, TcFilterBpf,
+    TcFilterBpfOption, TcFilterCgroup, TcFilterCgroupOption, TcFilterFlow,
+    TcFilterFlowOption, TcFilterFlower, TcFilterFlowerOption, TcFilterFw,
+    TcFilterFwOption, TcFilterMatchAll, TcFilterMatchAllOption, TcFilterRoute,
+    TcFilterRouteOption, TcFilterU32, TcFilterU32Option, TcMultiqQopt,
+    TcPlugQopt, TcQdiscFqCodel, TcQdiscFqCodelOption, TcQdiscHhf,
+    TcQdiscHhfOption, TcQdiscIngress, TcQdiscIngressOption, TcQdiscMq,
+    TcQdiscMqOption, TcQdiscMultiq, TcQdiscPlug, TcQdiscSkbprio, TcSkbprioQopt,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -17,6 +22,13 @@ pub enum TcOption {
     FqCodel(TcQdiscFqCodelOption),
     // Qdisc specific options
     Ingress(TcQdiscIngressOption),
+    Mq(TcQdiscMqOption),
+    Hhf(TcQdiscHhfOption),
+    // The multiq, skbprio and plug qdiscs use a bare C struct as
+    // TCA_OPTIONS payload instead of nested attributes
+    Multiq(TcMultiqQopt),
+    Skbprio(TcSkbprioQopt),
+    Plug(TcPlugQopt),
     // Filter specific options
     Flower(TcFilterFlowerOption),
     U32(TcFilterU32Option),
@@ -24,6 +36,16 @@ pub enum TcOption {
     MatchAll(TcFilterMatchAllOption),
     // BPF filter options
     Bpf(TcFilterBpfOption),
+    // basic filter options
+    Basic(TcFilterBasicOption),
+    // fw filter options
+    Fw(TcFilterFwOption),
+    // route filter options
+    Route(TcFilterRouteOption),
+    // cgroup filter options
+    Cgroup(TcFilterCgroupOption),
+    // flow filter options
+    Flow(TcFilterFlowOption),
     // Other options
     Other(DefaultNla),
 }
@@ -33,10 +55,20 @@ impl Nla for TcOption {
         match self {
             Self::FqCodel(u) => u.value_len(),
             Self::Ingress(u) => u.value_len(),
+            Self::Mq(u) => u.value_len(),
+            Self::Hhf(u) => u.value_len(),
+            Self::Multiq(u) => u.buffer_len(),
+            Self::Skbprio(u) => u.buffer_len(),
+            Self::Plug(u) => u.buffer_len(),
             Self::U32(u) => u.value_len(),
             Self::Flower(u) => u.value_len(),
             Self::MatchAll(m) => m.value_len(),
             Self::Bpf(u) => u.value_len(),
+            Self::Basic(u) => u.value_len(),
+            Self::Fw(u) => u.value_len(),
+            Self::Route(u) => u.value_len(),
+            Self::Cgroup(u) => u.value_len(),
+            Self::Flow(u) => u.value_len(),
             Self::Other(o) => o.value_len(),
         }
     }
@@ -45,10 +77,20 @@ impl Nla for TcOption {
         match self {
             Self::FqCodel(u) => u.emit_value(buffer),
             Self::Ingress(u) => u.emit_value(buffer),
+            Self::Mq(u) => u.emit_value(buffer),
+            Self::Hhf(u) => u.emit_value(buffer),
+            Self::Multiq(u) => u.emit(buffer),
+            Self::Skbprio(u) => u.emit(buffer),
+            Self::Plug(u) => u.emit(buffer),
             Self::Flower(u) => u.emit_value(buffer),
             Self::U32(u) => u.emit_value(buffer),
             Self::MatchAll(m) => m.emit_value(buffer),
             Self::Bpf(u) => u.emit_value(buffer),
+            Self::Basic(u) => u.emit_value(buffer),
+            Self::Fw(u) => u.emit_value(buffer),
+            Self::Route(u) => u.emit_value(buffer),
+            Self::Cgroup(u) => u.emit_value(buffer),
+            Self::Flow(u) => u.emit_value(buffer),
             Self::Other(o) => o.emit_value(buffer),
         }
     }
@@ -57,10 +99,18 @@ impl Nla for TcOption {
         match self {
             Self::FqCodel(u) => u.kind(),
             Self::Ingress(u) => u.kind(),
+            Self::Mq(u) => u.kind(),
+            Self::Hhf(u) => u.kind(),
+            Self::Multiq(_) | Self::Skbprio(_) | Self::Plug(_) => TCA_OPTIONS,
             Self::Flower(u) => u.kind(),
             Self::U32(u) => u.kind(),
             Self::MatchAll(m) => m.kind(),
             Self::Bpf(u) => u.kind(),
+            Self::Basic(u) => u.kind(),
+            Self::Fw(u) => u.kind(),
+            Self::Route(u) => u.kind(),
+            Self::Cgroup(u) => u.kind(),
+            Self::Flow(u) => u.kind(),
             Self::Other(o) => o.kind(),
         }
     }
@@ -80,6 +130,26 @@ where
                     "failed to parse ingress TCA_OPTIONS attributes",
                 )?)
             }
+            TcQdiscMq::KIND => Self::Mq(
+                TcQdiscMqOption::parse(buf)
+                    .context("failed to parse mq TCA_OPTIONS attributes")?,
+            ),
+            TcQdiscHhf::KIND => Self::Hhf(
+                TcQdiscHhfOption::parse(buf)
+                    .context("failed to parse hhf TCA_OPTIONS attributes")?,
+            ),
+            TcQdiscMultiq::KIND => Self::Multiq(
+                TcMultiqQopt::parse(buf.value())
+                    .context("failed to parse multiq TCA_OPTIONS")?,
+            ),
+            TcQdiscSkbprio::KIND => Self::Skbprio(
+                TcSkbprioQopt::parse(buf.value())
+                    .context("failed to parse skbprio TCA_OPTIONS")?,
+            ),
+            TcQdiscPlug::KIND => Self::Plug(
+                TcPlugQopt::parse(buf.value())
+                    .context("failed to parse plug TCA_OPTIONS")?,
+            ),
             TcFilterFlower::KIND => Self::Flower(
                 TcFilterFlowerOption::parse(buf)
                     .context("failed to parse flower TCA_OPTIONS attributes")?,
@@ -102,6 +172,26 @@ where
                 TcFilterBpfOption::parse(buf)
                     .context("failed to parse bpf TCA_OPTIONS attributes")?,
             ),
+            TcFilterBasic::KIND => Self::Basic(
+                TcFilterBasicOption::parse(buf)
+                    .context("failed to parse basic TCA_OPTIONS attributes")?,
+            ),
+            TcFilterFw::KIND => Self::Fw(
+                TcFilterFwOption::parse(buf)
+                    .context("failed to parse fw TCA_OPTIONS attributes")?,
+            ),
+            TcFilterRoute::KIND => Self::Route(
+                TcFilterRouteOption::parse(buf)
+                    .context("failed to parse route TCA_OPTIONS attributes")?,
+            ),
+            TcFilterCgroup::KIND => Self::Cgroup(
+                TcFilterCgroupOption::parse(buf)
+                    .context("failed to parse cgroup TCA_OPTIONS attributes")?,
+            ),
+            TcFilterFlow::KIND => Self::Flow(
+                TcFilterFlowOption::parse(buf)
+                    .context("failed to parse flow TCA_OPTIONS attributes")?,
+            ),
             _ => Self::Other(DefaultNla::parse(buf)?),
         })
     }
@@ -122,8 +212,15 @@ where
             | TcFilterMatchAll::KIND
             | TcFilterFlower::KIND
             | TcQdiscIngress::KIND
+            | TcQdiscMq::KIND
+            | TcQdiscHhf::KIND
             | TcQdiscFqCodel::KIND
-            | TcFilterBpf::KIND => {
+            | TcFilterBpf::KIND
+            | TcFilterBasic::KIND
+            | TcFilterFw::KIND
+            | TcFilterRoute::KIND
+            | TcFilterCgroup::KIND
+            | TcFilterFlow::KIND => {
                 let mut nlas = vec![];
                 for nla in NlasIterator::new(buf.value()) {
                     let nla = nla.context(format!(
@@ -139,6 +236,9 @@ where
                 }
                 Self(nlas)
             }
+            TcQdiscMultiq::KIND | TcQdiscSkbprio::KIND | TcQdiscPlug::KIND => {
+                Self(vec![TcOption::parse_with_param(buf, kind)?])
+            }
             // Kernel has no guide line or code indicate the scheduler
             // should place a nla_nest here. The `sfq` qdisc kernel code is
             // using single NLA instead nested ones. Hence we are storing
@@ -147,3 +247,25 @@ where
         })
     }
 }
+
+impl TcOption {
+    fn is_struct(&self) -> bool {
+        matches!(self, Self::Multiq(_) | Self::Skbprio(_) | Self::Plug(_))
+    }
+}
+
+// Length of TCA_OPTIONS payload, taking qdiscs using a bare C struct
+// instead of nested attributes into account.
+pub(crate) fn tc_options_len(opts: &[TcOption]) -> usize {
+    match opts {
+        [opt] if opt.is_struct() => opt.value_len(),
+        _ => opts.buffer_len(),
+    }
+}
+
+pub(crate) fn emit_tc_options(opts: &[TcOption], buffer: &mut [u8]) {
+    match opts {
+        [opt] if opt.is_struct() => opt.emit_value(buffer),
+        _ => opts.emit(buffer),
+    }
+}
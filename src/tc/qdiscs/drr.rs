@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT
+
+/// Deficit Round Robin qdisc
+///
+/// The drr qdisc serves its classes in turn, each one sending up to its
+/// quantum of bytes per round.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscDrr {}
+
+impl TcQdiscDrr {
+    pub(crate) const KIND: &'static str = "drr";
+}
+
+/// Class statistics
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcDrrXstats {
+    /// Bytes the class may still send in this round
+    pub deficit: u32,
+}
+
+// kernel struct `tc_drr_stats`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcDrrXstatsBuffer {
+    deficit: u32,
+}
+
+impl TcDrrXstats {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcDrrXstatsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcDrrXstatsBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            deficit: raw.deficit,
+        })
+    }
+}
+
+impl From<&TcDrrXstats> for TcDrrXstatsBuffer {
+    fn from(value: &TcDrrXstats) -> Self {
+        Self {
+            deficit: value.deficit,
+        }
+    }
+}
+
+impl Emitable for TcDrrXstats {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcDrrXstatsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcDrrXstatsBuffer::from(self);
+        buffer[..size_of::<TcDrrXstatsBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
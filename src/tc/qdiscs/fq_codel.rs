@@ -174,12 +174,18 @@ impl Emitable for TcFqCodelQdStats {
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
 pub struct TcFqCodelClStats {
-    deficit: i32,
-    ldelay: u32,
-    count: u32,
-    lastcount: u32,
-    dropping: u32,
-    drop_next: i32,
+    /// Bytes the flow may still send in this round
+    pub deficit: i32,
+    /// Sojourn time of the last dequeued packet, in microseconds
+    pub ldelay: u32,
+    /// Number of packets dropped or marked since entering dropping state
+    pub count: u32,
+    /// Value of `count` when leaving the last dropping state
+    pub lastcount: u32,
+    /// Whether the flow is in dropping state
+    pub dropping: u32,
+    /// Time until the next drop, in microseconds
+    pub drop_next: i32,
 }
 
 #[derive(
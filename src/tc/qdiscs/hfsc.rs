@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+
+/// Hierarchical Fair Service Curve qdisc
+///
+/// The hfsc qdisc schedules a hierarchy of classes following real-time,
+/// link-sharing and upper-limit service curves.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscHfsc {}
+
+impl TcQdiscHfsc {
+    pub(crate) const KIND: &'static str = "hfsc";
+}
+
+/// Class statistics
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcHfscXstats {
+    /// Total work done, in bytes
+    pub work: u64,
+    /// Work done under the real-time criteria, in bytes
+    pub rtwork: u64,
+    /// Current period
+    pub period: u32,
+    /// Class level in the hierarchy
+    pub level: u32,
+}
+
+// kernel struct `tc_hfsc_stats`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcHfscXstatsBuffer {
+    work: u64,
+    rtwork: u64,
+    period: u32,
+    level: u32,
+}
+
+impl TcHfscXstats {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcHfscXstatsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcHfscXstatsBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            work: raw.work,
+            rtwork: raw.rtwork,
+            period: raw.period,
+            level: raw.level,
+        })
+    }
+}
+
+impl From<&TcHfscXstats> for TcHfscXstatsBuffer {
+    fn from(value: &TcHfscXstats) -> Self {
+        Self {
+            work: value.work,
+            rtwork: value.rtwork,
+            period: value.period,
+            level: value.level,
+        }
+    }
+}
+
+impl Emitable for TcHfscXstats {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcHfscXstatsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcHfscXstatsBuffer::from(self);
+        buffer[..size_of::<TcHfscXstatsBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT
+
+/// Heavy-Hitter Filter qdisc
+///
+/// The hhf qdisc identifies heavy-hitter flows and schedules them with a
+/// lower priority than the other (mice) flows.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    emit_u32, parse_u32, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, Parseable,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscHhf {}
+
+impl TcQdiscHhf {
+    pub(crate) const KIND: &'static str = "hhf";
+}
+
+const TCA_HHF_BACKLOG_LIMIT: u16 = 1;
+const TCA_HHF_QUANTUM: u16 = 2;
+const TCA_HHF_HH_FLOWS_LIMIT: u16 = 3;
+const TCA_HHF_RESET_TIMEOUT: u16 = 4;
+const TCA_HHF_ADMIT_BYTES: u16 = 5;
+const TCA_HHF_EVICT_TIMEOUT: u16 = 6;
+const TCA_HHF_NON_HH_WEIGHT: u16 = 7;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcQdiscHhfOption {
+    /// Queue length in packets
+    BacklogLimit(u32),
+    /// Quantum in bytes
+    Quantum(u32),
+    /// Maximum number of heavy-hitter flows tracked
+    HhFlowsLimit(u32),
+    /// Heavy-hitter filter reset interval in microseconds
+    ResetTimeout(u32),
+    /// Bytes a flow must send before being counted as heavy-hitter
+    AdmitBytes(u32),
+    /// Idle time in microseconds before a heavy-hitter flow is evicted
+    EvictTimeout(u32),
+    /// Weight of the non heavy-hitter queue against the heavy-hitter one
+    NonHhWeight(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for TcQdiscHhfOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::BacklogLimit(_)
+            | Self::Quantum(_)
+            | Self::HhFlowsLimit(_)
+            | Self::ResetTimeout(_)
+            | Self::AdmitBytes(_)
+            | Self::EvictTimeout(_)
+            | Self::NonHhWeight(_) => 4,
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::BacklogLimit(d)
+            | Self::Quantum(d)
+            | Self::HhFlowsLimit(d)
+            | Self::ResetTimeout(d)
+            | Self::AdmitBytes(d)
+            | Self::EvictTimeout(d)
+            | Self::NonHhWeight(d) => emit_u32(buffer, *d).unwrap(),
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::BacklogLimit(_) => TCA_HHF_BACKLOG_LIMIT,
+            Self::Quantum(_) => TCA_HHF_QUANTUM,
+            Self::HhFlowsLimit(_) => TCA_HHF_HH_FLOWS_LIMIT,
+            Self::ResetTimeout(_) => TCA_HHF_RESET_TIMEOUT,
+            Self::AdmitBytes(_) => TCA_HHF_ADMIT_BYTES,
+            Self::EvictTimeout(_) => TCA_HHF_EVICT_TIMEOUT,
+            Self::NonHhWeight(_) => TCA_HHF_NON_HH_WEIGHT,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcQdiscHhfOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_HHF_BACKLOG_LIMIT => Self::BacklogLimit(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_BACKLOG_LIMIT")?,
+            ),
+            TCA_HHF_QUANTUM => Self::Quantum(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_QUANTUM")?,
+            ),
+            TCA_HHF_HH_FLOWS_LIMIT => Self::HhFlowsLimit(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_HH_FLOWS_LIMIT")?,
+            ),
+            TCA_HHF_RESET_TIMEOUT => Self::ResetTimeout(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_RESET_TIMEOUT")?,
+            ),
+            TCA_HHF_ADMIT_BYTES => Self::AdmitBytes(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_ADMIT_BYTES")?,
+            ),
+            TCA_HHF_EVICT_TIMEOUT => Self::EvictTimeout(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_EVICT_TIMEOUT")?,
+            ),
+            TCA_HHF_NON_HH_WEIGHT => Self::NonHhWeight(
+                parse_u32(payload)
+                    .context("failed to parse TCA_HHF_NON_HH_WEIGHT")?,
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse hhf nla")?,
+            ),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcHhfXstats {
+    /// Number of times max qdisc packet limit was hit
+    pub drop_overlimit: u32,
+    /// Number of times max heavy-hitters was hit
+    pub hh_overlimit: u32,
+    /// Number of captured heavy-hitters so far
+    pub hh_tot_count: u32,
+    /// Number of current heavy-hitters
+    pub hh_cur_count: u32,
+}
+
+// kernel struct `tc_hhf_xstats`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcHhfXstatsBuffer {
+    drop_overlimit: u32,
+    hh_overlimit: u32,
+    hh_tot_count: u32,
+    hh_cur_count: u32,
+}
+
+impl TcHhfXstats {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcHhfXstatsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcHhfXstatsBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            drop_overlimit: raw.drop_overlimit,
+            hh_overlimit: raw.hh_overlimit,
+            hh_tot_count: raw.hh_tot_count,
+            hh_cur_count: raw.hh_cur_count,
+        })
+    }
+}
+
+impl From<&TcHhfXstats> for TcHhfXstatsBuffer {
+    fn from(value: &TcHhfXstats) -> Self {
+        Self {
+            drop_overlimit: value.drop_overlimit,
+            hh_overlimit: value.hh_overlimit,
+            hh_tot_count: value.hh_tot_count,
+            hh_cur_count: value.hh_cur_count,
+        }
+    }
+}
+
+impl Emitable for TcHhfXstats {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcHhfXstatsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcHhfXstatsBuffer::from(self);
+        buffer[..size_of::<TcHhfXstatsBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
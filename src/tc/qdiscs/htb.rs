@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: MIT
+
+/// Hierarchy Token Bucket qdisc
+///
+/// The htb qdisc shapes traffic with a hierarchy of classes, each one
+/// guaranteed its rate and allowed to borrow unused bandwidth from its
+/// parent up to its ceil.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscHtb {}
+
+impl TcQdiscHtb {
+    pub(crate) const KIND: &'static str = "htb";
+}
+
+/// Class statistics
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcHtbXstats {
+    /// Number of packets sent while the class was under its rate
+    pub lends: u32,
+    /// Number of packets sent with bandwidth borrowed from an
+    /// ancestor
+    pub borrows: u32,
+    /// Number of packets larger than the MTU, no longer counted
+    pub giants: u32,
+    /// Remaining rate tokens, in psched ticks
+    pub tokens: i32,
+    /// Remaining ceil tokens, in psched ticks
+    pub ctokens: i32,
+}
+
+// kernel struct `tc_htb_xstats`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcHtbXstatsBuffer {
+    lends: u32,
+    borrows: u32,
+    giants: u32,
+    tokens: i32,
+    ctokens: i32,
+}
+
+impl TcHtbXstats {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcHtbXstatsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcHtbXstatsBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            lends: raw.lends,
+            borrows: raw.borrows,
+            giants: raw.giants,
+            tokens: raw.tokens,
+            ctokens: raw.ctokens,
+        })
+    }
+}
+
+impl From<&TcHtbXstats> for TcHtbXstatsBuffer {
+    fn from(value: &TcHtbXstats) -> Self {
+        Self {
+            lends: value.lends,
+            borrows: value.borrows,
+            giants: value.giants,
+            tokens: value.tokens,
+            ctokens: value.ctokens,
+        }
+    }
+}
+
+impl Emitable for TcHtbXstats {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcHtbXstatsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcHtbXstatsBuffer::from(self);
+        buffer[..size_of::<TcHtbXstatsBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
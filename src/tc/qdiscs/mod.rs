@@ -1,13 +1,31 @@
 // SPDX-License-Identifier: MIT
 
+mod drr;
 mod fq_codel;
+mod hfsc;
+mod hhf;
+mod htb;
 mod ingress;
+mod mq;
+mod multiq;
+mod plug;
+mod sfq;
+mod skbprio;
 
 pub use self::{
+    drr::{TcDrrXstats, TcDrrXstatsBuffer, TcQdiscDrr},
     fq_codel::{
         TcFqCodelClStats, TcFqCodelClStatsBuffer, TcFqCodelQdStats,
         TcFqCodelQdStatsBuffer, TcFqCodelXstats, TcQdiscFqCodel,
         TcQdiscFqCodelOption,
     },
+    hfsc::{TcHfscXstats, TcHfscXstatsBuffer, TcQdiscHfsc},
+    hhf::{TcHhfXstats, TcHhfXstatsBuffer, TcQdiscHhf, TcQdiscHhfOption},
+    htb::{TcHtbXstats, TcHtbXstatsBuffer, TcQdiscHtb},
     ingress::{TcQdiscIngress, TcQdiscIngressOption},
+    mq::{TcQdiscMq, TcQdiscMqOption},
+    multiq::{TcMultiqQopt, TcMultiqQoptBuffer, TcQdiscMultiq},
+    plug::{TcPlugAction, TcPlugQopt, TcPlugQoptBuffer, TcQdiscPlug},
+    sfq::{TcQdiscSfq, TcSfqXstats, TcSfqXstatsBuffer},
+    skbprio::{TcQdiscSkbprio, TcSkbprioQopt, TcSkbprioQoptBuffer},
 };
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT
+
+/// Multiqueue qdisc
+///
+/// The mq qdisc is a dummy scheduler which exposes one class per hardware
+/// transmit queue of the device. The kernel does not dump any TCA_OPTIONS
+/// for it, the interesting part is the class dump: each class represents a
+/// transmit queue and carries the handle of the qdisc attached to it.
+use netlink_packet_core::{
+    DecodeError, DefaultNla, ErrorContext, Nla, NlaBuffer, Parseable,
+};
+
+use crate::tc::{TcHandle, TcHeader};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscMq {}
+
+impl TcQdiscMq {
+    pub(crate) const KIND: &'static str = "mq";
+
+    /// Zero based transmit queue index of a mq class.
+    ///
+    /// The kernel uses `minor - 1` of the class handle as queue index.
+    /// Return `None` if the header does not describe a mq class.
+    pub fn class_queue_index(header: &TcHeader) -> Option<u16> {
+        header.handle.minor.checked_sub(1)
+    }
+
+    /// Handle of the qdisc attached to the transmit queue of a mq class.
+    ///
+    /// For class dumps, the kernel stores the handle of the child qdisc in
+    /// the `info` field of the header.
+    pub fn class_child(header: &TcHeader) -> TcHandle {
+        TcHandle::from(header.info)
+    }
+
+    /// Create the header of the mq class for the specified transmit queue.
+    pub fn class_header(
+        index: i32,
+        qdisc: TcHandle,
+        queue_index: u16,
+        child: TcHandle,
+    ) -> TcHeader {
+        TcHeader {
+            index,
+            handle: TcHandle {
+                major: qdisc.major,
+                minor: queue_index.saturating_add(1),
+            },
+            parent: TcHandle::ROOT,
+            info: child.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcQdiscMqOption {
+    Other(DefaultNla),
+}
+
+impl Nla for TcQdiscMqOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for TcQdiscMqOption
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self::Other(
+            DefaultNla::parse(buf).context("failed to parse mq nla")?,
+        ))
+    }
+}
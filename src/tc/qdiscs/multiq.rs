@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+
+/// Multiqueue band qdisc
+///
+/// The multiq qdisc assigns one band per hardware transmit queue. Its
+/// TCA_OPTIONS is a bare `struct tc_multiq_qopt` instead of nested
+/// attributes.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscMultiq {}
+
+impl TcQdiscMultiq {
+    pub(crate) const KIND: &'static str = "multiq";
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcMultiqQopt {
+    /// Number of bands
+    pub bands: u16,
+    /// Maximum number of queues, ignored by kernel when creating
+    pub max_bands: u16,
+}
+
+// kernel struct `tc_multiq_qopt`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcMultiqQoptBuffer {
+    bands: u16,
+    max_bands: u16,
+}
+
+impl TcMultiqQopt {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcMultiqQoptBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcMultiqQoptBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            bands: raw.bands,
+            max_bands: raw.max_bands,
+        })
+    }
+}
+
+impl From<&TcMultiqQopt> for TcMultiqQoptBuffer {
+    fn from(value: &TcMultiqQopt) -> Self {
+        Self {
+            bands: value.bands,
+            max_bands: value.max_bands,
+        }
+    }
+}
+
+impl Emitable for TcMultiqQopt {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcMultiqQoptBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcMultiqQoptBuffer::from(self);
+        buffer[..size_of::<TcMultiqQoptBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
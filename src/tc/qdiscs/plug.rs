@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+
+/// Plug qdisc
+///
+/// The plug qdisc buffers packets until it is told to release them. It is
+/// driven by sending `struct tc_plug_qopt` as TCA_OPTIONS with one of the
+/// [`TcPlugAction`] commands. The kernel never dumps these options back.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscPlug {}
+
+impl TcQdiscPlug {
+    pub(crate) const KIND: &'static str = "plug";
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcPlugQopt {
+    pub action: TcPlugAction,
+    /// Maximum number of bytes buffered, only used by
+    /// [`TcPlugAction::Limit`].
+    pub limit: u32,
+}
+
+// kernel struct `tc_plug_qopt`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcPlugQoptBuffer {
+    action: i32,
+    limit: u32,
+}
+
+impl TcPlugQopt {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcPlugQoptBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcPlugQoptBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            action: raw.action.into(),
+            limit: raw.limit,
+        })
+    }
+}
+
+impl From<&TcPlugQopt> for TcPlugQoptBuffer {
+    fn from(value: &TcPlugQopt) -> Self {
+        Self {
+            action: value.action.into(),
+            limit: value.limit,
+        }
+    }
+}
+
+impl Emitable for TcPlugQopt {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcPlugQoptBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcPlugQoptBuffer::from(self);
+        buffer[..size_of::<TcPlugQoptBuffer>()].copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TCQ_PLUG_BUFFER: i32 = 0;
+const TCQ_PLUG_RELEASE_ONE: i32 = 1;
+const TCQ_PLUG_RELEASE_INDEFINITE: i32 = 2;
+const TCQ_PLUG_LIMIT: i32 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcPlugAction {
+    /// Start buffering packets, inserting a plug behind the previous one.
+    #[default]
+    Buffer,
+    /// Release packets up to the first plug.
+    ReleaseOne,
+    /// Stop buffering and release all packets.
+    ReleaseIndefinite,
+    /// Change the buffer limit.
+    Limit,
+    Other(i32),
+}
+
+impl From<i32> for TcPlugAction {
+    fn from(d: i32) -> Self {
+        match d {
+            TCQ_PLUG_BUFFER => Self::Buffer,
+            TCQ_PLUG_RELEASE_ONE => Self::ReleaseOne,
+            TCQ_PLUG_RELEASE_INDEFINITE => Self::ReleaseIndefinite,
+            TCQ_PLUG_LIMIT => Self::Limit,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcPlugAction> for i32 {
+    fn from(v: TcPlugAction) -> i32 {
+        match v {
+            TcPlugAction::Buffer => TCQ_PLUG_BUFFER,
+            TcPlugAction::ReleaseOne => TCQ_PLUG_RELEASE_ONE,
+            TcPlugAction::ReleaseIndefinite => TCQ_PLUG_RELEASE_INDEFINITE,
+            TcPlugAction::Limit => TCQ_PLUG_LIMIT,
+            TcPlugAction::Other(d) => d,
+        }
+    }
+}
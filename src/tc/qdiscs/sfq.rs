@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MIT
+
+/// Stochastic Fairness Queueing qdisc
+///
+/// The sfq qdisc hashes flows into buckets served in round robin. Each
+/// bucket is dumped as a class.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscSfq {}
+
+impl TcQdiscSfq {
+    pub(crate) const KIND: &'static str = "sfq";
+}
+
+/// Class statistics
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcSfqXstats {
+    /// Bytes the flow may still send in this round
+    pub allot: i32,
+}
+
+// kernel struct `tc_sfq_xstats`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcSfqXstatsBuffer {
+    allot: i32,
+}
+
+impl TcSfqXstats {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcSfqXstatsBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcSfqXstatsBuffer>(),
+                )
+            })?;
+        Ok(Self { allot: raw.allot })
+    }
+}
+
+impl From<&TcSfqXstats> for TcSfqXstatsBuffer {
+    fn from(value: &TcSfqXstats) -> Self {
+        Self { allot: value.allot }
+    }
+}
+
+impl Emitable for TcSfqXstats {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcSfqXstatsBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcSfqXstatsBuffer::from(self);
+        buffer[..size_of::<TcSfqXstatsBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+
+/// SKB priority queue qdisc
+///
+/// The skbprio qdisc schedules packets according to `skb->priority`,
+/// dropping the lowest priority packets when the queue is full. Its
+/// TCA_OPTIONS is a bare `struct tc_skbprio_qopt` instead of nested
+/// attributes.
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TcQdiscSkbprio {}
+
+impl TcQdiscSkbprio {
+    pub(crate) const KIND: &'static str = "skbprio";
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcSkbprioQopt {
+    /// Queue length in packets
+    pub limit: u32,
+}
+
+// kernel struct `tc_skbprio_qopt`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcSkbprioQoptBuffer {
+    limit: u32,
+}
+
+impl TcSkbprioQopt {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcSkbprioQoptBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcSkbprioQoptBuffer>(),
+                )
+            })?;
+        Ok(Self { limit: raw.limit })
+    }
+}
+
+impl From<&TcSkbprioQopt> for TcSkbprioQoptBuffer {
+    fn from(value: &TcSkbprioQopt) -> Self {
+        Self { limit: value.limit }
+    }
+}
+
+impl Emitable for TcSkbprioQopt {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcSkbprioQoptBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcSkbprioQoptBuffer::from(self);
+        buffer[..size_of::<TcSkbprioQoptBuffer>()]
+            .copy_from_slice(raw.as_bytes());
+    }
+}
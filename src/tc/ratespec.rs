@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use super::TcLinkLayer;
+
+const TC_LINKLAYER_MASK: u8 = 0x0F;
+
+/// Rate specification shared by the rate limiting qdiscs and the police
+/// action.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcRateSpec {
+    /// Logarithm of the packet size range covered by each cell of the
+    /// rate table
+    pub cell_log: u8,
+    pub linklayer: TcLinkLayer,
+    /// Bytes added to each packet size
+    pub overhead: u16,
+    pub cell_align: i16,
+    /// Minimum packet size
+    pub mpu: u16,
+    /// Rate in bytes per second, `u32::MAX` when the real rate is given by a
+    /// 64 bits attribute
+    pub rate: u32,
+}
+
+// kernel struct `tc_ratespec`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcRateSpecBuffer {
+    cell_log: u8,
+    linklayer: u8,
+    overhead: u16,
+    cell_align: i16,
+    mpu: u16,
+    rate: u32,
+}
+
+impl TcRateSpec {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcRateSpecBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcRateSpecBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            cell_log: raw.cell_log,
+            linklayer: u32::from(raw.linklayer & TC_LINKLAYER_MASK).into(),
+            overhead: raw.overhead,
+            cell_align: raw.cell_align,
+            mpu: raw.mpu,
+            rate: raw.rate,
+        })
+    }
+}
+
+impl From<&TcRateSpec> for TcRateSpecBuffer {
+    fn from(value: &TcRateSpec) -> Self {
+        Self {
+            cell_log: value.cell_log,
+            linklayer: u32::from(value.linklayer) as u8 & TC_LINKLAYER_MASK,
+            overhead: value.overhead,
+            cell_align: value.cell_align,
+            mpu: value.mpu,
+            rate: value.rate,
+        }
+    }
+}
+
+impl Emitable for TcRateSpec {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcRateSpecBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcRateSpecBuffer::from(self);
+        buffer[..size_of::<TcRateSpecBuffer>()].copy_from_slice(raw.as_bytes());
+    }
+}
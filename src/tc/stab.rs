@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: MIT
+
+/// Size table
+///
+/// A size table (TCA_STAB) lets qdiscs account packets by their size on
+/// the wire instead of their size in memory, e.g. to take the ATM cell tax
+/// or link layer overhead into account.
+use std::mem::size_of;
+
+use netlink_packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer, Parseable,
+    NLA_ALIGNTO, NLA_HEADER_SIZE,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+const TCA_STAB_BASE: u16 = 1;
+const TCA_STAB_DATA: u16 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcStab {
+    /// Size table parameters
+    Base(TcSizeSpec),
+    /// Size table, only sent to kernel, never dumped back
+    Data(Vec<u16>),
+    Other(DefaultNla),
+}
+
+impl TcStab {
+    /// Generate the TCA_STAB attributes for the specified size table
+    /// parameters, computing the size table the same way iproute2 does.
+    pub fn from_size_spec(
+        mut spec: TcSizeSpec,
+    ) -> Result<Vec<Self>, DecodeError> {
+        let data = spec.calc_size_table()?;
+        let mut nlas = vec![Self::Base(spec)];
+        if !data.is_empty() {
+            nlas.push(Self::Data(data));
+        }
+        Ok(nlas)
+    }
+}
+
+impl Nla for TcStab {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Base(v) => v.buffer_len(),
+            Self::Data(v) => v.len() * size_of::<u16>(),
+            Self::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Base(v) => v.emit(buffer),
+            Self::Data(v) => {
+                for (i, size) in v.iter().enumerate() {
+                    buffer[i * 2..(i + 1) * 2]
+                        .copy_from_slice(&size.to_ne_bytes());
+                }
+            }
+            Self::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Base(_) => TCA_STAB_BASE,
+            Self::Data(_) => TCA_STAB_DATA,
+            Self::Other(attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcStab {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_STAB_BASE => Self::Base(
+                TcSizeSpec::parse(payload)
+                    .context("failed to parse TCA_STAB_BASE")?,
+            ),
+            TCA_STAB_DATA => Self::Data(
+                payload
+                    .chunks_exact(size_of::<u16>())
+                    .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+                    .collect(),
+            ),
+            _ => Self::Other(
+                DefaultNla::parse(buf).context("failed to parse stab nla")?,
+            ),
+        })
+    }
+}
+
+// Size table parameters used by iproute2 when not specified
+const DEFAULT_MTU: u32 = 2047;
+const DEFAULT_TSIZE: u32 = 512;
+
+const ATM_CELL_SIZE: u64 = 53;
+const ATM_CELL_PAYLOAD: u64 = 48;
+// The kernel shifts 32 bits packet sizes by `cell_log`
+const MAX_CELL_LOG: u8 = 31;
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcSizeSpec {
+    /// Logarithm of the packet size range covered by each table cell
+    pub cell_log: u8,
+    /// Logarithm of the unit of the sizes stored in the table
+    pub size_log: u8,
+    pub cell_align: i16,
+    /// Bytes added to each packet size before the table lookup
+    pub overhead: i32,
+    pub linklayer: TcLinkLayer,
+    /// Minimum packet size
+    pub mpu: u32,
+    /// Maximum packet size covered by the table
+    pub mtu: u32,
+    /// Number of entries in the size table
+    pub tsize: u32,
+}
+
+// kernel struct `tc_sizespec`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcSizeSpecBuffer {
+    cell_log: u8,
+    size_log: u8,
+    cell_align: i16,
+    overhead: i32,
+    linklayer: u32,
+    mpu: u32,
+    mtu: u32,
+    tsize: u32,
+}
+
+impl TcSizeSpec {
+    /// Largest `tsize` whose table still fits, along with the size table
+    /// parameters, in the TCA_STAB attribute and its 16 bits length.
+    pub const MAX_TSIZE: u32 = (((u16::MAX as usize
+        - 3 * NLA_HEADER_SIZE
+        - size_of::<TcSizeSpecBuffer>())
+        & !(NLA_ALIGNTO - 1))
+        / size_of::<u16>()) as u32;
+
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcSizeSpecBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcSizeSpecBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            cell_log: raw.cell_log,
+            size_log: raw.size_log,
+            cell_align: raw.cell_align,
+            overhead: raw.overhead,
+            linklayer: raw.linklayer.into(),
+            mpu: raw.mpu,
+            mtu: raw.mtu,
+            tsize: raw.tsize,
+        })
+    }
+
+    /// Compute the size table the same way iproute2 `tc_calc_size_table()`
+    /// does, updating `cell_log`, `size_log`, `cell_align`, `mtu` and
+    /// `tsize` accordingly.
+    ///
+    /// Return an empty table when no table is required, i.e. Ethernet link
+    /// layer with no minimum packet size, where only `overhead` matters.
+    /// Return an error when `tsize` is above [`Self::MAX_TSIZE`] or when
+    /// `mtu` cannot be covered by `tsize` cells. As for the `FromStr`
+    /// implementations of this crate, invalid parameters are reported as
+    /// [`DecodeError`], the only error type of this crate.
+    pub fn calc_size_table(&mut self) -> Result<Vec<u16>, DecodeError> {
+        if self.linklayer.is_ethernet_like() && self.mpu == 0 {
+            self.mtu = 0;
+            self.tsize = 0;
+            self.cell_log = 0;
+            self.cell_align = 0;
+            return Ok(Vec::new());
+        }
+
+        if self.mtu == 0 {
+            self.mtu = DEFAULT_MTU;
+        }
+        if self.tsize == 0 {
+            self.tsize = DEFAULT_TSIZE;
+        }
+        if self.tsize > Self::MAX_TSIZE {
+            return Err(DecodeError::from(format!(
+                "Size table of {} cells is larger than the maximum {}",
+                self.tsize,
+                Self::MAX_TSIZE
+            )));
+        }
+
+        self.cell_log = 0;
+        while (self.mtu >> self.cell_log) > self.tsize - 1 {
+            if self.cell_log == MAX_CELL_LOG {
+                return Err(DecodeError::from(format!(
+                    "Size table of {} cells cannot cover mtu {}",
+                    self.tsize, self.mtu
+                )));
+            }
+            self.cell_log += 1;
+        }
+
+        let mut table = vec![0u16; self.tsize as usize];
+        'again: loop {
+            for (i, entry) in table.iter_mut().enumerate().rev() {
+                let size =
+                    self.adjust_size(((i as u64) + 1) << self.cell_log)?;
+                match u16::try_from(
+                    size.checked_shr(self.size_log.into()).unwrap_or(0),
+                ) {
+                    Ok(s) => *entry = s,
+                    Err(_) => {
+                        self.size_log += 1;
+                        continue 'again;
+                    }
+                }
+            }
+            break;
+        }
+        // Due to the size calculation above using `(i + 1) << cell_log`
+        self.cell_align = -1;
+        Ok(table)
+    }
+
+    fn adjust_size(&self, size: u64) -> Result<u64, DecodeError> {
+        let size = size.max(self.mpu.into());
+        match self.linklayer {
+            TcLinkLayer::Atm => size
+                .div_ceil(ATM_CELL_PAYLOAD)
+                .checked_mul(ATM_CELL_SIZE)
+                .ok_or_else(|| {
+                    DecodeError::from(format!(
+                        "Size {size} overflows once split into ATM cells"
+                    ))
+                }),
+            _ => Ok(size),
+        }
+    }
+}
+
+impl From<&TcSizeSpec> for TcSizeSpecBuffer {
+    fn from(value: &TcSizeSpec) -> Self {
+        Self {
+            cell_log: value.cell_log,
+            size_log: value.size_log,
+            cell_align: value.cell_align,
+            overhead: value.overhead,
+            linklayer: value.linklayer.into(),
+            mpu: value.mpu,
+            mtu: value.mtu,
+            tsize: value.tsize,
+        }
+    }
+}
+
+impl Emitable for TcSizeSpec {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcSizeSpecBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcSizeSpecBuffer::from(self);
+        buffer[..size_of::<TcSizeSpecBuffer>()].copy_from_slice(raw.as_bytes());
+    }
+}
+
+const TC_LINKLAYER_UNAWARE: u32 = 0;
+const TC_LINKLAYER_ETHERNET: u32 = 1;
+const TC_LINKLAYER_ATM: u32 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum TcLinkLayer {
+    #[default]
+    Unaware,
+    Ethernet,
+    Atm,
+    Other(u32),
+}
+
+impl TcLinkLayer {
+    fn is_ethernet_like(&self) -> bool {
+        matches!(self, Self::Unaware | Self::Ethernet)
+    }
+}
+
+impl From<u32> for TcLinkLayer {
+    fn from(d: u32) -> Self {
+        match d {
+            TC_LINKLAYER_UNAWARE => Self::Unaware,
+            TC_LINKLAYER_ETHERNET => Self::Ethernet,
+            TC_LINKLAYER_ATM => Self::Atm,
+            _ => Self::Other(d),
+        }
+    }
+}
+
+impl From<TcLinkLayer> for u32 {
+    fn from(v: TcLinkLayer) -> u32 {
+        match v {
+            TcLinkLayer::Unaware => TC_LINKLAYER_UNAWARE,
+            TcLinkLayer::Ethernet => TC_LINKLAYER_ETHERNET,
+            TcLinkLayer::Atm => TC_LINKLAYER_ATM,
+            TcLinkLayer::Other(d) => d,
+        }
+    }
+}
@@ -71,3 +71,30 @@ impl Emitable for TcStatsBasic {
         buffer.copy_from_slice(raw.as_bytes());
     }
 }
+
+/// Byte/Packet throughput statistics with 64 bits packet counter.
+///
+/// Kernel truncates the packet counter of TCA_STATS_BASIC to the low 32
+/// bits and appends a TCA_STATS_PKT64 attribute holding the full value when
+/// it does not fit. Use [`TcStats2::basic64()`] or [`TcStats2::basic_hw64()`]
+/// to merge them.
+///
+/// [`TcStats2::basic64()`]: crate::tc::TcStats2::basic64
+/// [`TcStats2::basic_hw64()`]: crate::tc::TcStats2::basic_hw64
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcStatsBasic64 {
+    /// number of seen bytes
+    pub bytes: u64,
+    /// number of seen packets
+    pub packets: u64,
+}
+
+impl TcStatsBasic64 {
+    pub(crate) fn new(basic: &TcStatsBasic, pkt64: Option<u64>) -> Self {
+        Self {
+            bytes: basic.bytes,
+            packets: pkt64.unwrap_or(basic.packets.into()),
+        }
+    }
+}
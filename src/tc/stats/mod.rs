@@ -3,13 +3,18 @@
 mod basic;
 mod compat;
 mod queue;
+mod rate_est;
 mod stats2;
 mod xstats;
 
 pub use self::{
-    basic::{TcStatsBasic, TcStatsBasicBuffer},
+    basic::{TcStatsBasic, TcStatsBasic64, TcStatsBasicBuffer},
     compat::{TcStats, TcStatsBuffer},
     queue::{TcStatsQueue, TcStatsQueueBuffer},
+    rate_est::{
+        TcStatsRateEst, TcStatsRateEst64, TcStatsRateEst64Buffer,
+        TcStatsRateEstBuffer,
+    },
     stats2::TcStats2,
-    xstats::TcXstats,
+    xstats::{TcClassXstats, TcXstats},
 };
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+
+use std::mem::size_of;
+
+use netlink_packet_core::{DecodeError, Emitable};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+/// Rate estimator statistics
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcStatsRateEst {
+    /// Current flow byte rate
+    pub bps: u32,
+    /// Current flow packet rate
+    pub pps: u32,
+}
+
+// kernel struct `gnet_stats_rate_est`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcStatsRateEstBuffer {
+    bps: u32,
+    pps: u32,
+}
+
+impl TcStatsRateEst {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) =
+            TcStatsRateEstBuffer::ref_from_prefix(payload).map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcStatsRateEstBuffer>(),
+                )
+            })?;
+        Ok(Self {
+            bps: raw.bps,
+            pps: raw.pps,
+        })
+    }
+}
+
+impl From<&TcStatsRateEst> for TcStatsRateEstBuffer {
+    fn from(value: &TcStatsRateEst) -> Self {
+        Self {
+            bps: value.bps,
+            pps: value.pps,
+        }
+    }
+}
+
+impl Emitable for TcStatsRateEst {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcStatsRateEstBuffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcStatsRateEstBuffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
+
+/// Rate estimator statistics with 64 bits counters, only provided by kernel
+/// when the rate does not fit into [`TcStatsRateEst`].
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct TcStatsRateEst64 {
+    /// Current flow byte rate
+    pub bps: u64,
+    /// Current flow packet rate
+    pub pps: u64,
+}
+
+// kernel struct `gnet_stats_rate_est64`
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
+    Immutable,
+    Unaligned,
+)]
+#[repr(C, packed)]
+pub struct TcStatsRateEst64Buffer {
+    bps: u64,
+    pps: u64,
+}
+
+impl TcStatsRateEst64 {
+    pub fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let (raw, _) = TcStatsRateEst64Buffer::ref_from_prefix(payload)
+            .map_err(|_| {
+                DecodeError::buffer_too_small(
+                    payload.len(),
+                    size_of::<TcStatsRateEst64Buffer>(),
+                )
+            })?;
+        Ok(Self {
+            bps: raw.bps,
+            pps: raw.pps,
+        })
+    }
+}
+
+impl From<&TcStatsRateEst64> for TcStatsRateEst64Buffer {
+    fn from(value: &TcStatsRateEst64) -> Self {
+        Self {
+            bps: value.bps,
+            pps: value.pps,
+        }
+    }
+}
+
+impl Emitable for TcStatsRateEst64 {
+    fn buffer_len(&self) -> usize {
+        size_of::<TcStatsRateEst64Buffer>()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let raw = TcStatsRateEst64Buffer::from(self);
+        buffer.copy_from_slice(raw.as_bytes());
+    }
+}
@@ -1,19 +1,23 @@
 // SPDX-License-Identifier: MIT
 
 use netlink_packet_core::{
-    DecodeError, DefaultNla, Emitable, Nla, NlaBuffer, Parseable,
-    ParseableParametrized,
+    emit_u64, parse_u64, DecodeError, DefaultNla, Emitable, ErrorContext, Nla,
+    NlaBuffer, Parseable, ParseableParametrized,
 };
 
-use super::{TcStatsBasic, TcStatsQueue, TcXstats};
+use super::{
+    TcStatsBasic, TcStatsBasic64, TcStatsQueue, TcStatsRateEst,
+    TcStatsRateEst64, TcXstats,
+};
 
 const TCA_STATS_BASIC: u16 = 1;
-// const TCA_STATS_RATE_EST: u16 = 2; // TODO
+const TCA_STATS_RATE_EST: u16 = 2;
 const TCA_STATS_QUEUE: u16 = 3;
 const TCA_STATS_APP: u16 = 4;
-// const TCA_STATS_RATE_EST64: u16 = 5; // TODO
-// const TCA_STATS_PAD: u16 = 6;
+const TCA_STATS_RATE_EST64: u16 = 5;
+const TCA_STATS_PAD: u16 = 6;
 const TCA_STATS_BASIC_HW: u16 = 7;
+const TCA_STATS_PKT64: u16 = 8;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
@@ -22,9 +26,53 @@ pub enum TcStats2 {
     Basic(TcStatsBasic),
     Queue(TcStatsQueue),
     BasicHw(TcStatsBasic),
+    RateEst(TcStatsRateEst),
+    RateEst64(TcStatsRateEst64),
+    /// Full packet counter of the preceding [`TcStats2::Basic`] or
+    /// [`TcStats2::BasicHw`], only provided when it does not fit into 32
+    /// bits
+    Pkt64(u64),
     Other(DefaultNla),
 }
 
+impl TcStats2 {
+    /// Software basic statistics with the TCA_STATS_PKT64 packet counter
+    /// merged in.
+    pub fn basic64(stats: &[Self]) -> Option<TcStatsBasic64> {
+        Self::merge_pkt64(stats, |s| match s {
+            Self::Basic(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Hardware basic statistics with the TCA_STATS_PKT64 packet counter
+    /// merged in.
+    pub fn basic_hw64(stats: &[Self]) -> Option<TcStatsBasic64> {
+        Self::merge_pkt64(stats, |s| match s {
+            Self::BasicHw(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // Kernel places TCA_STATS_PKT64 right after the basic statistics it
+    // belongs to, possibly preceded by a TCA_STATS_PAD alignment attribute.
+    fn merge_pkt64(
+        stats: &[Self],
+        f: impl Fn(&Self) -> Option<&TcStatsBasic>,
+    ) -> Option<TcStatsBasic64> {
+        let pos = stats.iter().position(|s| f(s).is_some())?;
+        let basic = f(&stats[pos])?;
+        let pkt64 = stats[pos + 1..]
+            .iter()
+            .find(|s| !matches!(s, Self::Other(nla) if nla.kind() == TCA_STATS_PAD))
+            .and_then(|s| match s {
+                Self::Pkt64(v) => Some(*v),
+                _ => None,
+            });
+        Some(TcStatsBasic64::new(basic, pkt64))
+    }
+}
+
 impl Nla for TcStats2 {
     fn value_len(&self) -> usize {
         match self {
@@ -32,6 +80,9 @@ impl Nla for TcStats2 {
             Self::Basic(v) => v.buffer_len(),
             Self::Queue(v) => v.buffer_len(),
             Self::BasicHw(v) => v.buffer_len(),
+            Self::RateEst(v) => v.buffer_len(),
+            Self::RateEst64(v) => v.buffer_len(),
+            Self::Pkt64(_) => 8,
             Self::Other(ref nla) => nla.value_len(),
         }
     }
@@ -42,6 +93,9 @@ impl Nla for TcStats2 {
             Self::Basic(v) => v.emit(buffer),
             Self::Queue(v) => v.emit(buffer),
             Self::BasicHw(v) => v.emit(buffer),
+            Self::RateEst(v) => v.emit(buffer),
+            Self::RateEst64(v) => v.emit(buffer),
+            Self::Pkt64(v) => emit_u64(buffer, *v).unwrap(),
             Self::Other(ref nla) => nla.emit_value(buffer),
         }
     }
@@ -52,6 +106,9 @@ impl Nla for TcStats2 {
             Self::Basic(_) => TCA_STATS_BASIC,
             Self::Queue(_) => TCA_STATS_QUEUE,
             Self::BasicHw(_) => TCA_STATS_BASIC_HW,
+            Self::RateEst(_) => TCA_STATS_RATE_EST,
+            Self::RateEst64(_) => TCA_STATS_RATE_EST64,
+            Self::Pkt64(_) => TCA_STATS_PKT64,
             Self::Other(ref nla) => nla.kind(),
         }
     }
@@ -64,13 +121,37 @@ where
     fn parse_with_param(
         buf: &NlaBuffer<&'a T>,
         kind: &str,
+    ) -> Result<Self, DecodeError> {
+        Self::parse_with_class(buf, kind, false)
+    }
+}
+
+impl TcStats2 {
+    pub(crate) fn parse_with_class<T: AsRef<[u8]> + ?Sized>(
+        buf: &NlaBuffer<&T>,
+        kind: &str,
+        class: bool,
     ) -> Result<Self, DecodeError> {
         let payload = buf.value();
         Ok(match buf.kind() {
-            TCA_STATS_APP => Self::App(TcXstats::parse_with_param(buf, kind)?),
+            TCA_STATS_APP => {
+                Self::App(TcXstats::parse_with_class(buf, kind, class)?)
+            }
             TCA_STATS_BASIC => Self::Basic(TcStatsBasic::parse(payload)?),
             TCA_STATS_QUEUE => Self::Queue(TcStatsQueue::parse(payload)?),
             TCA_STATS_BASIC_HW => Self::BasicHw(TcStatsBasic::parse(payload)?),
+            TCA_STATS_RATE_EST => Self::RateEst(
+                TcStatsRateEst::parse(payload)
+                    .context("failed to parse TCA_STATS_RATE_EST")?,
+            ),
+            TCA_STATS_RATE_EST64 => Self::RateEst64(
+                TcStatsRateEst64::parse(payload)
+                    .context("failed to parse TCA_STATS_RATE_EST64")?,
+            ),
+            TCA_STATS_PKT64 => Self::Pkt64(
+                parse_u64(payload)
+                    .context("failed to parse TCA_STATS_PKT64")?,
+            ),
             _ => Self::Other(DefaultNla::parse(buf)?),
         })
     }
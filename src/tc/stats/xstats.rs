@@ -4,12 +4,19 @@ use netlink_packet_core::{
     DecodeError, Emitable, NlaBuffer, Parseable, ParseableParametrized,
 };
 
-use crate::tc::{TcFqCodelXstats, TcQdiscFqCodel};
+use crate::tc::{
+    TcDrrXstats, TcFqCodelXstats, TcHfscXstats, TcHhfXstats, TcHtbXstats,
+    TcQdiscDrr, TcQdiscFqCodel, TcQdiscHfsc, TcQdiscHhf, TcQdiscHtb,
+    TcQdiscSfq, TcSfqXstats,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
 pub enum TcXstats {
     FqCodel(TcFqCodelXstats),
+    Hhf(TcHhfXstats),
+    /// Class statistics, found in the RTM_NEWTCLASS messages
+    Class(TcClassXstats),
     Other(Vec<u8>),
 }
 
@@ -17,6 +24,8 @@ impl Emitable for TcXstats {
     fn buffer_len(&self) -> usize {
         match self {
             Self::FqCodel(v) => v.buffer_len(),
+            Self::Hhf(v) => v.buffer_len(),
+            Self::Class(v) => v.buffer_len(),
             Self::Other(v) => v.len(),
         }
     }
@@ -24,6 +33,8 @@ impl Emitable for TcXstats {
     fn emit(&self, buffer: &mut [u8]) {
         match self {
             Self::FqCodel(v) => v.emit(buffer),
+            Self::Hhf(v) => v.emit(buffer),
+            Self::Class(v) => v.emit(buffer),
             Self::Other(v) => buffer.copy_from_slice(v.as_slice()),
         }
     }
@@ -41,7 +52,83 @@ where
             TcQdiscFqCodel::KIND => {
                 TcXstats::FqCodel(TcFqCodelXstats::parse(buf.value())?)
             }
+            TcQdiscHhf::KIND => TcXstats::Hhf(TcHhfXstats::parse(buf.value())?),
             _ => TcXstats::Other(buf.value().to_vec()),
         })
     }
 }
+
+impl TcXstats {
+    // The kernel uses the same attributes for the statistics of qdiscs and
+    // classes, only the message type tells them apart.
+    pub(crate) fn parse_with_class<T: AsRef<[u8]> + ?Sized>(
+        buf: &NlaBuffer<&T>,
+        kind: &str,
+        class: bool,
+    ) -> Result<TcXstats, DecodeError> {
+        if class {
+            Ok(TcXstats::Class(TcClassXstats::parse_with_param(
+                buf.value(),
+                kind,
+            )?))
+        } else {
+            TcXstats::parse_with_param(buf, kind)
+        }
+    }
+}
+
+/// Class statistics of the classful qdiscs
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TcClassXstats {
+    Htb(TcHtbXstats),
+    Hfsc(TcHfscXstats),
+    Drr(TcDrrXstats),
+    /// Statistics of a fq_codel flow, always
+    /// [`TcFqCodelXstats::Class`] unless unknown to this crate
+    FqCodel(TcFqCodelXstats),
+    Sfq(TcSfqXstats),
+    Other(Vec<u8>),
+}
+
+impl Emitable for TcClassXstats {
+    fn buffer_len(&self) -> usize {
+        match self {
+            Self::Htb(v) => v.buffer_len(),
+            Self::Hfsc(v) => v.buffer_len(),
+            Self::Drr(v) => v.buffer_len(),
+            Self::FqCodel(v) => v.buffer_len(),
+            Self::Sfq(v) => v.buffer_len(),
+            Self::Other(v) => v.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Htb(v) => v.emit(buffer),
+            Self::Hfsc(v) => v.emit(buffer),
+            Self::Drr(v) => v.emit(buffer),
+            Self::FqCodel(v) => v.emit(buffer),
+            Self::Sfq(v) => v.emit(buffer),
+            Self::Other(v) => buffer.copy_from_slice(v.as_slice()),
+        }
+    }
+}
+
+impl ParseableParametrized<[u8], &str> for TcClassXstats {
+    fn parse_with_param(
+        payload: &[u8],
+        kind: &str,
+    ) -> Result<TcClassXstats, DecodeError> {
+        Ok(match kind {
+            TcQdiscHtb::KIND => Self::Htb(TcHtbXstats::parse(payload)?),
+            TcQdiscHfsc::KIND => Self::Hfsc(TcHfscXstats::parse(payload)?),
+            TcQdiscDrr::KIND => Self::Drr(TcDrrXstats::parse(payload)?),
+            TcQdiscFqCodel::KIND => {
+                Self::FqCodel(TcFqCodelXstats::parse(payload)?)
+            }
+            TcQdiscSfq::KIND => Self::Sfq(TcSfqXstats::parse(payload)?),
+            _ => Self::Other(payload.to_vec()),
+        })
+    }
+}
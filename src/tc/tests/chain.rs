@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv4Addr;
+
+use netlink_packet_core::{Emitable, Parseable, ParseableParametrized};
+
+use crate::{
+    tc::{
+        TcAttribute, TcChainTemplate, TcFilterFlowerOption, TcFilterU32Option,
+        TcHandle, TcHeader, TcMessage, TcOption,
+    },
+    AddressFamily, RouteNetlinkMessage,
+};
+
+const RTM_NEWCHAIN: u16 = 100;
+
+// Setup:
+//      tc qdisc add dev eth1 clsact
+//      tc chain add dev eth1 ingress chain 1 protocol ip \
+//          flower dst_ip 0.0.0.0/16
+//
+// Hand-built reply to:
+//
+//      tc chain show dev eth1 ingress
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_chain_flower_template() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x00, 0x00, // handle 0:0
+        0x00, 0x00, 0xff, 0xff, // parent ffff:0 (handle of clsact qdisc)
+        0x00, 0x00, 0x00, 0x00, // info: 0
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x01, 0x00, 0x00, 0x00, // chain 1
+        0x0b, 0x00, // length 11
+        0x01, 0x00, // TCA_KIND
+        0x66, 0x6c, 0x6f, 0x77, 0x65, 0x72, 0x00, // "flower\0"
+        0x00, // pad
+        0x1c, 0x00, // length 28
+        0x02, 0x00, // TCA_OPTIONS
+        0x06, 0x00, // length 6
+        0x08, 0x00, // TCA_FLOWER_KEY_ETH_TYPE
+        0xff, 0xff, // template dumps the mask as key
+        0x00, 0x00, // pad
+        0x08, 0x00, // length 8
+        0x0c, 0x00, // TCA_FLOWER_KEY_IPV4_DST
+        0xff, 0xff, 0x00, 0x00, // 255.255.0.0
+        0x08, 0x00, // length 8
+        0x0d, 0x00, // TCA_FLOWER_KEY_IPV4_DST_MASK
+        0xff, 0xff, 0x00, 0x00, // 255.255.0.0
+    ];
+
+    let header = TcHeader {
+        family: AddressFamily::Unspec,
+        index: 6,
+        handle: TcHandle::UNSPEC,
+        // The kernel reports the handle of the qdisc owning the filter
+        // block, not the clsact ingress minor.
+        parent: TcHandle::from_major(0xffff),
+        info: 0,
+    };
+    let template = TcChainTemplate::new(
+        1,
+        "flower",
+        vec![
+            TcOption::Flower(TcFilterFlowerOption::EthType(0xffff)),
+            TcOption::Flower(TcFilterFlowerOption::Ipv4Dst(Ipv4Addr::new(
+                255, 255, 0, 0,
+            ))),
+            TcOption::Flower(TcFilterFlowerOption::Ipv4DstMask(Ipv4Addr::new(
+                255, 255, 0, 0,
+            ))),
+        ],
+    );
+    let expected = template.to_message(header);
+
+    let parsed =
+        RouteNetlinkMessage::parse_with_param(&raw, RTM_NEWCHAIN).unwrap();
+    assert_eq!(
+        parsed,
+        RouteNetlinkMessage::NewTrafficChain(expected.clone())
+    );
+
+    let parsed = TcMessage::parse(&raw).unwrap();
+    let parsed_template = TcChainTemplate::from_message(&parsed).unwrap();
+    assert_eq!(parsed_template, template);
+    parsed_template.validate().unwrap();
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+#[test]
+fn test_chain_without_template() {
+    let msg =
+        TcMessage::from_parts(TcHeader::default(), vec![TcAttribute::Chain(2)]);
+    assert_eq!(TcChainTemplate::from_message(&msg), None);
+}
+
+#[test]
+fn test_chain_template_validation() {
+    let template = TcChainTemplate::new(
+        1,
+        "flower",
+        vec![TcOption::U32(TcFilterU32Option::ClassId(TcHandle {
+            major: 1,
+            minor: 1,
+        }))],
+    );
+    assert!(template.validate().is_err());
+
+    let template = TcChainTemplate::new(1, "fq_codel", vec![]);
+    assert!(template.validate().is_err());
+
+    let template = TcChainTemplate::new(
+        1,
+        "u32",
+        vec![TcOption::U32(TcFilterU32Option::ClassId(TcHandle {
+            major: 1,
+            minor: 1,
+        }))],
+    );
+    assert!(template.validate().is_err());
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, ParseableParametrized};
+
+use crate::{
+    tc::{
+        TcAttribute, TcClassXstats, TcDrrXstats, TcFqCodelClStats,
+        TcFqCodelXstats, TcHandle, TcHeader, TcHfscXstats, TcHtbXstats,
+        TcMessage, TcSfqXstats, TcStats2, TcXstats,
+    },
+    AddressFamily, RouteNetlinkMessage,
+};
+
+const RTM_NEWQDISC: u16 = 36;
+const RTM_NEWTCLASS: u16 = 40;
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: htb default 10
+//      tc class add dev eth1 parent 1: classid 1:10 htb rate 10mbit
+//
+// Hand-built reply to:
+//
+//      tc -s class show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+//   * Only TCA_KIND, TCA_STATS_APP of TCA_STATS2 and TCA_XSTATS are kept.
+#[test]
+fn test_get_class_htb_xstats() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x10, 0x00, 0x01, 0x00, // handle 1:10
+        0x00, 0x00, 0x01, 0x00, // parent 1:0
+        0x00, 0x00, 0x01, 0x80, // info: leaf qdisc 8001:
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_KIND
+        0x68, 0x74, 0x62, 0x00, // "htb\0"
+        0x1c, 0x00, // length 28
+        0x07, 0x00, // TCA_STATS2
+        0x18, 0x00, // length 24
+        0x04, 0x00, // TCA_STATS_APP
+        0x2a, 0x00, 0x00, 0x00, // lends: 42
+        0x03, 0x00, 0x00, 0x00, // borrows: 3
+        0x00, 0x00, 0x00, 0x00, // giants: 0
+        0x40, 0x0d, 0x03, 0x00, // tokens: 200000
+        0xc0, 0xf2, 0xfc, 0xff, // ctokens: -200000
+        0x18, 0x00, // length 24
+        0x04, 0x00, // TCA_XSTATS
+        0x2a, 0x00, 0x00, 0x00, // lends: 42
+        0x03, 0x00, 0x00, 0x00, // borrows: 3
+        0x00, 0x00, 0x00, 0x00, // giants: 0
+        0x40, 0x0d, 0x03, 0x00, // tokens: 200000
+        0xc0, 0xf2, 0xfc, 0xff, // ctokens: -200000
+    ];
+
+    let xstats = TcXstats::Class(TcClassXstats::Htb(TcHtbXstats {
+        lends: 42,
+        borrows: 3,
+        giants: 0,
+        tokens: 200000,
+        ctokens: -200000,
+    }));
+    let expected = TcMessage::from_parts(
+        TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle::new(1, 0x10),
+            parent: TcHandle::from_major(1),
+            info: 0x80010000,
+        },
+        vec![
+            TcAttribute::Kind("htb".to_string()),
+            TcAttribute::Stats2(vec![TcStats2::App(xstats.clone())]),
+            TcAttribute::Xstats(xstats),
+        ],
+    );
+
+    assert_eq!(
+        RouteNetlinkMessage::parse_with_param(&raw, RTM_NEWTCLASS).unwrap(),
+        RouteNetlinkMessage::NewTrafficClass(expected.clone())
+    );
+
+    // The same attributes in a qdisc message are not class statistics
+    let RouteNetlinkMessage::NewQueueDiscipline(qdisc) =
+        RouteNetlinkMessage::parse_with_param(&raw, RTM_NEWQDISC).unwrap()
+    else {
+        panic!("not a qdisc message");
+    };
+    assert_eq!(
+        qdisc.attributes[2],
+        TcAttribute::Xstats(TcXstats::Other(raw[raw.len() - 20..].to_vec()))
+    );
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+#[test]
+fn test_class_xstats_parse_back() {
+    for (kind, orig) in [
+        (
+            "hfsc",
+            TcClassXstats::Hfsc(TcHfscXstats {
+                work: 1 << 33,
+                rtwork: 1500,
+                period: 7,
+                level: 1,
+            }),
+        ),
+        ("drr", TcClassXstats::Drr(TcDrrXstats { deficit: 1514 })),
+        ("sfq", TcClassXstats::Sfq(TcSfqXstats { allot: -62 })),
+        (
+            "fq_codel",
+            TcClassXstats::FqCodel(TcFqCodelXstats::Class(TcFqCodelClStats {
+                deficit: 1514,
+                ldelay: 20,
+                count: 1,
+                lastcount: 0,
+                dropping: 1,
+                drop_next: -100,
+            })),
+        ),
+    ] {
+        let mut buffer = vec![0; orig.buffer_len()];
+        orig.emit(&mut buffer);
+        let parsed =
+            TcClassXstats::parse_with_param(buffer.as_slice(), kind).unwrap();
+        assert_eq!(orig, parsed);
+    }
+}
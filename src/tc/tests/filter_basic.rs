@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, NlaBuffer, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcBasicPcnt, TcEmatch, TcEmatchCanFilter, TcEmatchCmp,
+        TcEmatchCmpAlign, TcEmatchIpset, TcEmatchLayer, TcEmatchMatcher,
+        TcEmatchMetaHdr, TcEmatchMetaId, TcEmatchMetaOption, TcEmatchMetaType,
+        TcEmatchMetaValue, TcEmatchNbyte, TcEmatchOperand, TcEmatchRelation,
+        TcEmatchTree, TcFilterBasicOption, TcHandle, TcHeader, TcMessage,
+        TcOption, TcU32Key,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: htb
+//      tc filter add dev eth1 parent 1: basic \
+//          match 'meta(nf_mark gt 24) and cmp(u16 at 0 layer 1 eq 0x1234)' \
+//          classid 1:2
+//
+// Hand-built reply to:
+//
+//      tc -s filter show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_filter_basic_ematches() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x01, 0x00, 0x00, 0x00, // handle 0x1
+        0x00, 0x00, 0x01, 0x00, // parent 1:
+        0x00, 0x03, 0x00, 0xc0, // info: pref 49152, protocol all
+        0x0a, 0x00, // length 10
+        0x01, 0x00, // TCA_KIND
+        0x62, 0x61, 0x73, 0x69, 0x63, 0x00, // "basic\0"
+        0x00, 0x00, // pad
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x00, 0x00, 0x00, 0x00, // chain 0
+        0x68, 0x00, // length 104
+        0x02, 0x00, // TCA_OPTIONS
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_BASIC_CLASSID
+        0x02, 0x00, 0x01, 0x00, // 1:2
+        0x14, 0x00, // length 20
+        0x05, 0x00, // TCA_BASIC_PCNT
+        0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rcnt 10
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rhit 3
+        0x48, 0x00, // length 72
+        0x02, 0x00, // TCA_BASIC_EMATCHES
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_EMATCH_TREE_HDR
+        0x02, 0x00, // nmatches 2
+        0x00, 0x00, // progid 0
+        0x3c, 0x00, // length 60
+        0x02, 0x00, // TCA_EMATCH_TREE_LIST
+        0x20, 0x00, // length 32
+        0x01, 0x00, // ematch 1
+        0x00, 0x00, // matchid 0
+        0x04, 0x00, // kind TCF_EM_META
+        0x01, 0x00, // flags TCF_EM_REL_AND
+        0x00, 0x00, // pad
+        0x0c, 0x00, // length 12
+        0x01, 0x00, // TCA_EM_META_HDR
+        0x0c, 0x10, // left: int nf_mark
+        0x00, // left shift 0
+        0x01, // left op TCF_EM_OPND_GT
+        0x00, 0x10, // right: int value
+        0x00, // right shift 0
+        0x00, // right op TCF_EM_OPND_EQ
+        0x08, 0x00, // length 8
+        0x03, 0x00, // TCA_EM_META_RVALUE
+        0x18, 0x00, 0x00, 0x00, // 24
+        0x18, 0x00, // length 24
+        0x02, 0x00, // ematch 2
+        0x00, 0x00, // matchid 0
+        0x01, 0x00, // kind TCF_EM_CMP
+        0x00, 0x00, // flags TCF_EM_REL_END
+        0x00, 0x00, // pad
+        0x34, 0x12, 0x00, 0x00, // val 0x1234
+        0xff, 0xff, 0x00, 0x00, // mask 0xffff
+        0x00, 0x00, // off 0
+        0x12, // align TCF_EM_ALIGN_U16, flags TCF_EM_CMP_TRANS
+        0x01, // layer TCF_LAYER_NETWORK, opnd TCF_EM_OPND_EQ
+    ];
+
+    let meta = TcEmatchMatcher::Meta(vec![
+        TcEmatchMetaOption::Hdr(TcEmatchMetaHdr {
+            left: TcEmatchMetaValue {
+                kind: TcEmatchMetaType::Int,
+                id: TcEmatchMetaId::Nfmark,
+                shift: 0,
+                op: TcEmatchOperand::Gt,
+            },
+            right: TcEmatchMetaValue {
+                kind: TcEmatchMetaType::Int,
+                id: TcEmatchMetaId::Value,
+                shift: 0,
+                op: TcEmatchOperand::Eq,
+            },
+        }),
+        TcEmatchMetaOption::Rvalue(24u32.to_ne_bytes().to_vec()),
+    ]);
+    let cmp = TcEmatchMatcher::Cmp(TcEmatchCmp {
+        val: 0x1234,
+        mask: 0xffff,
+        off: 0,
+        align: TcEmatchCmpAlign::U16,
+        trans: true,
+        layer: TcEmatchLayer::Network,
+        opnd: TcEmatchOperand::Eq,
+    });
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 0, minor: 1 },
+            parent: TcHandle { major: 1, minor: 0 },
+            info: 0xc0000300,
+        },
+        attributes: vec![
+            TcAttribute::Kind("basic".to_string()),
+            TcAttribute::Chain(0),
+            TcAttribute::Options(vec![
+                TcOption::Basic(TcFilterBasicOption::ClassId(TcHandle {
+                    major: 1,
+                    minor: 2,
+                })),
+                TcOption::Basic(TcFilterBasicOption::Pcnt(TcBasicPcnt {
+                    rcnt: 10,
+                    rhit: 3,
+                })),
+                TcOption::Basic(TcFilterBasicOption::Ematches(
+                    TcEmatchTree::from_ematches(vec![
+                        TcEmatch::new(meta).and(),
+                        TcEmatch::new(cmp),
+                    ]),
+                )),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// Equivalent of:
+//      basic match 'not (nbyte("ab" at 2 layer 2) or u32(u16 0x50 0xffff at 2))
+//          and canid(sff 0x123) and ipset(foo src)'
+#[test]
+fn test_filter_basic_ematches_parse_back() {
+    let mut container = TcEmatch::new(TcEmatchMatcher::Container(3)).invert();
+    container.relation = TcEmatchRelation::And;
+    let option =
+        TcFilterBasicOption::Ematches(TcEmatchTree::from_ematches(vec![
+            container,
+            TcEmatch::new(TcEmatchMatcher::Canid(vec![TcEmatchCanFilter {
+                can_id: 0x123,
+                can_mask: 0x7ff,
+            }]))
+            .and(),
+            TcEmatch::new(TcEmatchMatcher::Ipset(TcEmatchIpset {
+                index: 3,
+                dim: 1,
+                flags: 1 << 1,
+            })),
+            TcEmatch::new(TcEmatchMatcher::Nbyte(TcEmatchNbyte {
+                off: 2,
+                layer: TcEmatchLayer::Transport,
+                needle: b"ab".to_vec(),
+            }))
+            .or(),
+            TcEmatch::new(TcEmatchMatcher::U32(TcU32Key {
+                mask: 0xffff,
+                val: 0x50,
+                off: 0,
+                offmask: 0,
+            })),
+        ]));
+
+    let mut buffer = vec![0; option.buffer_len()];
+    option.emit(&mut buffer);
+    let parsed =
+        TcFilterBasicOption::parse(&NlaBuffer::new_checked(&buffer).unwrap())
+            .unwrap();
+    assert_eq!(parsed, option);
+}
+
+#[test]
+fn test_filter_basic_ematch_unknown_flags() {
+    let raw = vec![
+        0x10, 0x00, // length 16
+        0x01, 0x00, // index 1
+        0x00, 0x00, // matchid 0
+        0x00, 0x00, // TCF_EM_CONTAINER
+        0x0d,
+        0x00, // flags: TCF_EM_SIMPLE | TCF_EM_INVERT | TCF_EM_REL_AND
+        0x00, 0x00, // pad
+        0x02, 0x00, 0x00, 0x00, // container position 2
+    ];
+
+    let mut expected =
+        TcEmatch::new(TcEmatchMatcher::Container(2)).and().invert();
+    expected.index = 1;
+    expected.flags = 1 << 3;
+
+    let parsed =
+        TcEmatch::parse(&NlaBuffer::new_checked(&raw).unwrap()).unwrap();
+    assert_eq!(parsed, expected);
+
+    let mut buf = vec![0; expected.buffer_len()];
+    expected.emit(&mut buf);
+    assert_eq!(buf, raw);
+}
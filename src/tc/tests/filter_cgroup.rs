@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcEmatch, TcEmatchMatcher, TcEmatchTree,
+        TcFilterCgroupOption, TcHandle, TcHeader, TcMessage, TcOption,
+        TcU32Key,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: htb
+//      tc filter add dev eth1 parent 1: handle 1 prio 30 \
+//          cgroup match 'u32(u8 0x06 0xff at 9)'
+//
+// Hand-built reply to:
+//
+//      tc filter show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_filter_cgroup() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x01, 0x00, 0x00, 0x00, // handle 0x1
+        0x00, 0x00, 0x01, 0x00, // parent 1:
+        0x00, 0x03, 0x1e, 0x00, // info: pref 30, protocol all
+        0x0b, 0x00, // length 11
+        0x01, 0x00, // TCA_KIND
+        0x63, 0x67, 0x72, 0x6f, 0x75, 0x70, 0x00, // "cgroup\0"
+        0x00, // pad
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x00, 0x00, 0x00, 0x00, // chain 0
+        0x30, 0x00, // length 48
+        0x02, 0x00, // TCA_OPTIONS
+        0x2c, 0x00, // length 44
+        0x03, 0x00, // TCA_CGROUP_EMATCHES
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_EMATCH_TREE_HDR
+        0x01, 0x00, // nmatches 1
+        0x00, 0x00, // progid 0
+        0x20, 0x00, // length 32
+        0x02, 0x00, // TCA_EMATCH_TREE_LIST
+        0x1c, 0x00, // length 28
+        0x01, 0x00, // ematch 1
+        0x00, 0x00, // matchid 0
+        0x03, 0x00, // kind TCF_EM_U32
+        0x00, 0x00, // flags TCF_EM_REL_END
+        0x00, 0x00, // pad
+        0x00, 0xff, 0x00, 0x00, // mask 0x00ff0000
+        0x00, 0x06, 0x00, 0x00, // val 0x00060000
+        0x08, 0x00, 0x00, 0x00, // off 8
+        0x00, 0x00, 0x00, 0x00, // offmask 0
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 0, minor: 1 },
+            parent: TcHandle { major: 1, minor: 0 },
+            info: 0x1e0300,
+        },
+        attributes: vec![
+            TcAttribute::Kind("cgroup".to_string()),
+            TcAttribute::Chain(0),
+            TcAttribute::Options(vec![TcOption::Cgroup(
+                TcFilterCgroupOption::Ematches(TcEmatchTree::from_ematches(
+                    vec![TcEmatch::new(TcEmatchMatcher::U32(TcU32Key {
+                        mask: 0x00ff0000,
+                        val: 0x00060000,
+                        off: 8,
+                        offmask: 0,
+                    }))],
+                )),
+            )]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
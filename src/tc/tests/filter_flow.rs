@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcFilterFlowOption, TcFlowKeys, TcFlowMode, TcHandle,
+        TcHeader, TcMessage, TcOption,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: sfq
+//      tc filter add dev eth1 parent 1: handle 1 prio 1 flow hash \
+//          keys src,dst divisor 1024 perturb 10 baseclass 1:1
+//
+// Hand-built reply to:
+//
+//      tc filter show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_filter_flow_hash() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x01, 0x00, 0x00, 0x00, // handle 0x1
+        0x00, 0x00, 0x01, 0x00, // parent 1:
+        0x00, 0x03, 0x01, 0x00, // info: pref 1, protocol all
+        0x09, 0x00, // length 9
+        0x01, 0x00, // TCA_KIND
+        0x66, 0x6c, 0x6f, 0x77, 0x00, // "flow\0"
+        0x00, 0x00, 0x00, // pad
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x00, 0x00, 0x00, 0x00, // chain 0
+        0x2c, 0x00, // length 44
+        0x02, 0x00, // TCA_OPTIONS
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_FLOW_KEYS
+        0x03, 0x00, 0x00, 0x00, // FLOW_KEY_SRC | FLOW_KEY_DST
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_FLOW_MODE
+        0x01, 0x00, 0x00, 0x00, // FLOW_MODE_HASH
+        0x08, 0x00, // length 8
+        0x08, 0x00, // TCA_FLOW_DIVISOR
+        0x00, 0x04, 0x00, 0x00, // 1024
+        0x08, 0x00, // length 8
+        0x03, 0x00, // TCA_FLOW_BASECLASS
+        0x01, 0x00, 0x01, 0x00, // 1:1
+        0x08, 0x00, // length 8
+        0x0c, 0x00, // TCA_FLOW_PERTURB
+        0x0a, 0x00, 0x00, 0x00, // 10 seconds
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 0, minor: 1 },
+            parent: TcHandle { major: 1, minor: 0 },
+            info: 0x10300,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flow".to_string()),
+            TcAttribute::Chain(0),
+            TcAttribute::Options(vec![
+                TcOption::Flow(TcFilterFlowOption::Keys(
+                    TcFlowKeys::Src | TcFlowKeys::Dst,
+                )),
+                TcOption::Flow(TcFilterFlowOption::Mode(TcFlowMode::Hash)),
+                TcOption::Flow(TcFilterFlowOption::Divisor(1024)),
+                TcOption::Flow(TcFilterFlowOption::BaseClass(TcHandle {
+                    major: 1,
+                    minor: 1,
+                })),
+                TcOption::Flow(TcFilterFlowOption::Perturb(10)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
@@ -2,17 +2,21 @@
 
 use std::net::{Ipv4Addr, Ipv6Addr};
 
-use netlink_packet_core::{Emitable, Parseable};
+use netlink_packet_core::{Emitable, NlasIterator, Parseable};
 
 use crate::{
     tc::{
         TcAction, TcActionAttribute, TcActionGeneric, TcActionMirrorOption,
-        TcActionOption, TcActionType, TcAttribute, TcFilterFlowerMplsLseOption,
-        TcFilterFlowerMplsOption, TcFilterFlowerOption, TcHandle, TcHeader,
-        TcMessage, TcMirror, TcMirrorActionType, TcOption, TcStats2,
-        TcStatsBasic, TcStatsQueue, Tcf,
+        TcActionOption, TcActionType, TcAttribute, TcClsFlags,
+        TcFilterFlowerCfmOption, TcFilterFlowerCtState,
+        TcFilterFlowerEncOptErspan, TcFilterFlowerEncOptGeneve,
+        TcFilterFlowerEncOption, TcFilterFlowerKeyFlags,
+        TcFilterFlowerMplsLseOption, TcFilterFlowerMplsOption,
+        TcFilterFlowerOption, TcHandle, TcHeader, TcMessage, TcMirror,
+        TcMirrorActionType, TcOption, TcStats2, TcStatsBasic, TcStatsQueue,
+        Tcf,
     },
-    AddressFamily,
+    AddressFamily, IpProtocol,
 };
 
 /*
@@ -230,7 +234,9 @@ fn test_get_filter_flower_mac_ipv4() {
                     0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
                 ])),
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(6)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Tcp,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::Ipv4Src(
                     "11.0.0.0".parse::<Ipv4Addr>().unwrap(),
                 )),
@@ -243,7 +249,9 @@ fn test_get_filter_flower_mac_ipv4() {
                 TcOption::Flower(TcFilterFlowerOption::Ipv4DstMask(
                     "255.0.0.0".parse::<Ipv4Addr>().unwrap(),
                 )),
-                TcOption::Flower(TcFilterFlowerOption::Flags(1 << 3)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
                 TcOption::Flower(TcFilterFlowerOption::Actions(vec![
                     get_mirred_action(2),
@@ -322,7 +330,9 @@ fn test_get_filter_flower_ipv6_ports() {
             TcAttribute::Chain(0),
             TcAttribute::Options(vec![
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x86DD)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(17)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Udp,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::IpTtl(63)),
                 TcOption::Flower(TcFilterFlowerOption::IpTtlMask(0xFF)),
                 TcOption::Flower(TcFilterFlowerOption::Ipv6Src(
@@ -341,7 +351,9 @@ fn test_get_filter_flower_ipv6_ports() {
                 TcOption::Flower(TcFilterFlowerOption::UdpSrcMask(65535)),
                 TcOption::Flower(TcFilterFlowerOption::UdpDst(137)),
                 TcOption::Flower(TcFilterFlowerOption::UdpDstMask(65535)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(1 << 3)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
                 TcOption::Flower(TcFilterFlowerOption::Actions(vec![
                     get_mirred_action(3),
@@ -398,14 +410,18 @@ fn test_get_filter_flower_ipv4_tcp_portrange() {
             TcAttribute::Chain(5),
             TcAttribute::Options(vec![
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(6)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Tcp,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::TcpFlags(0x01)),
                 TcOption::Flower(TcFilterFlowerOption::TcpFlagsMask(0x0F)),
                 TcOption::Flower(TcFilterFlowerOption::PortDstMin(22)),
                 TcOption::Flower(TcFilterFlowerOption::PortDstMax(3333)),
                 TcOption::Flower(TcFilterFlowerOption::PortSrcMin(11)),
                 TcOption::Flower(TcFilterFlowerOption::PortSrcMax(2222)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -457,12 +473,16 @@ fn test_get_filter_flower_ipv4_icmp() {
             TcAttribute::Chain(5),
             TcAttribute::Options(vec![
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(1)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Icmp,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv4Type(6)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv4TypeMask(252)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv4Code(8)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv4CodeMask(254)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -514,12 +534,16 @@ fn test_get_filter_flower_ipv6_icmp() {
             TcAttribute::Chain(5),
             TcAttribute::Options(vec![
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x86DD)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(58)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Other(58),
+                )),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv6Type(6)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv6TypeMask(252)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv6Code(8)),
                 TcOption::Flower(TcFilterFlowerOption::Icmpv6CodeMask(254)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -576,9 +600,15 @@ fn test_get_filter_flower_ct() {
             TcAttribute::Chain(5),
             TcAttribute::Options(vec![
                 TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
-                TcOption::Flower(TcFilterFlowerOption::IpProto(6)),
-                TcOption::Flower(TcFilterFlowerOption::CtState(8)),
-                TcOption::Flower(TcFilterFlowerOption::CtStateMask(8)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Tcp,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::CtState(
+                    TcFilterFlowerCtState::Tracked,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::CtStateMask(
+                    TcFilterFlowerCtState::Tracked,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::CtZone(11)),
                 TcOption::Flower(TcFilterFlowerOption::CtZoneMask(0xFFFF)),
                 TcOption::Flower(TcFilterFlowerOption::CtMark(3456)),
@@ -590,7 +620,9 @@ fn test_get_filter_flower_ct() {
                     255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
                     255, 255, 255, 255,
                 ])),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -674,7 +706,9 @@ fn test_get_filter_flower_arp() {
                 TcOption::Flower(TcFilterFlowerOption::ArpThaMask([
                     0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F,
                 ])),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -733,7 +767,9 @@ fn test_get_filter_flower_vlan() {
                 TcOption::Flower(TcFilterFlowerOption::CvlanPrio(3)),
                 TcOption::Flower(TcFilterFlowerOption::VlanEthType(0x8100)),
                 TcOption::Flower(TcFilterFlowerOption::CvlanEthType(0x0800)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -788,7 +824,9 @@ fn test_get_filter_flower_mpls_uc_single() {
                 TcOption::Flower(TcFilterFlowerOption::MplsTc(5)),
                 TcOption::Flower(TcFilterFlowerOption::MplsLabel(132)),
                 TcOption::Flower(TcFilterFlowerOption::MplsBos(1)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -861,7 +899,9 @@ fn test_get_filter_flower_mpls_uc_lse() {
                         TcFilterFlowerMplsLseOption::LseLabel(44),
                     ]),
                 ])),
-                TcOption::Flower(TcFilterFlowerOption::Flags(8)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
             ]),
         ],
@@ -953,7 +993,9 @@ fn test_get_filter_flower_ipv4_enc() {
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTosMask(255)),
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTtl(62)),
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTtlMask(255)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(1 << 3)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
                 TcOption::Flower(TcFilterFlowerOption::Actions(vec![
                     get_mirred_action(3),
@@ -1052,7 +1094,9 @@ fn test_get_filter_flower_ipv6_enc() {
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTosMask(255)),
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTtl(62)),
                 TcOption::Flower(TcFilterFlowerOption::EncKeyIpTtlMask(255)),
-                TcOption::Flower(TcFilterFlowerOption::Flags(1 << 3)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
                 TcOption::Flower(TcFilterFlowerOption::Actions(vec![
                     get_mirred_action(3),
@@ -1069,3 +1113,366 @@ fn test_get_filter_flower_ipv6_enc() {
 
     assert_eq!(buf, raw);
 }
+
+// TC rule:
+//      tc filter add dev veth1 ingress handle 0x1 chain 5 \
+//        proto ip flower enc_key_id 11 \
+//        geneve_opts 0102:80:00880022/ffff:ff:ffffffff,\
+//          0103:81:00880033/ffff:ff:ffffffff
+#[test]
+fn test_get_filter_flower_enc_opts_geneve() {
+    let raw = vec![
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0xF2, 0xFF, 0xFF, 0xFF, 0x08, 0x00, 0x00, 0xC0, 0x0B, 0x00, 0x01, 0x00,
+        0x66, 0x6C, 0x6F, 0x77, 0x65, 0x72, 0x00, 0x00, 0x08, 0x00, 0x0B, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x9C, 0x00, 0x02, 0x00, 0x06, 0x00, 0x08, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x00, 0x0B,
+        0x3C, 0x00, 0x54, 0x00, 0x1C, 0x00, 0x01, 0x00, 0x06, 0x00, 0x01, 0x00,
+        0x01, 0x02, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00, 0x80, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x03, 0x00, 0x00, 0x88, 0x00, 0x22, 0x1C, 0x00, 0x01, 0x00,
+        0x06, 0x00, 0x01, 0x00, 0x01, 0x03, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00,
+        0x81, 0x00, 0x00, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x88, 0x00, 0x33,
+        0x3C, 0x00, 0x55, 0x00, 0x1C, 0x00, 0x01, 0x00, 0x06, 0x00, 0x01, 0x00,
+        0xFF, 0xFF, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x03, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x1C, 0x00, 0x01, 0x00,
+        0x06, 0x00, 0x01, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00,
+        0xFF, 0x00, 0x00, 0x00, 0x08, 0x00, 0x03, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+        0x08, 0x00, 0x16, 0x00, 0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x56, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let geneve_mask = TcFilterFlowerEncOption::Geneve(vec![
+        TcFilterFlowerEncOptGeneve::Class(0xffff),
+        TcFilterFlowerEncOptGeneve::Type(0xff),
+        TcFilterFlowerEncOptGeneve::Data(vec![0xff, 0xff, 0xff, 0xff]),
+    ]);
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 8,
+            handle: TcHandle {
+                major: 0,
+                minor: 0x1,
+            },
+            parent: TcHandle {
+                major: 65535,
+                minor: 65522,
+            },
+            info: 3221225480,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flower".to_string()),
+            TcAttribute::Chain(5),
+            TcAttribute::Options(vec![
+                TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
+                TcOption::Flower(TcFilterFlowerOption::EncKeyId(11)),
+                TcOption::Flower(TcFilterFlowerOption::EncOpts(vec![
+                    TcFilterFlowerEncOption::Geneve(vec![
+                        TcFilterFlowerEncOptGeneve::Class(0x0102),
+                        TcFilterFlowerEncOptGeneve::Type(0x80),
+                        TcFilterFlowerEncOptGeneve::Data(vec![
+                            0x00, 0x88, 0x00, 0x22,
+                        ]),
+                    ]),
+                    TcFilterFlowerEncOption::Geneve(vec![
+                        TcFilterFlowerEncOptGeneve::Class(0x0103),
+                        TcFilterFlowerEncOptGeneve::Type(0x81),
+                        TcFilterFlowerEncOptGeneve::Data(vec![
+                            0x00, 0x88, 0x00, 0x33,
+                        ]),
+                    ]),
+                ])),
+                TcOption::Flower(TcFilterFlowerOption::EncOptsMask(vec![
+                    geneve_mask.clone(),
+                    geneve_mask,
+                ])),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// TC rule:
+//      tc filter add dev veth1 ingress handle 0x1 chain 5 \
+//        proto ip flower enc_key_id 11 \
+//        erspan_opts 2:0:1:7/2:0:1:3f enc_flags csum
+#[test]
+fn test_get_filter_flower_enc_opts_erspan() {
+    let raw = vec![
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0xF2, 0xFF, 0xFF, 0xFF, 0x08, 0x00, 0x00, 0xC0, 0x0B, 0x00, 0x01, 0x00,
+        0x66, 0x6C, 0x6F, 0x77, 0x65, 0x72, 0x00, 0x00, 0x08, 0x00, 0x0B, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x74, 0x00, 0x02, 0x00, 0x06, 0x00, 0x08, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x00, 0x0B,
+        0x20, 0x00, 0x54, 0x00, 0x1C, 0x00, 0x03, 0x00, 0x05, 0x00, 0x01, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x05, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x04, 0x00, 0x07, 0x00, 0x00, 0x00, 0x20, 0x00, 0x55, 0x00,
+        0x1C, 0x00, 0x03, 0x00, 0x05, 0x00, 0x01, 0x00, 0xFF, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x00, 0x04, 0x00,
+        0x3F, 0x00, 0x00, 0x00, 0x08, 0x00, 0x6E, 0x00, 0x00, 0x00, 0x00, 0x04,
+        0x08, 0x00, 0x6F, 0x00, 0x00, 0x00, 0x00, 0x04, 0x08, 0x00, 0x16, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x56, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 8,
+            handle: TcHandle {
+                major: 0,
+                minor: 0x1,
+            },
+            parent: TcHandle {
+                major: 65535,
+                minor: 65522,
+            },
+            info: 3221225480,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flower".to_string()),
+            TcAttribute::Chain(5),
+            TcAttribute::Options(vec![
+                TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
+                TcOption::Flower(TcFilterFlowerOption::EncKeyId(11)),
+                TcOption::Flower(TcFilterFlowerOption::EncOpts(vec![
+                    TcFilterFlowerEncOption::Erspan(vec![
+                        TcFilterFlowerEncOptErspan::Ver(2),
+                        TcFilterFlowerEncOptErspan::Dir(1),
+                        TcFilterFlowerEncOptErspan::Hwid(7),
+                    ]),
+                ])),
+                TcOption::Flower(TcFilterFlowerOption::EncOptsMask(vec![
+                    TcFilterFlowerEncOption::Erspan(vec![
+                        TcFilterFlowerEncOptErspan::Ver(0xff),
+                        TcFilterFlowerEncOptErspan::Dir(1),
+                        TcFilterFlowerEncOptErspan::Hwid(0x3f),
+                    ]),
+                ])),
+                TcOption::Flower(TcFilterFlowerOption::EncFlags(
+                    TcFilterFlowerKeyFlags::TunnelCsum,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::EncFlagsMask(
+                    TcFilterFlowerKeyFlags::TunnelCsum,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// TC rule:
+//      tc filter add dev veth1 ingress handle 0x1 chain 5 \
+//        proto ppp_ses flower pppoe_sid 1234 ppp_proto ip
+#[test]
+fn test_get_filter_flower_pppoe() {
+    let raw = vec![
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0xF2, 0xFF, 0xFF, 0xFF, 0x88, 0x64, 0x00, 0xC0, 0x0B, 0x00, 0x01, 0x00,
+        0x66, 0x6C, 0x6F, 0x77, 0x65, 0x72, 0x00, 0x00, 0x08, 0x00, 0x0B, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x2C, 0x00, 0x02, 0x00, 0x06, 0x00, 0x08, 0x00,
+        0x88, 0x64, 0x00, 0x00, 0x06, 0x00, 0x67, 0x00, 0x04, 0xD2, 0x00, 0x00,
+        0x06, 0x00, 0x68, 0x00, 0x00, 0x21, 0x00, 0x00, 0x08, 0x00, 0x16, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x56, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 8,
+            handle: TcHandle {
+                major: 0,
+                minor: 0x1,
+            },
+            parent: TcHandle {
+                major: 65535,
+                minor: 65522,
+            },
+            info: 3221251208,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flower".to_string()),
+            TcAttribute::Chain(5),
+            TcAttribute::Options(vec![
+                TcOption::Flower(TcFilterFlowerOption::EthType(0x8864)),
+                TcOption::Flower(TcFilterFlowerOption::PppoeSid(1234)),
+                TcOption::Flower(TcFilterFlowerOption::PppProto(0x0021)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// TC rule:
+//      tc filter add dev veth1 ingress handle 0x1 chain 5 \
+//        proto cfm flower cfm mdl 5 op 1 l2_miss 1
+#[test]
+fn test_get_filter_flower_cfm() {
+    let raw = vec![
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0xF2, 0xFF, 0xFF, 0xFF, 0x89, 0x02, 0x00, 0xC0, 0x0B, 0x00, 0x01, 0x00,
+        0x66, 0x6C, 0x6F, 0x77, 0x65, 0x72, 0x00, 0x00, 0x08, 0x00, 0x0B, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x38, 0x00, 0x02, 0x00, 0x06, 0x00, 0x08, 0x00,
+        0x89, 0x02, 0x00, 0x00, 0x14, 0x00, 0x6B, 0x80, 0x05, 0x00, 0x01, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x6A, 0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x16, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x56, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 8,
+            handle: TcHandle {
+                major: 0,
+                minor: 0x1,
+            },
+            parent: TcHandle {
+                major: 65535,
+                minor: 65522,
+            },
+            info: 3221226121,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flower".to_string()),
+            TcAttribute::Chain(5),
+            TcAttribute::Options(vec![
+                TcOption::Flower(TcFilterFlowerOption::EthType(0x8902)),
+                TcOption::Flower(TcFilterFlowerOption::Cfm(vec![
+                    TcFilterFlowerCfmOption::MdLevel(5),
+                    TcFilterFlowerCfmOption::Opcode(1),
+                ])),
+                TcOption::Flower(TcFilterFlowerOption::L2Miss(true)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// TC rule:
+//      tc filter add dev veth1 ingress handle 0x1 chain 5 \
+//        proto ip flower ip_proto esp spi 0x100
+#[test]
+fn test_get_filter_flower_ipsec_spi() {
+    let raw = vec![
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0xF2, 0xFF, 0xFF, 0xFF, 0x08, 0x00, 0x00, 0xC0, 0x0B, 0x00, 0x01, 0x00,
+        0x66, 0x6C, 0x6F, 0x77, 0x65, 0x72, 0x00, 0x00, 0x08, 0x00, 0x0B, 0x00,
+        0x05, 0x00, 0x00, 0x00, 0x34, 0x00, 0x02, 0x00, 0x06, 0x00, 0x08, 0x00,
+        0x08, 0x00, 0x00, 0x00, 0x05, 0x00, 0x09, 0x00, 0x32, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x6C, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08, 0x00, 0x6D, 0x00,
+        0xFF, 0xFF, 0xFF, 0xFF, 0x08, 0x00, 0x16, 0x00, 0x08, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x56, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 8,
+            handle: TcHandle {
+                major: 0,
+                minor: 0x1,
+            },
+            parent: TcHandle {
+                major: 65535,
+                minor: 65522,
+            },
+            info: 3221225480,
+        },
+        attributes: vec![
+            TcAttribute::Kind("flower".to_string()),
+            TcAttribute::Chain(5),
+            TcAttribute::Options(vec![
+                TcOption::Flower(TcFilterFlowerOption::EthType(0x0800)),
+                TcOption::Flower(TcFilterFlowerOption::IpProto(
+                    IpProtocol::Esp,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::Spi(0x100)),
+                TcOption::Flower(TcFilterFlowerOption::SpiMask(0xffffffff)),
+                TcOption::Flower(TcFilterFlowerOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
+                TcOption::Flower(TcFilterFlowerOption::InHwCount(0)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// `ct_state +trk+est` of tc-flower(8)
+#[test]
+fn test_emit_filter_flower_ct_state_trk_est() {
+    let raw = vec![
+        0x06, 0x00, 0x5B, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x06, 0x00, 0x5C, 0x00,
+        0x0A, 0x00, 0x00, 0x00,
+    ];
+
+    let state =
+        TcFilterFlowerCtState::Tracked | TcFilterFlowerCtState::Established;
+    let expected = vec![
+        TcFilterFlowerOption::CtState(state),
+        TcFilterFlowerOption::CtStateMask(state),
+    ];
+
+    let mut buf = vec![0; expected.as_slice().buffer_len()];
+
+    expected.as_slice().emit(&mut buf);
+
+    assert_eq!(buf, raw);
+
+    let parsed: Vec<TcFilterFlowerOption> = NlasIterator::new(&raw)
+        .map(|nla| TcFilterFlowerOption::parse(&nla.unwrap()).unwrap())
+        .collect();
+    assert_eq!(parsed, expected);
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcFilterFwOption, TcHandle, TcHeader, TcMessage, TcOption,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: htb
+//      tc filter add dev eth1 parent 1: protocol ip prio 10 \
+//          handle 5/0xff fw classid 1:5
+//
+// Hand-built reply to:
+//
+//      tc filter show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_filter_fw() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x05, 0x00, 0x00, 0x00, // handle 0x5
+        0x00, 0x00, 0x01, 0x00, // parent 1:
+        0x08, 0x00, 0x0a, 0x00, // info: pref 10, protocol ip
+        0x07, 0x00, // length 7
+        0x01, 0x00, // TCA_KIND
+        0x66, 0x77, 0x00, 0x00, // "fw\0" and 1 byte pad
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x00, 0x00, 0x00, 0x00, // chain 0
+        0x14, 0x00, // length 20
+        0x02, 0x00, // TCA_OPTIONS
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_FW_CLASSID
+        0x05, 0x00, 0x01, 0x00, // 1:5
+        0x08, 0x00, // length 8
+        0x05, 0x00, // TCA_FW_MASK
+        0xff, 0x00, 0x00, 0x00, // 0xff
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 0, minor: 5 },
+            parent: TcHandle { major: 1, minor: 0 },
+            info: 0xa0008,
+        },
+        attributes: vec![
+            TcAttribute::Kind("fw".to_string()),
+            TcAttribute::Chain(0),
+            TcAttribute::Options(vec![
+                TcOption::Fw(TcFilterFwOption::ClassId(TcHandle {
+                    major: 1,
+                    minor: 5,
+                })),
+                TcOption::Fw(TcFilterFwOption::Mask(0xff)),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
@@ -5,11 +5,12 @@ use netlink_packet_core::{Emitable, Parseable};
 use crate::{
     tc::{
         TcAction, TcActionAttribute, TcActionGeneric, TcActionMirrorOption,
-        TcActionOption, TcActionType, TcAttribute, TcFilterMatchAllOption,
-        TcHandle, TcHeader, TcMessage, TcMirror, TcMirrorActionType, TcOption,
-        TcStats2, TcStatsBasic, TcStatsQueue, Tcf,
+        TcActionOption, TcActionType, TcAttribute, TcClsFlags, TcFilterInfo,
+        TcFilterMatchAllOption, TcHandle, TcHeader, TcMessage, TcMirror,
+        TcMirrorActionType, TcOption, TcStats2, TcStatsBasic, TcStatsQueue,
+        Tcf,
     },
-    AddressFamily,
+    AddressFamily, EthernetProtocol,
 };
 
 // Setup:
@@ -35,7 +36,7 @@ fn test_get_filter_matchall() {
         0x32, 0x00, 0x00, 0x00, // iface index 50
         0x01, 0x00, 0x00, 0x00, // handle 0:1
         0x00, 0x00, 0x01, 0x00, // parent 1:0
-        0x00, 0x03, 0x00, 0xc0, // info: 3221226240, TODO: no idea
+        0x00, 0x03, 0x00, 0xc0, // info: pref 49152, protocol all
         0x0d, 0x00, // length 13
         0x01, 0x00, // TCA_KIND
         0x6d, 0x61, 0x74, 0x63, 0x68, 0x61, 0x6c, 0x6c, 0x00, 0x00, 0x00, 0x00,
@@ -106,13 +107,15 @@ fn test_get_filter_matchall() {
             index: 50,
             handle: TcHandle { major: 0, minor: 1 },
             parent: TcHandle { major: 1, minor: 0 },
-            info: 3221226240, // TODO(Gris Ge): What's this
+            info: 0xc0000300,
         },
         attributes: vec![
             TcAttribute::Kind("matchall".to_string()),
             TcAttribute::Chain(0),
             TcAttribute::Options(vec![
-                TcOption::MatchAll(TcFilterMatchAllOption::Flags(8)),
+                TcOption::MatchAll(TcFilterMatchAllOption::Flags(
+                    TcClsFlags::NotInHw,
+                )),
                 TcOption::MatchAll(TcFilterMatchAllOption::Pnct(vec![
                     1, 0, 0, 0, 0, 0, 0, 0, // TODO(Gris Ge)
                 ])),
@@ -171,6 +174,14 @@ fn test_get_filter_matchall() {
     };
 
     assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+    assert_eq!(
+        expected.header.filter_info(),
+        TcFilterInfo::new(49152, EthernetProtocol::All)
+    );
+    assert_eq!(
+        expected.header.filter_info().to_string(),
+        "prio 49152 protocol all"
+    );
 
     let mut buf = vec![0; expected.buffer_len()];
 
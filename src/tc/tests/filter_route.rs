@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcFilterRouteOption, TcHandle, TcHeader, TcMessage,
+        TcOption,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: htb
+//      tc filter add dev eth1 parent 1: protocol ip prio 20 \
+//          route from 3 to 10 classid 1:10
+//
+// Hand-built reply to:
+//
+//      tc filter show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_filter_route() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x0a, 0x00, 0x03,
+        0x00, // handle 0x3000a: from realm 3, to realm 10
+        0x00, 0x00, 0x01, 0x00, // parent 1:
+        0x08, 0x00, 0x14, 0x00, // info: pref 20, protocol ip
+        0x0a, 0x00, // length 10
+        0x01, 0x00, // TCA_KIND
+        0x72, 0x6f, 0x75, 0x74, 0x65, 0x00, // "route\0"
+        0x00, 0x00, // pad
+        0x08, 0x00, // length 8
+        0x0b, 0x00, // TCA_CHAIN
+        0x00, 0x00, 0x00, 0x00, // chain 0
+        0x1c, 0x00, // length 28
+        0x02, 0x00, // TCA_OPTIONS
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_ROUTE4_TO
+        0x0a, 0x00, 0x00, 0x00, // realm 10
+        0x08, 0x00, // length 8
+        0x03, 0x00, // TCA_ROUTE4_FROM
+        0x03, 0x00, 0x00, 0x00, // realm 3
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_ROUTE4_CLASSID
+        0x10, 0x00, 0x01, 0x00, // 1:10
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle {
+                major: 3,
+                minor: 10,
+            },
+            parent: TcHandle { major: 1, minor: 0 },
+            info: 0x140008,
+        },
+        attributes: vec![
+            TcAttribute::Kind("route".to_string()),
+            TcAttribute::Chain(0),
+            TcAttribute::Options(vec![
+                TcOption::Route(TcFilterRouteOption::To(10)),
+                TcOption::Route(TcFilterRouteOption::From(3)),
+                TcOption::Route(TcFilterRouteOption::ClassId(TcHandle {
+                    major: 1,
+                    minor: 0x10,
+                })),
+            ]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
@@ -2,15 +2,17 @@
 
 use std::net::Ipv4Addr;
 
-use netlink_packet_core::{Emitable, Parseable};
+use netlink_packet_core::{Emitable, NlaBuffer, Parseable};
 
 use crate::{
     tc::{
         filters::{TcU32OptionFlags, TcU32SelectorFlags},
-        TcAttribute, TcFilterU32Option, TcHandle, TcHeader, TcMessage,
-        TcOption, TcU32Key, TcU32Selector, TcU32SelectorBuffer,
+        TcActionPoliceOption, TcActionType, TcAttribute, TcFilterU32Option,
+        TcHandle, TcHeader, TcMessage, TcOption, TcPolice, TcRateSpec,
+        TcU32Handle, TcU32HashTable, TcU32Key, TcU32Mark, TcU32Node, TcU32Pcnt,
+        TcU32Selector, TcU32SelectorBuffer,
     },
-    AddressFamily,
+    AddressFamily, IpProtocol,
 };
 
 // Setup:
@@ -88,7 +90,9 @@ fn test_get_filter_u32() {
                         },
                     ],
                 })),
-                TcOption::U32(TcFilterU32Option::Hash(u32::from_be(0x80))),
+                TcOption::U32(TcFilterU32Option::Hash(TcU32Handle::table(
+                    0x800,
+                ))),
                 TcOption::U32(TcFilterU32Option::ClassId(TcHandle {
                     major: 1,
                     minor: 4,
@@ -96,7 +100,11 @@ fn test_get_filter_u32() {
                 TcOption::U32(TcFilterU32Option::Flags(
                     TcU32OptionFlags::NotInHw,
                 )),
-                TcOption::U32(TcFilterU32Option::Pnct(vec![0; 32])), // TODO
+                TcOption::U32(TcFilterU32Option::Pcnt(TcU32Pcnt {
+                    rcnt: 0,
+                    rhit: 0,
+                    kcnts: vec![0, 0],
+                })),
             ]),
         ],
     };
@@ -129,3 +137,375 @@ fn test_tcu32_selector_invalid_nkeys() {
     ];
     assert!(TcU32SelectorBuffer::new_checked(&buffer).is_err());
 }
+
+// Police of the u32 filter uses the police action options:
+//      tc filter add dev veth1 parent 1: protocol ip prio 10 u32 \
+//        match ip src 1.1.1.1/32 police rate 1mbit burst 100k drop
+#[test]
+fn test_tcu32_police_parse_back() {
+    let orig = TcFilterU32Option::Police(vec![
+        TcActionPoliceOption::Tbf(TcPolice {
+            action: TcActionType::Shot,
+            burst: 819200,
+            rate: TcRateSpec {
+                cell_log: 3,
+                rate: 125000,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        TcActionPoliceOption::Rate((0..256).map(|i| i * 64).collect()),
+    ]);
+    let mut buffer = vec![0; orig.buffer_len()];
+    orig.emit(&mut buffer);
+    let parsed = TcFilterU32Option::parse(
+        &NlaBuffer::new_checked(buffer.as_slice()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orig, parsed);
+}
+
+// The selector helpers build the same selector as the iproute2 command of
+// `test_get_filter_u32`:
+//      match ip dst 192.168.190.7 match ip dport 36000 0xffff flowid 1:4
+#[test]
+fn test_tcu32_selector_helpers() {
+    let sel = TcU32Selector::default()
+        .match_ip_dst(Ipv4Addr::new(192, 168, 190, 7), 32)
+        .and_then(|s| s.match_ip_dport(36000))
+        .unwrap()
+        .terminal();
+
+    assert_eq!(
+        sel,
+        TcU32Selector {
+            flags: TcU32SelectorFlags::Terminal,
+            nkeys: 2,
+            keys: vec![
+                TcU32Key {
+                    mask: 0xffffffff,
+                    val: u32::from_be_bytes(
+                        Ipv4Addr::new(192, 168, 190, 7).octets(),
+                    ),
+                    off: 16,
+                    offmask: 0,
+                },
+                TcU32Key {
+                    mask: 0x0000ffff,
+                    val: 36000,
+                    off: 20,
+                    offmask: 0,
+                },
+            ],
+            ..Default::default()
+        }
+    );
+}
+
+// Matches on the same 32 bit word are merged in a single key, matches
+// relative to the next header use an offset mask of -1:
+//      match ip src 10.0.0.0/8 match ip protocol 6 0xff \
+//      match ip sport 1024 0xffff match ip dport 80 0xffff \
+//      match u8 0x02 0x02 at nexthdr+13
+#[test]
+fn test_tcu32_selector_helpers_merge_and_nexthdr() {
+    let sel = TcU32Selector::default()
+        .match_ip_src(Ipv4Addr::new(10, 1, 2, 3), 8)
+        .and_then(|s| s.match_ip_protocol(IpProtocol::Tcp))
+        .and_then(|s| s.match_ip_sport(1024))
+        .and_then(|s| s.match_ip_dport(80))
+        .and_then(|s| s.match_tcp_flags(0x02, 0x02))
+        .unwrap();
+
+    assert_eq!(
+        sel.keys,
+        vec![
+            TcU32Key {
+                mask: 0xff000000,
+                val: 0x0a000000,
+                off: 12,
+                offmask: 0,
+            },
+            TcU32Key {
+                mask: 0x00ff0000,
+                val: 0x00060000,
+                off: 8,
+                offmask: 0,
+            },
+            TcU32Key {
+                mask: 0xffffffff,
+                val: (1024 << 16) | 80,
+                off: 20,
+                offmask: 0,
+            },
+            TcU32Key {
+                mask: 0x00020000,
+                val: 0x00020000,
+                off: 12,
+                offmask: -1,
+            },
+        ]
+    );
+    assert_eq!(sel.nkeys, 4);
+}
+
+// iproute2 rejects these matches instead of building a selector which
+// never matches or whose key count wraps.
+#[test]
+fn test_tcu32_selector_helpers_errors() {
+    let sel = TcU32Selector::default()
+        .match_ip_dst(Ipv4Addr::new(10, 0, 0, 1), 32)
+        .unwrap();
+    assert!(sel
+        .clone()
+        .match_ip_dst(Ipv4Addr::new(10, 0, 0, 2), 32)
+        .is_err());
+    // Not conflicting, merged in the same key
+    assert_eq!(
+        sel.clone()
+            .match_ip_dst(Ipv4Addr::new(10, 0, 0, 0), 24)
+            .unwrap(),
+        sel
+    );
+
+    assert!(TcU32Selector::default()
+        .match_u16(0x50, 0xffff, 3, false)
+        .is_err());
+    assert!(TcU32Selector::default()
+        .match_u32(0x50, 0xffff, 2, false)
+        .is_err());
+
+    let mut sel = TcU32Selector::default();
+    for i in 0..u8::MAX as i32 {
+        sel = sel.match_u32(1, 1, 4 * i, false).unwrap();
+    }
+    assert_eq!(sel.nkeys, u8::MAX);
+    assert!(sel.match_u32(1, 1, 4 * 255, false).is_err());
+}
+
+//      tc filter add dev veth1 parent 1: protocol ip prio 10 u32 \
+//        match mark 0x10 0xff indev veth1 flowid 1:1
+#[test]
+fn test_tcu32_indev_mark_parse_back() {
+    for orig in [
+        TcFilterU32Option::Indev("veth1".to_string()),
+        TcFilterU32Option::Mark(TcU32Mark {
+            val: 0x10,
+            mask: 0xff,
+            success: 0,
+        }),
+    ] {
+        let mut buffer = vec![0; orig.buffer_len()];
+        orig.emit(&mut buffer);
+        let parsed = TcFilterU32Option::parse(
+            &NlaBuffer::new_checked(buffer.as_slice()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(orig, parsed);
+    }
+}
+
+#[test]
+fn test_tcu32_handle() {
+    let handle = TcU32Handle::from(0x80000800);
+    assert_eq!(handle, TcU32Handle::new(0x800, 0, 0x800));
+    assert_eq!(u32::from(TcU32Handle::new(2, 5, 1)), 0x00205001);
+    assert_eq!(handle.to_string(), "800::800");
+    assert_eq!(TcU32Handle::new(2, 5, 1).to_string(), "2:5:1");
+    assert_eq!(TcU32Handle::table(2).to_string(), "2:");
+    assert_eq!(
+        TcHandle::from(handle),
+        TcHandle {
+            major: 0x8000,
+            minor: 0x800,
+        }
+    );
+}
+
+// Same tree as:
+//      tc filter add dev veth1 parent 1: prio 5 handle 2: protocol ip \
+//          u32 divisor 256
+//      tc filter add dev veth1 parent 1: prio 5 protocol ip u32 ht 2:5: \
+//          match ip dst 10.0.0.5/32 flowid 1:10
+//      tc filter add dev veth1 parent 1: prio 5 protocol ip u32 ht 800:: \
+//          match ip protocol 6 0xff hashkey mask 0x000000ff at 16 link 2:
+#[test]
+fn test_tcu32_hash_table_messages() {
+    let header = TcHeader {
+        index: 35,
+        parent: TcHandle { major: 1, minor: 0 },
+        info: (5 << 16) | 0x0008,
+        ..Default::default()
+    };
+    let leaf = TcU32Node {
+        hash: 5,
+        class_id: Some(TcHandle {
+            major: 1,
+            minor: 10,
+        }),
+        ..TcU32Node::new(
+            TcU32Selector::default()
+                .match_ip_dst(Ipv4Addr::new(10, 0, 0, 5), 32)
+                .unwrap()
+                .terminal(),
+        )
+    };
+    let link_sel = TcU32Selector::default()
+        .match_ip_protocol(IpProtocol::Tcp)
+        .unwrap()
+        .hash_key(0x000000ff, 16);
+    let link = TcU32Node {
+        link: Some(TcU32HashTable::new(2, 256, vec![leaf.clone()])),
+        ..TcU32Node::new(link_sel.clone())
+    };
+    assert_eq!(link.bucket(u32::from(Ipv4Addr::new(10, 0, 0, 5))), 5);
+
+    let tree = TcU32HashTable::root(vec![link]);
+    let msgs = tree.to_messages(&header);
+    let options = |msg: &TcMessage| -> Vec<TcFilterU32Option> {
+        msg.attributes
+            .iter()
+            .filter_map(|attr| match attr {
+                TcAttribute::Options(v) => Some(v),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|o| match o {
+                TcOption::U32(o) => Some(o.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    assert_eq!(msgs.len(), 3);
+    assert_eq!(msgs[0].header.handle, TcU32Handle::table(2).into());
+    assert_eq!(options(&msgs[0]), vec![TcFilterU32Option::Divisor(256)]);
+    assert_eq!(msgs[1].header.handle, TcHandle::UNSPEC);
+    assert_eq!(
+        options(&msgs[1]),
+        vec![
+            TcFilterU32Option::Hash(TcU32Handle::new(2, 5, 0)),
+            TcFilterU32Option::ClassId(TcHandle {
+                major: 1,
+                minor: 10
+            }),
+            TcFilterU32Option::Selector(leaf.selector.clone()),
+        ]
+    );
+    assert_eq!(
+        options(&msgs[2]),
+        vec![
+            TcFilterU32Option::Hash(TcU32Handle::table(TcU32Handle::ROOT_HTID)),
+            TcFilterU32Option::Link(TcU32Handle::table(2)),
+            TcFilterU32Option::Selector(link_sel.clone()),
+        ]
+    );
+    for msg in &msgs {
+        assert_eq!(msg.header.info, header.info);
+        assert_eq!(msg.header.parent, header.parent);
+    }
+
+    // Dump of the same tree, as the kernel reports it
+    let dump_msg = |handle: u32, options: Vec<TcFilterU32Option>| {
+        TcMessage::from_parts(
+            TcHeader {
+                handle: handle.into(),
+                ..header.clone()
+            },
+            vec![
+                TcAttribute::Kind("u32".to_string()),
+                TcAttribute::Chain(0),
+                TcAttribute::Options(
+                    options.into_iter().map(TcOption::U32).collect(),
+                ),
+            ],
+        )
+    };
+    let dump = vec![
+        dump_msg(0x00200000, vec![TcFilterU32Option::Divisor(256)]),
+        dump_msg(
+            0x00205800,
+            vec![
+                TcFilterU32Option::Selector(leaf.selector.clone()),
+                TcFilterU32Option::Hash(TcU32Handle::new(2, 5, 0)),
+                TcFilterU32Option::ClassId(TcHandle {
+                    major: 1,
+                    minor: 10,
+                }),
+            ],
+        ),
+        dump_msg(0x80000000, vec![TcFilterU32Option::Divisor(1)]),
+        dump_msg(
+            0x80000800,
+            vec![
+                TcFilterU32Option::Selector(link_sel.clone()),
+                TcFilterU32Option::Hash(TcU32Handle::table(0x800)),
+                TcFilterU32Option::Link(TcU32Handle::table(2)),
+                TcFilterU32Option::Flags(TcU32OptionFlags::NotInHw),
+            ],
+        ),
+    ];
+    let expected = TcU32HashTable::new(
+        0x800,
+        1,
+        vec![TcU32Node {
+            node: 0x800,
+            link: Some(TcU32HashTable::new(
+                2,
+                256,
+                vec![TcU32Node {
+                    node: 0x800,
+                    ..leaf
+                }],
+            )),
+            options: vec![TcFilterU32Option::Flags(TcU32OptionFlags::NotInHw)],
+            ..TcU32Node::new(link_sel)
+        }],
+    );
+    assert_eq!(
+        TcU32HashTable::from_messages(&dump).unwrap(),
+        vec![expected]
+    );
+}
+
+// Same as:
+//      tc filter add dev veth1 parent 1: prio 5 handle ::10 protocol ip \
+//          u32 ht 800:: match ip protocol 17 0xff flowid 1:20
+#[test]
+fn test_tcu32_hash_table_messages_root_node_id() {
+    let header = TcHeader {
+        index: 35,
+        parent: TcHandle { major: 1, minor: 0 },
+        info: (5 << 16) | 0x0008,
+        ..Default::default()
+    };
+    let tree = TcU32HashTable::root(vec![TcU32Node {
+        node: 0x10,
+        class_id: Some(TcHandle {
+            major: 1,
+            minor: 0x20,
+        }),
+        ..TcU32Node::new(
+            TcU32Selector::default()
+                .match_ip_protocol(IpProtocol::Udp)
+                .unwrap(),
+        )
+    }]);
+    let msgs = tree.to_messages(&header);
+
+    assert_eq!(msgs.len(), 1);
+    // The kernel picks the real root hash table id
+    assert_eq!(msgs[0].header.handle, TcU32Handle::new(0, 0, 0x10).into());
+    assert!(msgs[0].attributes.contains(&TcAttribute::Options(vec![
+        TcOption::U32(TcFilterU32Option::Hash(TcU32Handle::table(
+            TcU32Handle::ROOT_HTID
+        ))),
+        TcOption::U32(TcFilterU32Option::ClassId(TcHandle {
+            major: 1,
+            minor: 0x20
+        })),
+        TcOption::U32(TcFilterU32Option::Selector(
+            tree.nodes[0].selector.clone()
+        )),
+    ])));
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MIT
+
+use std::str::FromStr;
+
+use crate::{
+    tc::{TcFilterInfo, TcHandle, TcHeader},
+    EthernetProtocol,
+};
+
+#[test]
+fn test_tc_handle_from_str() {
+    for (s, expected) in [
+        ("1:10", TcHandle::new(1, 0x10)),
+        ("ffff:", TcHandle::from_major(0xffff)),
+        (":a", TcHandle::from_minor(0xa)),
+        ("10020", TcHandle::new(1, 0x20)),
+        ("root", TcHandle::ROOT),
+        ("none", TcHandle::UNSPEC),
+        ("ingress", TcHandle::INGRESS),
+        ("clsact", TcHandle::CLSACT),
+        ("ffff:fff2", TcHandle::CLSACT_INGRESS),
+    ] {
+        assert_eq!(TcHandle::from_str(s).unwrap(), expected, "{s}");
+    }
+    for s in ["", "1:2:3", "10000:", "g:1", "x", "egress"] {
+        assert!(TcHandle::from_str(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn test_tc_handle_display() {
+    for (handle, expected) in [
+        (TcHandle::new(1, 0x10), "1:10"),
+        (TcHandle::from_major(0xffff), "ffff:"),
+        (TcHandle::from_minor(0xa), ":a"),
+        (TcHandle::ROOT, "root"),
+        (TcHandle::UNSPEC, "none"),
+        (TcHandle::INGRESS, "ffff:fff1"),
+        (TcHandle::CLSACT_EGRESS, "ffff:fff3"),
+    ] {
+        assert_eq!(handle.to_string(), expected);
+        assert_eq!(TcHandle::from_str(expected).unwrap(), handle);
+    }
+}
+
+#[test]
+fn test_tc_header_clsact() {
+    let ingress = TcHeader::clsact_ingress(3);
+    assert_eq!(ingress.index, 3);
+    assert_eq!(u32::from(ingress.parent), 0xfffffff2);
+    assert_eq!(u32::from(TcHeader::clsact_egress(3).parent), 0xfffffff3);
+}
+
+#[test]
+fn test_tc_filter_info() {
+    // info of `tc filter add dev veth1 parent 1: prio 4 protocol ip ...`
+    let info = TcFilterInfo::from(0x40008);
+    assert_eq!(info, TcFilterInfo::new(4, EthernetProtocol::Ip));
+    assert_eq!(info.to_string(), "prio 4 protocol ip");
+    assert_eq!(u32::from(info), 0x40008);
+
+    // RTM_GETTFILTER of `tc filter show dev X ingress protocol ipv6`
+    let header = TcHeader::clsact_ingress(3)
+        .with_filter_info(TcFilterInfo::new(0, EthernetProtocol::Ipv6));
+    assert_eq!(header.info, 0xdd86);
+    assert_eq!(header.filter_info().protocol, EthernetProtocol::Ipv6);
+    assert_eq!(header.filter_info().priority, 0);
+}
@@ -1,14 +1,44 @@
 // SPDX-License-Identifier: MIT
 
+#[cfg(test)]
+mod chain;
+#[cfg(test)]
+mod class_xstats;
+#[cfg(test)]
+mod filter_basic;
 #[cfg(test)]
 mod filter_bpf;
 #[cfg(test)]
+mod filter_cgroup;
+#[cfg(test)]
+mod filter_flow;
+#[cfg(test)]
 mod filter_flower;
 #[cfg(test)]
+mod filter_fw;
+#[cfg(test)]
 mod filter_matchall;
 #[cfg(test)]
+mod filter_route;
+#[cfg(test)]
 mod filter_u32;
 #[cfg(test)]
+mod handle;
+#[cfg(test)]
 mod qdisc_fq_codel;
 #[cfg(test)]
+mod qdisc_hhf;
+#[cfg(test)]
 mod qdisc_ingress;
+#[cfg(test)]
+mod qdisc_mq;
+#[cfg(test)]
+mod qdisc_multiq;
+#[cfg(test)]
+mod qdisc_plug;
+#[cfg(test)]
+mod qdisc_skbprio;
+#[cfg(test)]
+mod stab;
+#[cfg(test)]
+mod stats2;
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcHandle, TcHeader, TcHhfXstats, TcMessage, TcOption,
+        TcQdiscHhfOption, TcStats2, TcXstats,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: hhf
+//
+// Hand-built reply to:
+//
+//      tc -s qdisc show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+//   * Only the TCA_STATS_APP of TCA_STATS2 is kept.
+#[test]
+fn test_get_qdisc_hhf() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x02, 0x00, 0x00, 0x00, // info(refcount): 2
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_KIND
+        0x68, 0x68, 0x66, 0x00, // "hhf\0"
+        0x3c, 0x00, // length 60
+        0x02, 0x00, // TCA_OPTIONS
+        0x08, 0x00, // length 8
+        0x01, 0x00, // TCA_HHF_BACKLOG_LIMIT
+        0xe8, 0x03, 0x00, 0x00, // 1000
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_HHF_QUANTUM
+        0xea, 0x05, 0x00, 0x00, // 1514
+        0x08, 0x00, // length 8
+        0x03, 0x00, // TCA_HHF_HH_FLOWS_LIMIT
+        0x00, 0x08, 0x00, 0x00, // 2048
+        0x08, 0x00, // length 8
+        0x04, 0x00, // TCA_HHF_RESET_TIMEOUT
+        0x40, 0x9c, 0x00, 0x00, // 40000
+        0x08, 0x00, // length 8
+        0x05, 0x00, // TCA_HHF_ADMIT_BYTES
+        0x00, 0x00, 0x02, 0x00, // 131072
+        0x08, 0x00, // length 8
+        0x06, 0x00, // TCA_HHF_EVICT_TIMEOUT
+        0x40, 0x42, 0x0f, 0x00, // 1000000
+        0x08, 0x00, // length 8
+        0x07, 0x00, // TCA_HHF_NON_HH_WEIGHT
+        0x02, 0x00, 0x00, 0x00, // 2
+        0x18, 0x00, // length 24
+        0x07, 0x00, // TCA_STATS2
+        0x14, 0x00, // length 20
+        0x04, 0x00, // TCA_STATS_APP
+        0x00, 0x00, 0x00, 0x00, // drop_overlimit: 0
+        0x01, 0x00, 0x00, 0x00, // hh_overlimit: 1
+        0x05, 0x00, 0x00, 0x00, // hh_tot_count: 5
+        0x02, 0x00, 0x00, 0x00, // hh_cur_count: 2
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            info: 2,
+        },
+        attributes: vec![
+            TcAttribute::Kind("hhf".to_string()),
+            TcAttribute::Options(vec![
+                TcOption::Hhf(TcQdiscHhfOption::BacklogLimit(1000)),
+                TcOption::Hhf(TcQdiscHhfOption::Quantum(1514)),
+                TcOption::Hhf(TcQdiscHhfOption::HhFlowsLimit(2048)),
+                TcOption::Hhf(TcQdiscHhfOption::ResetTimeout(40000)),
+                TcOption::Hhf(TcQdiscHhfOption::AdmitBytes(131072)),
+                TcOption::Hhf(TcQdiscHhfOption::EvictTimeout(1000000)),
+                TcOption::Hhf(TcQdiscHhfOption::NonHhWeight(2)),
+            ]),
+            TcAttribute::Stats2(vec![TcStats2::App(TcXstats::Hhf(
+                TcHhfXstats {
+                    drop_overlimit: 0,
+                    hh_overlimit: 1,
+                    hh_tot_count: 5,
+                    hh_cur_count: 2,
+                },
+            ))]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcHandle, TcHeader, TcMessage, TcQdiscMq, TcStats,
+        TcStats2, TcStatsBasic, TcStatsQueue,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      ip link add eth1 numtxqueues 2 type veth peer eth1.peer
+//      tc qdisc add dev eth1 root handle 8001: mq
+//      tc qdisc replace dev eth1 parent 8001:2 handle 20: fq_codel
+//
+// Hand-built reply to:
+//
+//      tc -s class show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+//   * Only the class of the second transmit queue is kept.
+#[test]
+fn test_get_class_mq() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x05, 0x00, 0x00, 0x00, // iface index: 5
+        0x02, 0x00, 0x01, 0x80, // handle 8001:2
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x00, 0x00, 0x20, 0x00, // info: child qdisc handle 20:
+        0x07, 0x00, // length 7
+        0x01, 0x00, // TCA_KIND
+        0x6d, 0x71, 0x00, 0x00, // "mq\0" and 1 byte pad
+        0x30, 0x00, // length 48
+        0x07, 0x00, // TCA_STATS2
+        0x14, 0x00, // length 20
+        0x01, 0x00, // TCA_STATS_BASIC
+        0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes: 1000
+        0x0a, 0x00, 0x00, 0x00, // packets: 10
+        0x00, 0x00, 0x00, 0x00, // padding
+        0x18, 0x00, // length 24
+        0x03, 0x00, // TCA_STATS_QUEUE
+        0x00, 0x00, 0x00, 0x00, // qlen: 0
+        0x00, 0x00, 0x00, 0x00, // backlog: 0
+        0x01, 0x00, 0x00, 0x00, // drops: 1
+        0x00, 0x00, 0x00, 0x00, // requeues: 0
+        0x00, 0x00, 0x00, 0x00, // overlimits: 0
+        0x2c, 0x00, // length 44
+        0x03, 0x00, // TCA_STATS
+        0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes: 1000
+        0x0a, 0x00, 0x00, 0x00, // packets: 10
+        0x01, 0x00, 0x00, 0x00, // drops: 1
+        0x00, 0x00, 0x00, 0x00, // overlimits: 0
+        0x00, 0x00, 0x00, 0x00, // bps: 0
+        0x00, 0x00, 0x00, 0x00, // pps: 0
+        0x00, 0x00, 0x00, 0x00, // qlen: 0
+        0x00, 0x00, 0x00, 0x00, // backlog: 0
+        0x00, 0x00, 0x00, 0x00, // padding
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 5,
+            handle: TcHandle {
+                major: 0x8001,
+                minor: 2,
+            },
+            parent: TcHandle::ROOT,
+            info: 0x200000,
+        },
+        attributes: vec![
+            TcAttribute::Kind("mq".to_string()),
+            TcAttribute::Stats2(vec![
+                TcStats2::Basic(TcStatsBasic {
+                    bytes: 1000,
+                    packets: 10,
+                }),
+                TcStats2::Queue(TcStatsQueue {
+                    qlen: 0,
+                    backlog: 0,
+                    drops: 1,
+                    requeues: 0,
+                    overlimits: 0,
+                }),
+            ]),
+            TcAttribute::Stats(TcStats {
+                bytes: 1000,
+                packets: 10,
+                drops: 1,
+                overlimits: 0,
+                bps: 0,
+                pps: 0,
+                qlen: 0,
+                backlog: 0,
+            }),
+        ],
+    };
+
+    let parsed = TcMessage::parse(&raw).unwrap();
+    assert_eq!(expected, parsed);
+
+    assert_eq!(TcQdiscMq::class_queue_index(&parsed.header), Some(1));
+    assert_eq!(
+        TcQdiscMq::class_child(&parsed.header),
+        TcHandle {
+            major: 0x20,
+            minor: 0
+        }
+    );
+    assert_eq!(
+        TcQdiscMq::class_header(
+            5,
+            TcHandle {
+                major: 0x8001,
+                minor: 0
+            },
+            1,
+            TcHandle {
+                major: 0x20,
+                minor: 0
+            },
+        ),
+        expected.header
+    );
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{TcAttribute, TcHandle, TcHeader, TcMessage, TcMultiqQopt, TcOption},
+    AddressFamily,
+};
+
+// Setup:
+//      ip link add eth1 numtxqueues 4 type veth peer eth1.peer
+//      tc qdisc add dev eth1 root handle 1: multiq
+//
+// Hand-built reply to:
+//
+//      tc qdisc show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_qdisc_multiq() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x02, 0x00, 0x00, 0x00, // info(refcount): 2
+        0x0b, 0x00, // length 11
+        0x01, 0x00, // TCA_KIND
+        0x6d, 0x75, 0x6c, 0x74, 0x69, 0x71, 0x00,
+        0x00, // "multiq\0" and 1 byte pad
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_OPTIONS as `struct tc_multiq_qopt`
+        0x04, 0x00, // bands: 4
+        0x04, 0x00, // max_bands: 4
+        0x05, 0x00, // length 5
+        0x0c, 0x00, // TCA_HW_OFFLOAD
+        0x00, 0x00, 0x00, 0x00, // 0 with padding
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            info: 2,
+        },
+        attributes: vec![
+            TcAttribute::Kind("multiq".to_string()),
+            TcAttribute::Options(vec![TcOption::Multiq(TcMultiqQopt {
+                bands: 4,
+                max_bands: 4,
+            })]),
+            TcAttribute::HwOffload(0),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
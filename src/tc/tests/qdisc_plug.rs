@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::tc::{
+    TcAttribute, TcHandle, TcHeader, TcMessage, TcOption, TcPlugAction,
+    TcPlugQopt,
+};
+
+// The kernel never dumps the plug options, hence only check what
+//      tc qdisc change dev eth1 root handle 1: plug release_one
+// would send.
+#[test]
+fn test_change_qdisc_plug_release_one() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x00, 0x00, 0x00, 0x00, // info: 0
+        0x09, 0x00, // length 9
+        0x01, 0x00, // TCA_KIND
+        0x70, 0x6c, 0x75, 0x67, 0x00, 0x00, 0x00,
+        0x00, // "plug\0" and 3 bytes pad
+        0x0c, 0x00, // length 12
+        0x02, 0x00, // TCA_OPTIONS as `struct tc_plug_qopt`
+        0x01, 0x00, 0x00, 0x00, // action: TCQ_PLUG_RELEASE_ONE
+        0x00, 0x00, 0x00, 0x00, // limit: 0
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            ..Default::default()
+        },
+        attributes: vec![
+            TcAttribute::Kind("plug".to_string()),
+            TcAttribute::Options(vec![TcOption::Plug(TcPlugQopt {
+                action: TcPlugAction::ReleaseOne,
+                limit: 0,
+            })]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
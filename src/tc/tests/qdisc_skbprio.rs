@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::{
+    tc::{TcAttribute, TcHandle, TcHeader, TcMessage, TcOption, TcSkbprioQopt},
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: skbprio limit 128
+//
+// Hand-built reply to:
+//
+//      tc qdisc show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_get_qdisc_skbprio() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x02, 0x00, 0x00, 0x00, // info(refcount): 2
+        0x0c, 0x00, // length 12
+        0x01, 0x00, // TCA_KIND
+        0x73, 0x6b, 0x62, 0x70, 0x72, 0x69, 0x6f, 0x00, // "skbprio\0"
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_OPTIONS as `struct tc_skbprio_qopt`
+        0x80, 0x00, 0x00, 0x00, // limit: 128
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            info: 2,
+        },
+        attributes: vec![
+            TcAttribute::Kind("skbprio".to_string()),
+            TcAttribute::Options(vec![TcOption::Skbprio(TcSkbprioQopt {
+                limit: 128,
+            })]),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
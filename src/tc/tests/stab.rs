@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{Emitable, Parseable};
+
+use crate::tc::{
+    TcAttribute, TcEstimator, TcHandle, TcHeader, TcLinkLayer, TcMessage,
+    TcOption, TcSizeSpec, TcSkbprioQopt, TcStab,
+};
+
+// Hand-built reply to:
+//
+//      tc qdisc add dev eth1 root handle 1: estimator 1sec 8sec \
+//          stab overhead 24 linklayer ethernet skbprio limit 64
+//
+// Message content:
+//   * rtnetlink header omitted.
+#[test]
+fn test_add_qdisc_with_rate_and_stab() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x00, 0x00, 0x00, 0x00, // info: 0
+        0x0c, 0x00, // length 12
+        0x01, 0x00, // TCA_KIND
+        0x73, 0x6b, 0x62, 0x70, 0x72, 0x69, 0x6f, 0x00, // "skbprio\0"
+        0x06, 0x00, // length 6
+        0x05, 0x00, // TCA_RATE
+        0x00, // interval: 0 (1 second)
+        0x03, // ewma_log: 3
+        0x00, 0x00, // padding
+        0x08, 0x00, // length 8
+        0x02, 0x00, // TCA_OPTIONS as `struct tc_skbprio_qopt`
+        0x40, 0x00, 0x00, 0x00, // limit: 64
+        0x20, 0x00, // length 32
+        0x08, 0x00, // TCA_STAB
+        0x1c, 0x00, // length 28
+        0x01, 0x00, // TCA_STAB_BASE
+        0x00, // cell_log: 0
+        0x00, // size_log: 0
+        0x00, 0x00, // cell_align: 0
+        0x18, 0x00, 0x00, 0x00, // overhead: 24
+        0x01, 0x00, 0x00, 0x00, // linklayer: TC_LINKLAYER_ETHERNET
+        0x00, 0x00, 0x00, 0x00, // mpu: 0
+        0x00, 0x00, 0x00, 0x00, // mtu: 0
+        0x00, 0x00, 0x00, 0x00, // tsize: 0
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            ..Default::default()
+        },
+        attributes: vec![
+            TcAttribute::Kind("skbprio".to_string()),
+            TcAttribute::Rate(TcEstimator {
+                interval: 0,
+                ewma_log: 3,
+            }),
+            TcAttribute::Options(vec![TcOption::Skbprio(TcSkbprioQopt {
+                limit: 64,
+            })]),
+            TcAttribute::Stab(
+                TcStab::from_size_spec(TcSizeSpec {
+                    overhead: 24,
+                    linklayer: TcLinkLayer::Ethernet,
+                    ..Default::default()
+                })
+                .unwrap(),
+            ),
+        ],
+    };
+
+    assert_eq!(expected, TcMessage::parse(&raw).unwrap());
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+#[test]
+fn test_stab_atm_size_table() {
+    let stab = TcStab::from_size_spec(TcSizeSpec {
+        linklayer: TcLinkLayer::Atm,
+        mtu: 512,
+        tsize: 8,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        stab,
+        vec![
+            TcStab::Base(TcSizeSpec {
+                cell_log: 7,
+                size_log: 0,
+                cell_align: -1,
+                overhead: 0,
+                linklayer: TcLinkLayer::Atm,
+                mpu: 0,
+                mtu: 512,
+                tsize: 8,
+            }),
+            // Every 128 bytes slot rounded up to 53 bytes ATM cells carrying
+            // 48 bytes of payload each.
+            TcStab::Data(vec![159, 318, 424, 583, 742, 848, 1007, 1166]),
+        ]
+    );
+}
+
+#[test]
+fn test_stab_ethernet_mpu_size_table() {
+    let mut spec = TcSizeSpec {
+        linklayer: TcLinkLayer::Ethernet,
+        mpu: 300,
+        tsize: 4,
+        mtu: 512,
+        ..Default::default()
+    };
+    assert_eq!(spec.calc_size_table().unwrap(), vec![300, 512, 768, 1024]);
+    assert_eq!(spec.cell_log, 8);
+    assert_eq!(spec.cell_align, -1);
+}
+
+#[test]
+fn test_stab_size_log_overflow() {
+    let mut spec = TcSizeSpec {
+        linklayer: TcLinkLayer::Ethernet,
+        mpu: 1,
+        tsize: 2,
+        mtu: 200000,
+        ..Default::default()
+    };
+    assert_eq!(spec.calc_size_table().unwrap(), vec![16384, 32768]);
+    assert_eq!(spec.cell_log, 17);
+    assert_eq!(spec.size_log, 3);
+}
+
+#[test]
+fn test_stab_mtu_too_large_for_tsize() {
+    let mut spec = TcSizeSpec {
+        linklayer: TcLinkLayer::Ethernet,
+        mpu: 1,
+        tsize: 1,
+        mtu: 1 << 31,
+        ..Default::default()
+    };
+    assert!(spec.calc_size_table().is_err());
+
+    spec.mtu = (1 << 31) - 1;
+    assert_eq!(spec.calc_size_table().unwrap(), vec![32768]);
+    assert_eq!(spec.cell_log, 31);
+    assert_eq!(spec.size_log, 16);
+}
+
+#[test]
+fn test_stab_atm_large_mtu() {
+    let mut spec = TcSizeSpec {
+        linklayer: TcLinkLayer::Atm,
+        tsize: 2,
+        mtu: u32::MAX,
+        ..Default::default()
+    };
+    assert_eq!(spec.calc_size_table().unwrap(), vec![18090, 36181]);
+    assert_eq!(spec.cell_log, 31);
+    assert_eq!(spec.size_log, 17);
+}
+
+#[test]
+fn test_stab_tsize_too_large() {
+    assert_eq!(TcSizeSpec::MAX_TSIZE, 32748);
+
+    let mut spec = TcSizeSpec {
+        linklayer: TcLinkLayer::Atm,
+        tsize: u32::MAX,
+        ..Default::default()
+    };
+    assert!(spec.calc_size_table().is_err());
+
+    spec.tsize = TcSizeSpec::MAX_TSIZE;
+    let stab = TcStab::from_size_spec(spec).unwrap();
+    let len = stab.as_slice().buffer_len();
+    assert!(len + 4 <= u16::MAX as usize, "{len}");
+}
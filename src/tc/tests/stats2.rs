@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{DefaultNla, Emitable, Parseable};
+
+use crate::{
+    tc::{
+        TcAttribute, TcHandle, TcHeader, TcMessage, TcStats2, TcStatsBasic,
+        TcStatsBasic64, TcStatsQueue, TcStatsRateEst, TcStatsRateEst64,
+    },
+    AddressFamily,
+};
+
+// Setup:
+//      tc qdisc add dev eth1 root handle 1: est 1sec 8sec noqueue
+//
+// Hand-built reply, after more than 2^32 packets went through at more
+// than 4GB/s, to:
+//
+//      tc -s qdisc show dev eth1
+//
+// Message content:
+//   * rtnetlink header omitted.
+//   * Only TCA_KIND and TCA_STATS2 are kept.
+#[test]
+fn test_get_qdisc_stats2_64bits() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x02, 0x00, 0x00, 0x00, // info(refcount): 2
+        0x0c, 0x00, // length 12
+        0x01, 0x00, // TCA_KIND
+        0x6e, 0x6f, 0x71, 0x75, 0x65, 0x75, 0x65, 0x00, // "noqueue\0"
+        0x5c, 0x00, // length 92
+        0x07, 0x00, // TCA_STATS2
+        0x14, 0x00, // length 20
+        0x01, 0x00, // TCA_STATS_BASIC
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, // bytes: 2^41
+        0x05, 0x00, 0x00, 0x00, // packets: 5
+        0x00, 0x00, 0x00, 0x00, // padding
+        0x0c, 0x00, // length 12
+        0x08, 0x00, // TCA_STATS_PKT64
+        0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, // 2^32 + 5
+        0x0c, 0x00, // length 12
+        0x02, 0x00, // TCA_STATS_RATE_EST
+        0xff, 0xff, 0xff, 0xff, // bps: clamped to u32::MAX
+        0xe8, 0x03, 0x00, 0x00, // pps: 1000
+        0x14, 0x00, // length 20
+        0x05, 0x00, // TCA_STATS_RATE_EST64
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, // bps: 2^32
+        0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // pps: 1000
+        0x18, 0x00, // length 24
+        0x03, 0x00, // TCA_STATS_QUEUE
+        0x00, 0x00, 0x00, 0x00, // qlen: 0
+        0x00, 0x00, 0x00, 0x00, // backlog: 0
+        0x00, 0x00, 0x00, 0x00, // drops: 0
+        0x00, 0x00, 0x00, 0x00, // requeues: 0
+        0x00, 0x00, 0x00, 0x00, // overlimits: 0
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            info: 2,
+        },
+        attributes: vec![
+            TcAttribute::Kind("noqueue".to_string()),
+            TcAttribute::Stats2(vec![
+                TcStats2::Basic(TcStatsBasic {
+                    bytes: 1 << 41,
+                    packets: 5,
+                }),
+                TcStats2::Pkt64((1 << 32) + 5),
+                TcStats2::RateEst(TcStatsRateEst {
+                    bps: u32::MAX,
+                    pps: 1000,
+                }),
+                TcStats2::RateEst64(TcStatsRateEst64 {
+                    bps: 1 << 32,
+                    pps: 1000,
+                }),
+                TcStats2::Queue(TcStatsQueue::default()),
+            ]),
+        ],
+    };
+
+    let parsed = TcMessage::parse(&raw).unwrap();
+    assert_eq!(expected, parsed);
+
+    let TcAttribute::Stats2(stats) = &parsed.attributes[1] else {
+        panic!("no TCA_STATS2 in {parsed:?}");
+    };
+    assert_eq!(
+        TcStats2::basic64(stats),
+        Some(TcStatsBasic64 {
+            bytes: 1 << 41,
+            packets: (1 << 32) + 5,
+        })
+    );
+    assert_eq!(TcStats2::basic_hw64(stats), None);
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}
+
+// Same as above, with a zero length TCA_STATS_PAD attribute, as inserted by
+// nla_put_64bit() to align its payload, before TCA_STATS_PKT64 and with a
+// TCA_STATS_BASIC_HW not followed by any TCA_STATS_PKT64.
+#[test]
+fn test_get_qdisc_stats2_pkt64_after_pad() {
+    let raw = vec![
+        0x00, // AF_UNSPEC
+        0x00, 0x00, 0x00, // padding
+        0x06, 0x00, 0x00, 0x00, // iface index: 6
+        0x00, 0x00, 0x01, 0x00, // handle 1:0
+        0xff, 0xff, 0xff, 0xff, // parent TC_H_ROOT
+        0x02, 0x00, 0x00, 0x00, // info(refcount): 2
+        0x0c, 0x00, // length 12
+        0x01, 0x00, // TCA_KIND
+        0x6e, 0x6f, 0x71, 0x75, 0x65, 0x75, 0x65, 0x00, // "noqueue\0"
+        0x58, 0x00, // length 88
+        0x07, 0x00, // TCA_STATS2
+        0x14, 0x00, // length 20
+        0x01, 0x00, // TCA_STATS_BASIC
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, // bytes: 2^41
+        0x05, 0x00, 0x00, 0x00, // packets: 5
+        0x00, 0x00, 0x00, 0x00, // padding
+        0x04, 0x00, // length 4
+        0x06, 0x00, // TCA_STATS_PAD
+        0x0c, 0x00, // length 12
+        0x08, 0x00, // TCA_STATS_PKT64
+        0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, // 2^32 + 5
+        0x14, 0x00, // length 20
+        0x07, 0x00, // TCA_STATS_BASIC_HW
+        0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes: 100
+        0x01, 0x00, 0x00, 0x00, // packets: 1
+        0x00, 0x00, 0x00, 0x00, // padding
+        0x04, 0x00, // length 4
+        0x06, 0x00, // TCA_STATS_PAD
+        0x18, 0x00, // length 24
+        0x03, 0x00, // TCA_STATS_QUEUE
+        0x00, 0x00, 0x00, 0x00, // qlen: 0
+        0x00, 0x00, 0x00, 0x00, // backlog: 0
+        0x00, 0x00, 0x00, 0x00, // drops: 0
+        0x00, 0x00, 0x00, 0x00, // requeues: 0
+        0x00, 0x00, 0x00, 0x00, // overlimits: 0
+    ];
+
+    let expected = TcMessage {
+        header: TcHeader {
+            family: AddressFamily::Unspec,
+            index: 6,
+            handle: TcHandle { major: 1, minor: 0 },
+            parent: TcHandle::ROOT,
+            info: 2,
+        },
+        attributes: vec![
+            TcAttribute::Kind("noqueue".to_string()),
+            TcAttribute::Stats2(vec![
+                TcStats2::Basic(TcStatsBasic {
+                    bytes: 1 << 41,
+                    packets: 5,
+                }),
+                TcStats2::Other(DefaultNla::new(6, vec![])),
+                TcStats2::Pkt64((1 << 32) + 5),
+                TcStats2::BasicHw(TcStatsBasic {
+                    bytes: 100,
+                    packets: 1,
+                }),
+                TcStats2::Other(DefaultNla::new(6, vec![])),
+                TcStats2::Queue(TcStatsQueue::default()),
+            ]),
+        ],
+    };
+
+    let parsed = TcMessage::parse(&raw).unwrap();
+    assert_eq!(expected, parsed);
+
+    let TcAttribute::Stats2(stats) = &parsed.attributes[1] else {
+        panic!("no TCA_STATS2 in {parsed:?}");
+    };
+    assert_eq!(
+        TcStats2::basic64(stats),
+        Some(TcStatsBasic64 {
+            bytes: 1 << 41,
+            packets: (1 << 32) + 5,
+        })
+    );
+    assert_eq!(
+        TcStats2::basic_hw64(stats),
+        Some(TcStatsBasic64 {
+            bytes: 100,
+            packets: 1,
+        })
+    );
+
+    let mut buf = vec![0; expected.buffer_len()];
+
+    expected.emit(&mut buf);
+
+    assert_eq!(buf, raw);
+}